@@ -445,6 +445,10 @@ match 表达式：
 - 利用编译器检查确保处理所有情况
 - 合理使用 if let 简化单一模式匹配
 
+实际应用：
+- Web 项目的 /events 接口把 Event 枚举序列化成 JSON，
+  通过 Server-Sent Events 实时推送（见 projects/web/sse.rs）
+
 编译运行：
 cargo run --bin enums
 */
@@ -4,6 +4,13 @@
 use std::time::Duration;
 use tokio::time::sleep;
 
+// 复用 Web 项目中的 HTTP 客户端；这里只用到其中一部分功能
+#[path = "projects/web/client.rs"]
+#[allow(dead_code)]
+mod http_client;
+
+use http_client::{Client, ClientConfig};
+
 // 异步主函数
 #[tokio::main]
 async fn main() {
@@ -228,28 +235,39 @@ async fn main() {
     // ========== 实际应用示例 ==========
     println!("\n========== 实际应用示例 ==========");
 
-    // 11. 并发HTTP请求模拟
-    println!("\n11. 模拟并发网络请求：");
+    // 11. 并发HTTP请求
+    println!("\n11. 并发网络请求：");
 
-    let urls = vec![
-        "https://api.example.com/users",
-        "https://api.example.com/posts",
-        "https://api.example.com/comments",
-    ];
+    // 先在本机启动一个小型 HTTP 服务器，再用真正的 HTTP 客户端访问它
+    let addr = match start_local_server().await {
+        Ok(addr) => addr,
+        Err(e) => {
+            println!("无法启动本地服务器: {}", e);
+            return;
+        }
+    };
+    println!("本地服务器运行在 http://{}", addr);
+
+    // 客户端内部用互斥锁保存空闲连接，可以放进 Arc 在任务之间共享
+    let client = Arc::new(Client::new(ClientConfig::default()));
+    let paths = vec!["/users", "/posts", "/comments"];
 
     let start = std::time::Instant::now();
 
     let mut handles = Vec::new();
-    for (i, url) in urls.into_iter().enumerate() {
-        let handle = tokio::spawn(async move { simulate_http_request(url, 200 + i * 100).await });
+    for (i, path) in paths.into_iter().enumerate() {
+        let url = format!("http://{}{}?delay={}", addr, path, 200 + i * 100);
+        let client = Arc::clone(&client);
+        let handle = tokio::spawn(async move { http_get(client, url).await });
         handles.push(handle);
     }
 
     let mut responses = Vec::new();
     for handle in handles {
         match handle.await {
-            Ok(response) => responses.push(response),
-            Err(e) => println!("请求失败: {}", e),
+            Ok(Ok(response)) => responses.push(response),
+            Ok(Err(e)) => println!("请求失败: {}", e),
+            Err(e) => println!("任务失败: {}", e),
         }
     }
 
@@ -260,6 +278,13 @@ async fn main() {
     }
     println!("总耗时: {:?}", elapsed);
 
+    // 三个请求同时进行，用了三条连接；完成后它们都留在客户端里等待复用
+    println!("客户端保留的空闲连接: {}", client.idle_connections());
+    match http_get(Arc::clone(&client), format!("http://{}/users", addr)).await {
+        Ok(response) => println!("再次请求（复用已有连接）: {}", response),
+        Err(e) => println!("请求失败: {}", e),
+    }
+
     // ========== 资源管理 ==========
     println!("\n========== 异步资源管理 ==========");
 
@@ -317,15 +342,70 @@ async fn fallible_async_task(id: u32, should_fail: bool) -> Result<String, Strin
     }
 }
 
-// 模拟HTTP请求
-async fn simulate_http_request(url: &str, delay_ms: usize) -> String {
+// 发起 HTTP GET 请求
+// 客户端使用阻塞 I/O，放到 spawn_blocking 的专用线程中执行，避免阻塞异步运行时
+async fn http_get(client: std::sync::Arc<Client>, url: String) -> Result<String, String> {
     println!("  发起请求: {}", url);
-    sleep(Duration::from_millis(delay_ms as u64)).await;
+    let response = tokio::task::spawn_blocking(move || client.get(&url).send())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let result = format!("{} {}", response.status, response.text());
+    println!("  {}", result);
+    Ok(result)
+}
+
+// 启动本地 HTTP 服务器，返回实际监听的地址（端口由系统分配）
+async fn start_local_server() -> std::io::Result<std::net::SocketAddr> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
 
-    // 模拟响应
-    let response = format!("响应来自 {} ({}ms)", url, delay_ms);
-    println!("  {}", response);
-    response
+    // 每个连接一个异步任务
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve_connection(stream));
+        }
+    });
+    Ok(addr)
+}
+
+// 处理一条连接上的多个请求（HTTP/1.1 持久连接）
+// 查询参数 delay 指定响应前等待的毫秒数，用来模拟慢速接口
+async fn serve_connection(stream: tokio::net::TcpStream) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(()); // 客户端关闭了连接
+        }
+
+        // 跳过请求头，直到空行
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).await? == 0 || header.trim_end().is_empty() {
+                break;
+            }
+        }
+
+        let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let delay_ms: u64 = query
+            .strip_prefix("delay=")
+            .and_then(|d| d.parse().ok())
+            .unwrap_or(0);
+        sleep(Duration::from_millis(delay_ms)).await;
+
+        let body = format!("响应来自 {} ({}ms)", path, delay_ms);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        reader.get_mut().write_all(response.as_bytes()).await?;
+    }
 }
 
 // ========== 连接池示例 ==========
@@ -445,7 +525,8 @@ async fn use_connection_from_pool(
 - 异步错误恢复策略
 
 实际应用：
-- HTTP 客户端：并发请求
+- HTTP 客户端：并发请求（本地服务器 + projects/web/client.rs）
+- spawn_blocking：在专用线程中执行阻塞 I/O
 - 数据库连接池：资源管理
 - WebSocket：实时通信
 - 文件 I/O：异步读写
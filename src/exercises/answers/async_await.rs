@@ -0,0 +1,28 @@
+// 练习: 异步编程
+
+use std::cell::Cell;
+
+pub async fn double(x: i32) -> i32 {
+    x * 2
+}
+
+// 依次 await 每个数的 double，返回它们的和
+pub async fn sum_doubled(values: &[i32]) -> i32 {
+    let mut sum = 0;
+    for &value in values {
+        sum += double(value).await;
+    }
+    sum
+}
+
+// async fn 返回的 Future 会借用 name，这里要求 Future 不借用任何参数（'static），
+// 所以先复制出一个 String，再返回拿走它所有权的 async move 块
+pub fn greeting(name: &str) -> impl Future<Output = String> + 'static {
+    let name = name.to_string();
+    async move { format!("你好, {}", name) }
+}
+
+// 计数器加一；函数体在 Future 第一次被 poll 时才执行
+pub async fn increment(counter: &Cell<u32>) {
+    counter.set(counter.get() + 1);
+}
@@ -0,0 +1,52 @@
+// 练习: 并发汇总 (join!)
+
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+use tokio::time;
+
+#[derive(Debug, PartialEq)]
+pub struct Dashboard {
+    pub user: String,
+    pub orders: usize,
+    pub points: u32,
+}
+
+// 三个模拟的查询，分别耗时 100、200、150 毫秒
+pub async fn load_user(id: u32) -> String {
+    time::sleep(Duration::from_millis(100)).await;
+    format!("user-{}", id)
+}
+
+pub async fn load_orders(id: u32) -> Vec<u32> {
+    time::sleep(Duration::from_millis(200)).await;
+    (0..id % 4).map(|n| id * 100 + n).collect()
+}
+
+pub async fn load_points(id: u32) -> u32 {
+    time::sleep(Duration::from_millis(150)).await;
+    id * 10
+}
+
+// 用 tokio::join! 同时发出三个查询，总耗时等于最慢的那个
+pub async fn load_dashboard(id: u32) -> Dashboard {
+    let (user, orders, points) = tokio::join!(load_user(id), load_orders(id), load_points(id));
+    Dashboard {
+        user,
+        orders: orders.len(),
+        points,
+    }
+}
+
+// 为每个 id 启动一个任务查询积分（扇出），再汇总所有任务的结果（扇入）
+pub async fn total_points(ids: &[u32]) -> u32 {
+    let mut tasks = JoinSet::new();
+    for &id in ids {
+        tasks.spawn(load_points(id));
+    }
+    let mut total = 0;
+    while let Some(points) = tasks.join_next().await {
+        total += points.expect("查询任务 panic");
+    }
+    total
+}
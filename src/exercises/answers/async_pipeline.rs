@@ -0,0 +1,36 @@
+// 练习: 有界通道流水线
+
+use tokio::sync::mpsc;
+
+// 生产者：依次把 items 发送到通道；通道满时 send 会等待，接收端关闭后停止
+pub async fn produce(items: Vec<u32>, tx: mpsc::Sender<u32>) {
+    for item in items {
+        if tx.send(item).await.is_err() {
+            break;
+        }
+    }
+}
+
+// 处理阶段：把收到的每个数平方后发送给下一阶段；上游发完或下游关闭时结束
+pub async fn square(mut rx: mpsc::Receiver<u32>, tx: mpsc::Sender<u32>) {
+    while let Some(value) = rx.recv().await {
+        if tx.send(value * value).await.is_err() {
+            break;
+        }
+    }
+}
+
+// 用两个容量为 capacity 的有界通道把 produce → square → 收集 串起来，
+// produce 和 square 各自用 tokio::spawn 运行，按顺序返回收集到的结果
+pub async fn pipeline(items: Vec<u32>, capacity: usize) -> Vec<u32> {
+    let (numbers_tx, numbers_rx) = mpsc::channel(capacity);
+    let (squares_tx, mut squares_rx) = mpsc::channel(capacity);
+    tokio::spawn(produce(items, numbers_tx));
+    tokio::spawn(square(numbers_rx, squares_tx));
+
+    let mut results = Vec::new();
+    while let Some(value) = squares_rx.recv().await {
+        results.push(value);
+    }
+    results
+}
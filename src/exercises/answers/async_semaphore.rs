@@ -0,0 +1,37 @@
+// 练习: 限流抓取 (Semaphore)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time;
+
+// 模拟抓取一个网址：耗时 100 毫秒，返回网址的长度
+pub async fn fetch(url: String) -> usize {
+    time::sleep(Duration::from_millis(100)).await;
+    url.len()
+}
+
+// 为每个网址启动一个任务，但用 Semaphore 保证最多只有 limit 个同时在抓取；
+// 结果按 urls 的顺序返回。limit 为 0 时按 1 处理
+pub async fn fetch_all(urls: &[&str], limit: usize) -> Vec<usize> {
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, url) in urls.iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let url = url.to_string();
+        tasks.spawn(async move {
+            // 许可在任务结束时随 _permit 一起释放
+            let _permit = semaphore.acquire_owned().await.expect("信号量被关闭");
+            (index, fetch(url).await)
+        });
+    }
+
+    let mut results = vec![0; urls.len()];
+    while let Some(result) = tasks.join_next().await {
+        let (index, length) = result.expect("抓取任务 panic");
+        results[index] = length;
+    }
+    results
+}
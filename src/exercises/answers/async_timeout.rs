@@ -0,0 +1,36 @@
+// 练习: 超时控制
+
+use std::time::Duration;
+
+use tokio::time;
+
+#[derive(Debug, PartialEq)]
+pub enum FetchError {
+    Timeout,
+}
+
+// future 在 limit 之内完成时返回它的结果，否则放弃它（drop）并返回 Err(FetchError::Timeout)
+pub async fn with_timeout<F: Future>(limit: Duration, future: F) -> Result<F::Output, FetchError> {
+    time::timeout(limit, future)
+        .await
+        .map_err(|_| FetchError::Timeout)
+}
+
+// 最多尝试 attempts 次，每次都用 make(第几次，从 0 开始) 创建新的 future 并限时 limit；
+// 第一次在时限内完成就返回它的结果，全部超时时返回 Err(FetchError::Timeout)
+pub async fn retry_with_timeout<T, F, Fut>(
+    attempts: u32,
+    limit: Duration,
+    mut make: F,
+) -> Result<T, FetchError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = T>,
+{
+    for attempt in 0..attempts {
+        if let Ok(value) = with_timeout(limit, make(attempt)).await {
+            return Ok(value);
+        }
+    }
+    Err(FetchError::Timeout)
+}
@@ -0,0 +1,10 @@
+// 编译错误谜题: 持有不可变引用时修改 (E0502)
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    // push 可能让 Vec 重新分配内存，指向旧内存的引用就会悬垂；
+    // i32 实现了 Copy，直接复制出值，就不再借用 numbers
+    let first = numbers[0];
+    numbers.push(4);
+    println!("第一个元素: {}, 共 {} 个", first, numbers.len());
+}
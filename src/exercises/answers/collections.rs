@@ -0,0 +1,24 @@
+// 练习: 集合类型
+
+use std::collections::HashMap;
+
+// 把每个元素翻倍
+pub fn double_all(values: &mut [i32]) {
+    for x in values {
+        *x *= 2;
+    }
+}
+
+// 统计每个单词（按空白分隔）出现的次数
+pub fn word_count(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+// 队伍不存在时才插入分数；返回表中该队伍现在的分数
+pub fn insert_if_absent(scores: &mut HashMap<String, i32>, team: &str, score: i32) -> i32 {
+    *scores.entry(team.to_string()).or_insert(score)
+}
@@ -0,0 +1,46 @@
+// 练习: 控制流和模式匹配
+
+pub fn classify(n: u32) -> &'static str {
+    match n {
+        0 => "零",
+        1..=9 => "个位数",
+        10..=99 => "两位数",
+        _ => "很大",
+    }
+}
+
+// 第一个大于 limit 的 2 的幂；loop 通过 break 返回值
+pub fn first_power_of_two_above(limit: u32) -> u32 {
+    let mut n = 1;
+    loop {
+        if n > limit {
+            break n;
+        }
+        n *= 2;
+    }
+}
+
+// 从 from 倒数到 1
+pub fn countdown(from: u32) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    let mut n = from;
+    while n != 0 {
+        numbers.push(n);
+        n -= 1;
+    }
+    numbers
+}
+
+// 返回 (所有 Some 中的值之和, None 的个数)
+pub fn sum_present(values: &[Option<i32>]) -> (i32, usize) {
+    let mut sum = 0;
+    let mut missing = 0;
+    for value in values {
+        if let Some(x) = value {
+            sum += x;
+        } else {
+            missing += 1;
+        }
+    }
+    (sum, missing)
+}
@@ -0,0 +1,37 @@
+// 练习: 枚举和模式匹配
+
+#[derive(Debug)]
+pub enum IpAddr {
+    V4(u8, u8, u8, u8),
+    V6(String),
+}
+
+// IPv4 格式化为 "127.0.0.1"，IPv6 原样返回
+pub fn format_ip(ip: &IpAddr) -> String {
+    match ip {
+        IpAddr::V4(a, b, c, d) => format!("{}.{}.{}.{}", a, b, c, d),
+        IpAddr::V6(addr) => addr.clone(),
+    }
+}
+
+#[derive(Debug)]
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(u8, u8, u8),
+}
+
+pub fn describe(msg: &Message) -> String {
+    match msg {
+        Message::Quit => "退出".to_string(),
+        Message::Move { x, y } => format!("移动到 ({}, {})", x, y),
+        Message::Write(text) => format!("写入: {}", text),
+        Message::ChangeColor(r, g, b) => format!("颜色 RGB({}, {}, {})", r, g, b),
+    }
+}
+
+// None 保持 None，Some(i) 变成 Some(i + 1)；map 等价于对应的 match
+pub fn plus_one(x: Option<i32>) -> Option<i32> {
+    x.map(|i| i + 1)
+}
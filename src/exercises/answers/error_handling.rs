@@ -0,0 +1,39 @@
+// 练习: 错误处理
+
+use std::num::ParseIntError;
+
+pub fn divide(numerator: f64, denominator: f64) -> Result<f64, String> {
+    if denominator == 0.0 {
+        Err(String::from("除数不能为零"))
+    } else {
+        Ok(numerator / denominator)
+    }
+}
+
+// 解析失败时用 ? 把 ParseIntError 原样返回；允许首尾空白
+pub fn parse_and_double(s: &str) -> Result<i32, ParseIntError> {
+    let n: i32 = s.trim().parse()?;
+    Ok(n * 2)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AppError {
+    DivisionByZero,
+    // 无法解析的原始输入
+    InvalidNumber(String),
+}
+
+// 解析两个整数并相除
+pub fn parse_and_divide(a: &str, b: &str) -> Result<i32, AppError> {
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<i32>()
+            .map_err(|_| AppError::InvalidNumber(s.to_string()))
+    };
+    let a = parse(a)?;
+    let b = parse(b)?;
+    if b == 0 {
+        return Err(AppError::DivisionByZero);
+    }
+    Ok(a / b)
+}
@@ -0,0 +1,19 @@
+// 练习: 函数定义和调用
+
+pub fn greet(name: &str) -> String {
+    format!("你好, {}! 欢迎学习 Rust!", name)
+}
+
+// 函数体最后的表达式就是返回值，后面不能加分号
+pub fn add_one(x: i32) -> i32 {
+    x + 1
+}
+
+pub fn factorial(n: u32) -> u32 {
+    if n <= 1 { 1 } else { n * factorial(n - 1) }
+}
+
+// 返回一个把参数乘以 factor 的闭包；move 让闭包拿走 factor 的所有权
+pub fn multiplier(factor: i32) -> impl Fn(i32) -> i32 {
+    move |x| x * factor
+}
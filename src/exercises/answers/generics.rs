@@ -0,0 +1,69 @@
+// 练习: 泛型和Trait
+
+// 最大的元素；空切片返回 None
+pub fn largest<T: PartialOrd + Copy>(list: &[T]) -> Option<T> {
+    let mut items = list.iter().copied();
+    let mut largest = items.next()?;
+    for item in items {
+        if item > largest {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Point<T, U> {
+    pub x: T,
+    pub y: U,
+}
+
+impl<T, U> Point<T, U> {
+    // 取 self 的 x 和 other 的 y 组成新的点；方法可以有自己的泛型参数
+    pub fn mixup<V, W>(self, other: Point<V, W>) -> Point<T, W> {
+        Point {
+            x: self.x,
+            y: other.y,
+        }
+    }
+}
+
+pub trait Summary {
+    fn author(&self) -> String;
+
+    // 默认实现可以调用同一个 Trait 中的其他方法
+    fn summarize(&self) -> String {
+        format!("(阅读更多来自 {} 的内容...)", self.author())
+    }
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+}
+
+// 只实现 author，summarize 使用默认实现
+impl Summary for Tweet {
+    fn author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub author: String,
+}
+
+impl Summary for NewsArticle {
+    fn author(&self) -> String {
+        self.author.clone()
+    }
+
+    fn summarize(&self) -> String {
+        format!("{}, by {}", self.headline, self.author)
+    }
+}
+
+pub fn notify(item: &impl Summary) -> String {
+    format!("突发新闻! {}", item.summarize())
+}
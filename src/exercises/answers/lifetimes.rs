@@ -0,0 +1,30 @@
+// 练习: 生命周期
+
+// 返回较长的字符串，一样长时返回 x；返回值可能来自任何一个参数，所以两个参数共用 'a
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() >= y.len() { x } else { y }
+}
+
+// 返回值只来自 items，与 keyword 无关：keyword 不需要和返回值有相同的生命周期
+pub fn find_containing<'a>(items: &[&'a str], keyword: &str) -> Option<&'a str> {
+    items.iter().copied().find(|item| item.contains(keyword))
+}
+
+// 结构体持有引用时必须标注生命周期：摘录不能比原文活得更久
+#[derive(Debug, PartialEq)]
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    // 原文的第一句话（第一个 '。' 之前的部分）
+    pub fn first_sentence(text: &'a str) -> ImportantExcerpt<'a> {
+        let part = text.split('。').next().unwrap_or(text);
+        ImportantExcerpt { part }
+    }
+
+    // 省略规则：有 &self 时，返回值的生命周期与 self 相同
+    pub fn part(&self) -> &str {
+        self.part
+    }
+}
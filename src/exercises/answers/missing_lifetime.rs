@@ -0,0 +1,13 @@
+// 编译错误谜题: 缺少生命周期标注 (E0106)
+
+// 有两个引用参数时，编译器无法推断返回值借用自哪一个；
+// 返回值可能来自 text 或 fallback，所以两者和返回值都标上同一个 'a
+fn first_line<'a>(text: &'a str, fallback: &'a str) -> &'a str {
+    text.lines().next().unwrap_or(fallback)
+}
+
+fn main() {
+    let article = String::from("Rust 所有权\n每个值都有一个所有者");
+    println!("标题: {}", first_line(&article, "(无标题)"));
+    println!("标题: {}", first_line("", "(无标题)"));
+}
@@ -0,0 +1,42 @@
+// 练习: 模块系统
+
+pub mod math {
+    pub mod basic {
+        pub fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub fn multiply(a: i32, b: i32) -> i32 {
+            a * b
+        }
+    }
+
+    pub mod advanced {
+        pub fn factorial(n: u32) -> u64 {
+            (1..=n as u64).product()
+        }
+
+        pub fn fibonacci(n: u32) -> u64 {
+            let (mut a, mut b) = (0, 1);
+            for _ in 0..n {
+                (a, b) = (b, a + b);
+            }
+            a
+        }
+    }
+}
+
+pub mod utils {
+    // 重新导出：调用者写 utils::reverse，不需要知道私有模块 string_utils 的存在
+    pub use self::string_utils::{is_palindrome, reverse};
+
+    mod string_utils {
+        pub fn reverse(s: &str) -> String {
+            s.chars().rev().collect()
+        }
+
+        pub fn is_palindrome(s: &str) -> bool {
+            s == reverse(s)
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// 练习: 所有权基础
+
+// 函数内创建的 String 通过返回值把所有权交给调用者
+pub fn gives_ownership() -> String {
+    String::from("give ownership")
+}
+
+// 取得所有权，修改后再交还
+pub fn takes_and_gives_back(mut s: String) -> String {
+    s.push_str(" (returned)");
+    s
+}
+
+// 返回两个相互独立的 String：修改其中一个不影响另一个
+pub fn duplicate(s: String) -> (String, String) {
+    (s.clone(), s)
+}
+
+// 交换两个 String：只移动，不复制堆上的数据
+pub fn swap(pair: (String, String)) -> (String, String) {
+    let (a, b) = pair;
+    (b, a)
+}
@@ -0,0 +1,18 @@
+// 练习: 引用和借用
+
+// 不可变引用：只读取，不取得所有权；返回字符数而不是字节数
+pub fn calculate_length(s: &str) -> usize {
+    s.chars().count()
+}
+
+// 可变引用：在调用者的 String 后面追加 ", world"
+pub fn change(s: &mut String) {
+    s.push_str(", world");
+}
+
+// s 在函数返回时被释放，不能返回它的引用（悬垂引用），要把 String 本身交出去
+pub fn dangle() -> String {
+    let mut s = String::from("dangle");
+    s.push_str(" test");
+    s
+}
@@ -0,0 +1,13 @@
+// 编译错误谜题: 借用的值活得不够久 (E0597)
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+fn main() {
+    let string1 = String::from("long string is long");
+    // longest 的返回值可能借用 string2，所以 string2 必须活到最后一次使用 result 的地方
+    let string2 = String::from("xyz");
+    let result = longest(string1.as_str(), string2.as_str());
+    println!("最长的字符串是: '{}'", result);
+}
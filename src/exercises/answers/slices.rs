@@ -0,0 +1,25 @@
+// 练习: 切片操作
+
+// 第一个空格之前的部分；没有空格时返回整个字符串
+pub fn first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+    s
+}
+
+// 去掉第一个和最后一个元素；不足两个元素时返回空切片
+pub fn middle(a: &[i32]) -> &[i32] {
+    if a.len() < 2 { &[] } else { &a[1..a.len() - 1] }
+}
+
+// 前 n 个字符（不是字节）；中文字符占 3 个字节，切在字符中间会 panic
+pub fn prefix_chars(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
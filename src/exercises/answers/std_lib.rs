@@ -0,0 +1,43 @@
+// 练习: 标准库综合应用
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// 偶数的平方和
+pub fn even_square_sum(numbers: &[i32]) -> i32 {
+    numbers
+        .iter()
+        .filter(|&&x| x % 2 == 0)
+        .map(|&x| x * x)
+        .sum()
+}
+
+// 每个单词（按空白分隔）的字符数
+pub fn word_lengths(text: &str) -> Vec<usize> {
+    text.split_whitespace()
+        .map(|word| word.chars().count())
+        .collect()
+}
+
+// 把每一行写入文件（每行以 '\n' 结尾），再按行读回
+pub fn write_lines(path: &Path, lines: &[&str]) -> io::Result<Vec<String>> {
+    let mut content = String::new();
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    fs::write(path, content)?;
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+// dir/name.ext
+pub fn build_path(dir: &str, name: &str, ext: &str) -> PathBuf {
+    let mut path = PathBuf::from(dir);
+    path.push(name);
+    path.set_extension(ext);
+    path
+}
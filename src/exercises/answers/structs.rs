@@ -0,0 +1,40 @@
+// 练习: 结构体和方法
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub username: String,
+    pub email: String,
+    pub age: u32,
+    pub active: bool,
+}
+
+impl User {
+    // 关联函数：新用户默认是激活状态
+    pub fn new(username: &str, email: &str, age: u32) -> User {
+        User {
+            username: username.to_string(),
+            email: email.to_string(),
+            age,
+            active: true,
+        }
+    }
+
+    pub fn is_adult(&self) -> bool {
+        self.age >= 18
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+}
+
+// 元组结构体：RGB 颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    // 例如 Color(255, 0, 16) → "#ff0010"
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
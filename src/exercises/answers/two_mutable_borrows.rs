@@ -0,0 +1,12 @@
+// 编译错误谜题: 同时存在两个可变引用 (E0499)
+
+fn main() {
+    let mut scores = vec![1, 2, 3];
+    // 同一时刻只能有一个可变引用：先用完 first，再创建 last
+    // （也可以用 split_first_mut 一次拿到两个不重叠部分的可变引用）
+    let first = &mut scores[0];
+    *first += 10;
+    let last = scores.last_mut().unwrap();
+    *last += 10;
+    println!("{:?}", scores);
+}
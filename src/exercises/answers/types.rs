@@ -0,0 +1,17 @@
+// 练习: 数据类型和转换
+
+// 解构元组，再按相反的顺序组合成新元组
+pub fn reverse_tuple(t: (i32, f64, bool)) -> (bool, f64, i32) {
+    let (a, b, c) = t;
+    (c, b, a)
+}
+
+// 数组的长度是类型的一部分：[i32; 5] 和 [i32; 4] 是不同的类型
+pub fn array_sum(a: [i32; 5]) -> i32 {
+    a.iter().sum()
+}
+
+// f32 as u8 截断小数部分，超出范围时取边界值；u8 as char 按 ASCII/Latin-1 解释
+pub fn float_to_char(x: f32) -> char {
+    x as u8 as char
+}
@@ -0,0 +1,13 @@
+// 编译错误谜题: 使用已经移动的值 (E0382)
+
+// 只读取长度，借用就够了；参数是 String 时 name 的所有权会移动进函数，之后不能再用
+fn byte_len(s: &str) -> usize {
+    s.len()
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let len = byte_len(&name);
+    println!("{} 有 {} 个字节", name, len);
+    println!("{} 的大写是 {}", name, name.to_uppercase());
+}
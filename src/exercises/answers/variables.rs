@@ -0,0 +1,20 @@
+// 练习: 变量和可变性
+
+// 常量必须标注类型，名字全大写，数字可以用 _ 分隔
+pub const MAX_POINTS: u32 = 100_000;
+
+// 1 + 2 + ... + n，用一个可变变量累加
+pub fn count_up(n: u32) -> u32 {
+    let mut total = 0;
+    for i in 1..=n {
+        total += i;
+    }
+    total
+}
+
+// 去掉首尾空白、转为大写后加上 "!"；每一步都用同一个名字 s 遮蔽上一步的值
+pub fn shout(s: &str) -> String {
+    let s = s.trim();
+    let s = s.to_uppercase();
+    s + "!"
+}
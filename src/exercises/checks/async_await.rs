@@ -0,0 +1,55 @@
+// 检查: 异步编程
+
+use std::cell::Cell;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("async fn", doubles),
+    ("在 async fn 中 await", sums_doubled),
+    ("async 块", greets),
+    ("Future 是惰性的", futures_are_lazy),
+];
+
+// 最简单的执行器：反复 poll 直到完成；这里的 Future 都不等待 I/O，不需要真正的唤醒
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn doubles() {
+    assert_eq!(block_on(double(21)), 42, "double(21)");
+}
+
+fn sums_doubled() {
+    assert_eq!(
+        block_on(sum_doubled(&[1, 2, 3])),
+        12,
+        "sum_doubled(&[1, 2, 3])"
+    );
+    assert_eq!(block_on(sum_doubled(&[])), 0, "sum_doubled(&[])");
+}
+
+fn greets() {
+    // name 在 Future 执行之前就被释放了，所以 Future 不能借用它
+    let future = {
+        let name = String::from("异步");
+        greeting(&name)
+    };
+    assert_eq!(block_on(future), "你好, 异步");
+}
+
+fn futures_are_lazy() {
+    let counter = Cell::new(0);
+    let future = increment(&counter);
+    assert_eq!(counter.get(), 0, "创建 Future 时不应执行函数体");
+    block_on(future);
+    assert_eq!(counter.get(), 1, "poll 之后应执行函数体");
+}
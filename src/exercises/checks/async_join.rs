@@ -0,0 +1,64 @@
+// 检查: 并发汇总 (join!)
+// 用 tokio 的暂停时间运行：sleep 不真的等待，用时是确定的，可以据此判断查询是否并发
+
+use std::time::Duration;
+
+use tokio::runtime;
+use tokio::time::Instant;
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("汇总三个查询", loads_dashboard),
+    ("join! 并发查询", joins_concurrently),
+    ("扇出扇入", fans_out_and_in),
+];
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .expect("无法创建 tokio 运行时")
+        .block_on(future)
+}
+
+fn loads_dashboard() {
+    let dashboard = block_on(load_dashboard(7));
+    assert_eq!(
+        dashboard,
+        Dashboard {
+            user: "user-7".to_string(),
+            orders: 3,
+            points: 70,
+        }
+    );
+}
+
+fn joins_concurrently() {
+    let elapsed = block_on(async {
+        let start = Instant::now();
+        load_dashboard(1).await;
+        start.elapsed()
+    });
+    assert_eq!(
+        elapsed,
+        Duration::from_millis(200),
+        "三个查询应同时进行，总耗时等于最慢的 200ms（依次查询需要 450ms）"
+    );
+}
+
+fn fans_out_and_in() {
+    let (total, elapsed) = block_on(async {
+        let start = Instant::now();
+        let total = total_points(&[1, 2, 3, 4, 5]).await;
+        (total, start.elapsed())
+    });
+    assert_eq!(total, 150, "total_points(&[1, 2, 3, 4, 5])");
+    assert_eq!(
+        elapsed,
+        Duration::from_millis(150),
+        "五个查询应同时进行，总耗时等于一次查询的 150ms"
+    );
+    assert_eq!(block_on(total_points(&[])), 0, "total_points(&[])");
+}
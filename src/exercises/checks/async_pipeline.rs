@@ -0,0 +1,103 @@
+// 检查: 有界通道流水线
+// 用 tokio 的暂停时间运行：等不到的结果会在虚拟时间里超时，而不是让检查卡住
+
+use std::panic;
+use std::time::Duration;
+
+use tokio::runtime;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("流水线结果", runs_pipeline),
+    ("通道满时等待", applies_backpressure),
+    ("接收端关闭后停止", stops_when_receiver_dropped),
+    ("上游结束后结束", finishes_when_upstream_done),
+];
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .expect("无法创建 tokio 运行时")
+        .block_on(future)
+}
+
+// 等待 future，一秒（虚拟时间）内没有完成时检查失败
+async fn within_a_second<F: Future>(future: F, what: &str) -> F::Output {
+    time::timeout(Duration::from_secs(1), future)
+        .await
+        .unwrap_or_else(|_| panic!("{}", what))
+}
+
+// 等待 spawn 出去的阶段结束；它 panic 时把原来的 panic 传出来，报告中显示的是它自己的消息
+async fn finished(stage: JoinHandle<()>, what: &str) {
+    if let Err(e) = within_a_second(stage, what).await {
+        panic::resume_unwind(e.into_panic());
+    }
+}
+
+fn runs_pipeline() {
+    block_on(async {
+        let results = within_a_second(pipeline((1..=10).collect(), 2), "pipeline 没有结束").await;
+        assert_eq!(results, [1, 4, 9, 16, 25, 36, 49, 64, 81, 100]);
+        let results = within_a_second(pipeline(vec![3], 1), "pipeline 没有结束").await;
+        assert_eq!(results, [9]);
+        let results = within_a_second(pipeline(Vec::new(), 1), "pipeline 没有结束").await;
+        assert!(results.is_empty(), "没有输入时结果为空");
+    });
+}
+
+fn applies_backpressure() {
+    block_on(async {
+        let (tx, mut rx) = mpsc::channel(2);
+        let producer = tokio::spawn(produce((1..=5).collect(), tx));
+        time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(rx.len(), 2, "通道容量为 2，没人接收时只能放进 2 个");
+        assert!(!producer.is_finished(), "通道满时生产者应等待");
+
+        let mut received = Vec::new();
+        while let Some(value) = rx.recv().await {
+            received.push(value);
+        }
+        assert_eq!(received, [1, 2, 3, 4, 5]);
+        assert!(producer.is_finished(), "全部发送后生产者应结束");
+    });
+}
+
+fn stops_when_receiver_dropped() {
+    block_on(async {
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        within_a_second(produce((1..=100).collect(), tx), "接收端关闭后 produce 应停止").await;
+
+        let (numbers_tx, numbers_rx) = mpsc::channel(1);
+        let (squares_tx, squares_rx) = mpsc::channel(1);
+        drop(squares_rx);
+        let stage = tokio::spawn(square(numbers_rx, squares_tx));
+        // 上游没有关闭，square 要在发送失败时自己停下来
+        numbers_tx.send(3).await.expect("square 提前结束了");
+        finished(stage, "下游关闭后 square 应停止").await;
+        drop(numbers_tx);
+    });
+}
+
+fn finishes_when_upstream_done() {
+    block_on(async {
+        let (numbers_tx, numbers_rx) = mpsc::channel(4);
+        let (squares_tx, mut squares_rx) = mpsc::channel(4);
+        let stage = tokio::spawn(square(numbers_rx, squares_tx));
+        numbers_tx.send(2).await.expect("square 提前结束了");
+        numbers_tx.send(5).await.expect("square 提前结束了");
+        drop(numbers_tx);
+
+        finished(stage, "上游关闭后 square 应结束").await;
+        assert_eq!(squares_rx.recv().await, Some(4));
+        assert_eq!(squares_rx.recv().await, Some(25));
+        assert_eq!(squares_rx.recv().await, None, "square 结束后通道应关闭");
+    });
+}
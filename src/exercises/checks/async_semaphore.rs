@@ -0,0 +1,69 @@
+// 检查: 限流抓取 (Semaphore)
+// 用 tokio 的暂停时间运行：每次抓取正好 100ms，从总耗时可以算出同时抓取了几个
+
+use std::time::Duration;
+
+use tokio::runtime;
+use tokio::time::Instant;
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("按输入顺序返回结果", keeps_order),
+    ("最多同时抓取 limit 个", limits_concurrency),
+    ("limit 足够大时全部并发", runs_all_at_once),
+];
+
+const URLS: [&str; 5] = [
+    "https://www.rust-lang.org",
+    "https://docs.rs",
+    "https://crates.io",
+    "https://tokio.rs",
+    "https://doc.rust-lang.org/book",
+];
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .expect("无法创建 tokio 运行时")
+        .block_on(future)
+}
+
+// 返回结果和总耗时
+fn timed_fetch_all(urls: &[&str], limit: usize) -> (Vec<usize>, Duration) {
+    block_on(async {
+        let start = Instant::now();
+        let results = fetch_all(urls, limit).await;
+        (results, start.elapsed())
+    })
+}
+
+fn keeps_order() {
+    let (results, _) = timed_fetch_all(&URLS, 2);
+    assert_eq!(results, [25, 15, 17, 16, 30]);
+    let (results, elapsed) = timed_fetch_all(&[], 2);
+    assert!(results.is_empty(), "没有网址时结果为空");
+    assert_eq!(elapsed, Duration::ZERO, "没有网址时不用等待");
+}
+
+fn limits_concurrency() {
+    let (_, elapsed) = timed_fetch_all(&URLS, 2);
+    assert_eq!(
+        elapsed,
+        Duration::from_millis(300),
+        "5 个网址、每次最多 2 个，应分 3 批共 300ms（依次抓取是 500ms，不限流是 100ms）"
+    );
+    let (_, elapsed) = timed_fetch_all(&URLS, 1);
+    assert_eq!(elapsed, Duration::from_millis(500), "limit 为 1 时依次抓取");
+}
+
+fn runs_all_at_once() {
+    let (_, elapsed) = timed_fetch_all(&URLS, 10);
+    assert_eq!(
+        elapsed,
+        Duration::from_millis(100),
+        "limit 不小于网址数时应同时抓取"
+    );
+}
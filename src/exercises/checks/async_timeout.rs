@@ -0,0 +1,92 @@
+// 检查: 超时控制
+// 用 tokio 的暂停时间运行：没有任务可以推进时时钟直接跳到下一个定时器，
+// sleep 不真的等待，用时也是确定的
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use tokio::runtime;
+use tokio::time::{self, Instant};
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("时限内完成", finishes_in_time),
+    ("超时", times_out),
+    ("超时后放弃 future", drops_slow_future),
+    ("超时重试", retries),
+];
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .expect("无法创建 tokio 运行时")
+        .block_on(future)
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+async fn slow(value: u32, millis: u64) -> u32 {
+    time::sleep(ms(millis)).await;
+    value
+}
+
+fn finishes_in_time() {
+    block_on(async {
+        let start = Instant::now();
+        assert_eq!(with_timeout(ms(100), slow(7, 50)).await, Ok(7));
+        assert_eq!(start.elapsed(), ms(50), "完成后应立即返回");
+    });
+}
+
+fn times_out() {
+    block_on(async {
+        let start = Instant::now();
+        assert_eq!(
+            with_timeout(ms(100), slow(7, 200)).await,
+            Err(FetchError::Timeout)
+        );
+        assert_eq!(start.elapsed(), ms(100), "应在时限到达时返回，不等 future 完成");
+    });
+}
+
+fn drops_slow_future() {
+    block_on(async {
+        let finished = Rc::new(Cell::new(false));
+        let flag = Rc::clone(&finished);
+        let result = with_timeout(ms(100), async move {
+            time::sleep(ms(200)).await;
+            flag.set(true);
+        })
+        .await;
+        assert_eq!(result, Err(FetchError::Timeout));
+        time::sleep(ms(500)).await;
+        assert!(!finished.get(), "超时的 future 应被放弃，不能在后台继续运行");
+    });
+}
+
+fn retries() {
+    block_on(async {
+        // 第 0、1、2 次分别需要 300、200、100 毫秒
+        let start = Instant::now();
+        let result = retry_with_timeout(5, ms(150), |attempt| {
+            slow(attempt, 300 - u64::from(attempt) * 100)
+        })
+        .await;
+        assert_eq!(result, Ok(2), "第 2 次尝试应在时限内完成");
+        assert_eq!(start.elapsed(), ms(400), "两次超时 150ms，加上成功的 100ms");
+
+        let result = retry_with_timeout(3, ms(50), |attempt| slow(attempt, 100)).await;
+        assert_eq!(result, Err(FetchError::Timeout), "每次都超时");
+        assert_eq!(
+            retry_with_timeout(0, ms(50), |attempt| slow(attempt, 10)).await,
+            Err(FetchError::Timeout),
+            "attempts 为 0 时不尝试"
+        );
+    });
+}
@@ -0,0 +1,37 @@
+// 检查: 集合类型
+
+use std::collections::HashMap;
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("就地修改元素", doubles_in_place),
+    ("单词统计", counts_words),
+    ("条件插入", inserts_if_absent),
+];
+
+fn doubles_in_place() {
+    let mut v = vec![5, 6, 7, 8];
+    double_all(&mut v);
+    assert_eq!(v, [10, 12, 14, 16]);
+}
+
+fn counts_words() {
+    let counts = word_count("hello world wonderful world");
+    assert_eq!(counts.len(), 3, "不同单词的个数");
+    assert_eq!(counts.get("world"), Some(&2), "\"world\" 的次数");
+    assert_eq!(counts.get("hello"), Some(&1), "\"hello\" 的次数");
+    assert!(word_count("   ").is_empty(), "只有空白时没有单词");
+}
+
+fn inserts_if_absent() {
+    let mut scores = HashMap::new();
+    scores.insert(String::from("蓝队"), 10);
+    assert_eq!(insert_if_absent(&mut scores, "红队", 30), 30, "新队伍");
+    assert_eq!(
+        insert_if_absent(&mut scores, "蓝队", 40),
+        10,
+        "已存在的队伍"
+    );
+    assert_eq!(scores.get("蓝队"), Some(&10), "已存在的分数不应被覆盖");
+}
@@ -0,0 +1,50 @@
+// 检查: 控制流和模式匹配
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("match 范围模式", classifies),
+    ("loop 返回值", loops_to_power_of_two),
+    ("while 倒数", counts_down),
+    ("if let 处理 Option", sums_present_values),
+];
+
+fn classifies() {
+    assert_eq!(classify(0), "零", "classify(0)");
+    assert_eq!(classify(7), "个位数", "classify(7)");
+    assert_eq!(classify(42), "两位数", "classify(42)");
+    assert_eq!(classify(99), "两位数", "classify(99)");
+    assert_eq!(classify(100), "很大", "classify(100)");
+}
+
+fn loops_to_power_of_two() {
+    assert_eq!(
+        first_power_of_two_above(0),
+        1,
+        "first_power_of_two_above(0)"
+    );
+    assert_eq!(
+        first_power_of_two_above(5),
+        8,
+        "first_power_of_two_above(5)"
+    );
+    assert_eq!(
+        first_power_of_two_above(8),
+        16,
+        "first_power_of_two_above(8)"
+    );
+}
+
+fn counts_down() {
+    assert_eq!(countdown(3), vec![3, 2, 1], "countdown(3)");
+    assert_eq!(countdown(0), Vec::<u32>::new(), "countdown(0)");
+}
+
+fn sums_present_values() {
+    assert_eq!(
+        sum_present(&[Some(1), None, Some(5), None]),
+        (6, 2),
+        "sum_present(&[Some(1), None, Some(5), None])"
+    );
+    assert_eq!(sum_present(&[]), (0, 0), "sum_present(&[])");
+}
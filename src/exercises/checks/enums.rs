@@ -0,0 +1,32 @@
+// 检查: 枚举和模式匹配
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("带数据的变体", formats_ip),
+    ("不同形式的变体", describes_messages),
+    ("Option 运算", adds_one_to_option),
+];
+
+fn formats_ip() {
+    assert_eq!(format_ip(&IpAddr::V4(127, 0, 0, 1)), "127.0.0.1");
+    assert_eq!(format_ip(&IpAddr::V6(String::from("::1"))), "::1");
+}
+
+fn describes_messages() {
+    assert_eq!(describe(&Message::Quit), "退出");
+    assert_eq!(describe(&Message::Move { x: 10, y: 20 }), "移动到 (10, 20)");
+    assert_eq!(
+        describe(&Message::Write(String::from("你好"))),
+        "写入: 你好"
+    );
+    assert_eq!(
+        describe(&Message::ChangeColor(255, 0, 0)),
+        "颜色 RGB(255, 0, 0)"
+    );
+}
+
+fn adds_one_to_option() {
+    assert_eq!(plus_one(Some(5)), Some(6), "plus_one(Some(5))");
+    assert_eq!(plus_one(None), None, "plus_one(None)");
+}
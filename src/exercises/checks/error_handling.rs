@@ -0,0 +1,49 @@
+// 检查: 错误处理
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("返回 Result", divides),
+    ("? 传播错误", parses_and_doubles),
+    ("自定义错误类型", reports_app_errors),
+];
+
+fn divides() {
+    assert_eq!(divide(10.0, 2.0), Ok(5.0), "divide(10.0, 2.0)");
+    assert_eq!(
+        divide(10.0, 0.0),
+        Err(String::from("除数不能为零")),
+        "divide(10.0, 0.0)"
+    );
+}
+
+fn parses_and_doubles() {
+    assert_eq!(parse_and_double("21"), Ok(42), "parse_and_double(\"21\")");
+    assert_eq!(
+        parse_and_double(" 10 "),
+        Ok(20),
+        "parse_and_double(\" 10 \")"
+    );
+    assert!(
+        parse_and_double("abc").is_err(),
+        "parse_and_double(\"abc\") 应当出错"
+    );
+}
+
+fn reports_app_errors() {
+    assert_eq!(
+        parse_and_divide("10", "3"),
+        Ok(3),
+        "parse_and_divide(\"10\", \"3\")"
+    );
+    assert_eq!(
+        parse_and_divide("10", "0"),
+        Err(AppError::DivisionByZero),
+        "parse_and_divide(\"10\", \"0\")"
+    );
+    assert_eq!(
+        parse_and_divide("十", "2"),
+        Err(AppError::InvalidNumber(String::from("十"))),
+        "parse_and_divide(\"十\", \"2\")"
+    );
+}
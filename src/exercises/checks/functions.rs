@@ -0,0 +1,37 @@
+// 检查: 函数定义和调用
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("返回 String", greets),
+    ("表达式作为返回值", adds_one),
+    ("递归", computes_factorial),
+    ("返回闭包", returns_closure),
+];
+
+fn greets() {
+    assert_eq!(
+        greet("学习者"),
+        "你好, 学习者! 欢迎学习 Rust!",
+        "greet(\"学习者\")"
+    );
+}
+
+fn adds_one() {
+    assert_eq!(add_one(5), 6, "add_one(5)");
+    assert_eq!(add_one(-1), 0, "add_one(-1)");
+}
+
+fn computes_factorial() {
+    assert_eq!(factorial(0), 1, "factorial(0)");
+    assert_eq!(factorial(1), 1, "factorial(1)");
+    assert_eq!(factorial(5), 120, "factorial(5)");
+    assert_eq!(factorial(10), 3_628_800, "factorial(10)");
+}
+
+fn returns_closure() {
+    let triple = multiplier(3);
+    assert_eq!(triple(4), 12, "multiplier(3)(4)");
+    assert_eq!(triple(-2), -6, "multiplier(3)(-2)");
+    assert_eq!(multiplier(0)(7), 0, "multiplier(0)(7)");
+}
@@ -0,0 +1,54 @@
+// 检查: 泛型和Trait
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("泛型函数", finds_largest),
+    ("泛型方法", mixes_points),
+    ("Trait 默认实现", summarizes),
+    ("impl Trait 参数", notifies),
+];
+
+fn finds_largest() {
+    assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(100), "整数");
+    assert_eq!(largest(&['y', 'm', 'a', 'q']), Some('y'), "字符");
+    assert_eq!(largest(&[1.5, -2.0]), Some(1.5), "浮点数");
+    assert_eq!(largest::<i32>(&[]), None, "空切片");
+}
+
+fn mixes_points() {
+    let p1 = Point { x: 5, y: 10.4 };
+    let p2 = Point { x: "Hello", y: 'c' };
+    assert_eq!(p1.mixup(p2), Point { x: 5, y: 'c' });
+}
+
+fn tweet() -> Tweet {
+    Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("当然，就像您可能知道的"),
+    }
+}
+
+fn summarizes() {
+    let tweet = tweet();
+    assert!(!tweet.content.is_empty());
+    assert_eq!(tweet.author(), "@horse_ebooks");
+    assert_eq!(
+        tweet.summarize(),
+        "(阅读更多来自 @horse_ebooks 的内容...)",
+        "默认实现"
+    );
+
+    let article = NewsArticle {
+        headline: String::from("重大新闻"),
+        author: String::from("记者"),
+    };
+    assert_eq!(article.summarize(), "重大新闻, by 记者", "覆盖默认实现");
+}
+
+fn notifies() {
+    assert_eq!(
+        notify(&tweet()),
+        "突发新闻! (阅读更多来自 @horse_ebooks 的内容...)"
+    );
+}
@@ -0,0 +1,45 @@
+// 检查: 生命周期
+// 有些用例只要能编译就说明生命周期标注正确
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("返回较长的引用", finds_longest),
+    ("返回值只依赖部分参数", result_outlives_keyword),
+    ("持有引用的结构体", excerpts_first_sentence),
+];
+
+fn finds_longest() {
+    let string1 = String::from("长字符串");
+    let result;
+    {
+        let string2 = String::from("短");
+        assert_eq!(longest(&string1, &string2), "长字符串");
+        result = longest(&string1, "字面量");
+    }
+    // 一样长时返回第一个参数
+    assert_eq!(result, "长字符串");
+    assert_eq!(longest("ab", "cd"), "ab", "一样长时返回 x");
+}
+
+fn result_outlives_keyword() {
+    let items = ["apple", "banana", "cherry"];
+    let found;
+    {
+        // keyword 在这个块结束时被释放，found 仍然可以使用
+        let keyword = String::from("nan");
+        found = find_containing(&items, &keyword);
+    }
+    assert_eq!(found, Some("banana"));
+    assert_eq!(find_containing(&items, "xyz"), None);
+}
+
+fn excerpts_first_sentence() {
+    let novel = String::from("叫我以实玛利。几年前...");
+    let excerpt = ImportantExcerpt::first_sentence(&novel);
+    assert_eq!(excerpt.part(), "叫我以实玛利");
+    assert_eq!(
+        ImportantExcerpt::first_sentence("没有句号").part,
+        "没有句号"
+    );
+}
@@ -0,0 +1,48 @@
+// 检查: 模块系统
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("嵌套模块", uses_nested_modules),
+    ("pub use 重新导出", uses_reexports),
+];
+
+fn uses_nested_modules() {
+    assert_eq!(math::basic::add(5, 3), 8, "math::basic::add(5, 3)");
+    assert_eq!(
+        math::basic::multiply(5, 3),
+        15,
+        "math::basic::multiply(5, 3)"
+    );
+    assert_eq!(
+        math::advanced::factorial(0),
+        1,
+        "math::advanced::factorial(0)"
+    );
+    assert_eq!(
+        math::advanced::factorial(20),
+        2_432_902_008_176_640_000,
+        "math::advanced::factorial(20)"
+    );
+    assert_eq!(
+        math::advanced::fibonacci(0),
+        0,
+        "math::advanced::fibonacci(0)"
+    );
+    assert_eq!(
+        math::advanced::fibonacci(7),
+        13,
+        "math::advanced::fibonacci(7)"
+    );
+    assert_eq!(
+        math::advanced::fibonacci(90),
+        2_880_067_194_370_816_120,
+        "math::advanced::fibonacci(90)"
+    );
+}
+
+fn uses_reexports() {
+    assert_eq!(utils::reverse("你好"), "好你", "utils::reverse(\"你好\")");
+    assert!(utils::is_palindrome("racecar"), "racecar 是回文");
+    assert!(!utils::is_palindrome("rust"), "rust 不是回文");
+}
@@ -0,0 +1,47 @@
+// 检查: 所有权基础
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("返回值转移所有权", gives),
+    ("取得并交还所有权", takes_and_returns),
+    ("clone 得到独立副本", duplicates),
+    ("移动而不是复制", swaps_without_copy),
+];
+
+fn gives() {
+    assert_eq!(gives_ownership(), "give ownership", "gives_ownership()");
+}
+
+fn takes_and_returns() {
+    let s = String::from("hello");
+    assert_eq!(
+        takes_and_gives_back(s),
+        "hello (returned)",
+        "takes_and_gives_back(\"hello\")"
+    );
+}
+
+fn duplicates() {
+    let (mut a, b) = duplicate(String::from("原件"));
+    a.push_str("（已修改）");
+    assert_eq!(a, "原件（已修改）");
+    assert_eq!(b, "原件", "修改一个副本不应影响另一个");
+}
+
+fn swaps_without_copy() {
+    let a = String::from("左");
+    let b = String::from("右");
+    let (a_ptr, b_ptr) = (a.as_ptr(), b.as_ptr());
+    let (x, y) = swap((a, b));
+    assert_eq!(
+        (x.as_str(), y.as_str()),
+        ("右", "左"),
+        "swap((\"左\", \"右\"))"
+    );
+    // 移动只转移所有权，堆上的数据留在原地
+    assert!(
+        x.as_ptr() == b_ptr && y.as_ptr() == a_ptr,
+        "swap 应当移动 String，而不是 clone"
+    );
+}
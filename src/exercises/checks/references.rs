@@ -0,0 +1,29 @@
+// 检查: 引用和借用
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("不可变引用", borrows_immutably),
+    ("可变引用", borrows_mutably),
+    ("修复悬垂引用", fixes_dangle),
+];
+
+fn borrows_immutably() {
+    let s = String::from("引用测试");
+    assert_eq!(calculate_length(&s), 4, "calculate_length(\"引用测试\")");
+    // s 只是被借用，仍然可以使用
+    assert_eq!(s, "引用测试");
+}
+
+fn borrows_mutably() {
+    let mut s = String::from("hello");
+    change(&mut s);
+    assert_eq!(s, "hello, world", "change 之后");
+    change(&mut s);
+    assert_eq!(s, "hello, world, world", "第二次 change 之后");
+}
+
+fn fixes_dangle() {
+    let s = dangle();
+    assert_eq!(s, "dangle test", "dangle()");
+}
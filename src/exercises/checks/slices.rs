@@ -0,0 +1,37 @@
+// 检查: 切片操作
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("字符串切片", finds_first_word),
+    ("数组切片", slices_middle),
+    ("按字符边界切片", slices_by_char),
+];
+
+fn finds_first_word() {
+    assert_eq!(
+        first_word("hello world"),
+        "hello",
+        "first_word(\"hello world\")"
+    );
+    assert_eq!(first_word("rust"), "rust", "first_word(\"rust\")");
+    assert_eq!(first_word(""), "", "first_word(\"\")");
+}
+
+fn slices_middle() {
+    assert_eq!(
+        middle(&[1, 2, 3, 4, 5]),
+        &[2, 3, 4],
+        "middle(&[1, 2, 3, 4, 5])"
+    );
+    assert_eq!(middle(&[1, 2]), &[] as &[i32], "middle(&[1, 2])");
+    assert_eq!(middle(&[1]), &[] as &[i32], "middle(&[1])");
+    assert_eq!(middle(&[]), &[] as &[i32], "middle(&[])");
+}
+
+fn slices_by_char() {
+    let s = "你好世界，Rust 很棒！";
+    assert_eq!(prefix_chars(s, 4), "你好世界", "prefix_chars(s, 4)");
+    assert_eq!(prefix_chars(s, 0), "", "prefix_chars(s, 0)");
+    assert_eq!(prefix_chars("abc", 10), "abc", "prefix_chars(\"abc\", 10)");
+}
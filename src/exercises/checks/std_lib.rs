@@ -0,0 +1,46 @@
+// 检查: 标准库综合应用
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("迭代器链", chains_iterators),
+    ("文件读写", writes_and_reads_lines),
+    ("路径构建", builds_path),
+];
+
+fn chains_iterators() {
+    let numbers: Vec<i32> = (1..=10).collect();
+    assert_eq!(even_square_sum(&numbers), 220, "1..=10 中偶数的平方和");
+    assert_eq!(even_square_sum(&[]), 0, "空切片");
+    assert_eq!(word_lengths("Hello, 世界 Rust"), [6, 2, 4], "word_lengths");
+}
+
+fn writes_and_reads_lines() {
+    let path = env::temp_dir().join(format!("rust-exercise-{}.txt", process::id()));
+    let lines = write_lines(&path, &["Hello, Rust!", "这是测试文件", "1,2,3,4,5"]);
+    let content = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    assert_eq!(
+        lines.expect("write_lines 出错"),
+        ["Hello, Rust!", "这是测试文件", "1,2,3,4,5"]
+    );
+    assert_eq!(
+        content.expect("文件没有被创建"),
+        "Hello, Rust!\n这是测试文件\n1,2,3,4,5\n",
+        "文件内容"
+    );
+}
+
+fn builds_path() {
+    assert_eq!(
+        build_path("/tmp", "test", "rs"),
+        PathBuf::from("/tmp/test.rs")
+    );
+    assert_eq!(build_path("a/b", "c.txt", "md"), PathBuf::from("a/b/c.md"));
+}
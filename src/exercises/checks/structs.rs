@@ -0,0 +1,40 @@
+// 检查: 结构体和方法
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("关联函数", creates_user),
+    ("&self 方法", checks_adult),
+    ("&mut self 方法", deactivates),
+    ("元组结构体", formats_color),
+];
+
+fn creates_user() {
+    let user = User::new("张三", "zhangsan@example.com", 25);
+    assert_eq!(user.username, "张三");
+    assert_eq!(user.email, "zhangsan@example.com");
+    assert_eq!(user.age, 25);
+    assert!(user.active, "新用户应当是激活状态");
+}
+
+fn checks_adult() {
+    assert!(
+        User::new("a", "a@example.com", 18).is_adult(),
+        "18 岁是成年人"
+    );
+    assert!(
+        !User::new("b", "b@example.com", 17).is_adult(),
+        "17 岁不是成年人"
+    );
+}
+
+fn deactivates() {
+    let mut user = User::new("李四", "lisi@example.com", 17);
+    user.deactivate();
+    assert!(!user.active, "deactivate 之后 active 应为 false");
+}
+
+fn formats_color() {
+    assert_eq!(Color(255, 0, 16).to_hex(), "#ff0010", "Color(255, 0, 16)");
+    assert_eq!(Color(0, 0, 0).to_hex(), "#000000", "Color(0, 0, 0)");
+}
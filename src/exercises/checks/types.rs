@@ -0,0 +1,34 @@
+// 检查: 数据类型和转换
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("元组解构", reverses_tuple),
+    ("定长数组求和", sums_array),
+    ("as 类型转换", converts_float_to_char),
+];
+
+fn reverses_tuple() {
+    assert_eq!(
+        reverse_tuple((500, 6.4, false)),
+        (false, 6.4, 500),
+        "reverse_tuple((500, 6.4, false))"
+    );
+}
+
+fn sums_array() {
+    assert_eq!(array_sum([1, 2, 3, 4, 5]), 15, "array_sum([1, 2, 3, 4, 5])");
+    assert_eq!(
+        array_sum([-5, 0, 0, 0, 5]),
+        0,
+        "array_sum([-5, 0, 0, 0, 5])"
+    );
+}
+
+fn converts_float_to_char() {
+    assert_eq!(float_to_char(65.4321), 'A', "float_to_char(65.4321)");
+    assert_eq!(float_to_char(97.9), 'a', "float_to_char(97.9)");
+    // 超出 u8 范围时取边界值
+    assert_eq!(float_to_char(-1.0), '\0', "float_to_char(-1.0)");
+    assert_eq!(float_to_char(300.0), 'ÿ', "float_to_char(300.0)");
+}
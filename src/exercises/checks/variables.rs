@@ -0,0 +1,24 @@
+// 检查: 变量和可变性
+
+use super::solution::*;
+
+pub const CASES: &[(&str, fn())] = &[
+    ("常量 MAX_POINTS", max_points),
+    ("可变变量累加", count_up_sums),
+    ("变量遮蔽", shout_shadows),
+];
+
+fn max_points() {
+    assert_eq!(MAX_POINTS, 100_000, "MAX_POINTS");
+}
+
+fn count_up_sums() {
+    assert_eq!(count_up(0), 0, "count_up(0)");
+    assert_eq!(count_up(4), 10, "count_up(4)");
+    assert_eq!(count_up(100), 5050, "count_up(100)");
+}
+
+fn shout_shadows() {
+    assert_eq!(shout("  hi "), "HI!", "shout(\"  hi \")");
+    assert_eq!(shout("rust"), "RUST!", "shout(\"rust\")");
+}
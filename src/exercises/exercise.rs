@@ -0,0 +1,330 @@
+// exercises/exercise.rs - 练习的统一接口
+// 每个练习都能回答：是什么（id、标题、分组、难度）、要做什么（任务说明、起始模板），
+// 卡住了怎么办（逐级提示、参考答案），以及做对了没有（check 返回通过/失败报告）
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::grader;
+use super::harness::CaseResult;
+
+// 练习分组，对应命令行中的 basic / ownership / generics / async
+// 排序按课程顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Topic {
+    Basic,
+    Ownership,
+    Generics,
+    Async,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 4] = [
+        Topic::Basic,
+        Topic::Ownership,
+        Topic::Generics,
+        Topic::Async,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Topic::Basic => "basic",
+            Topic::Ownership => "ownership",
+            Topic::Generics => "generics",
+            Topic::Async => "async",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Topic::Basic => "基础语法练习",
+            Topic::Ownership => "所有权系统练习",
+            Topic::Generics => "泛型和Trait练习",
+            Topic::Async => "异步编程练习",
+        }
+    }
+
+    pub fn summary(self) -> &'static str {
+        match self {
+            Topic::Basic => "涵盖变量、数据类型、函数、控制流等基础概念",
+            Topic::Ownership => "涵盖所有权、借用、切片等核心概念",
+            Topic::Generics => "涵盖结构体、枚举、集合、错误处理、泛型和 Trait",
+            Topic::Async => "涵盖生命周期、模块、async/await、tokio 并发和标准库",
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Topic::ALL
+            .into_iter()
+            .find(|topic| topic.name() == s)
+            .ok_or_else(|| format!("未知练习类型: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    // 命令行中使用的名称，例如 --difficulty easy..medium
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name() == s)
+            .ok_or_else(|| format!("未知难度: {}（可选 easy、medium、hard）", s))
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Difficulty::Easy => "⭐ 简单",
+            Difficulty::Medium => "⭐⭐ 中等",
+            Difficulty::Hard => "⭐⭐⭐ 困难",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// 一次检查的结果
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub cases: Vec<CaseResult>,
+    // 没能运行完所有用例的原因，例如编译失败、超时
+    pub error: Option<String>,
+    // 编译器输出的错误和警告；只有编译学习者的文件时才有
+    pub diagnostics: String,
+    // 学习者的程序自己打印的内容
+    pub output: String,
+    pub compile_time: Option<Duration>,
+}
+
+impl Report {
+    pub fn new(cases: Vec<CaseResult>) -> Report {
+        Report {
+            cases,
+            ..Report::default()
+        }
+    }
+
+    pub fn failed(error: String) -> Report {
+        Report {
+            error: Some(error),
+            ..Report::default()
+        }
+    }
+
+    // 没有任何用例的报告不算通过
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && !self.cases.is_empty()
+            && self.cases.iter().all(|case| case.failure.is_none())
+    }
+
+    pub fn passed_count(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.failure.is_none())
+            .count()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.cases.iter().map(|case| case.duration).sum()
+    }
+}
+
+pub trait Exercise {
+    // 唯一标识，命令行中用它指定练习，例如 "references"
+    fn id(&self) -> &str;
+    fn title(&self) -> &str;
+    fn topic(&self) -> Topic;
+    // 对应的课程编号，例如 references 是 6（src/06_references.rs）
+    fn lesson(&self) -> u8;
+    fn difficulty(&self) -> Difficulty;
+    // 知识点标签，比分组更细，例如 "borrowing"、"tokio"；命令行中用 --tag 筛选
+    fn tags(&self) -> &[&str];
+    // 任务说明
+    fn prompt(&self) -> &str;
+    // 学习者开始做题时的代码模板
+    fn starter(&self) -> &str;
+    // 逐级深入的提示：先是思路，再指出具体要改的地方；不包含参考答案
+    fn hints(&self) -> &[&str];
+    // 参考答案，所有提示都用完后才展示
+    fn solution(&self) -> &str;
+    // 学习者在仓库中做题的文件（solutions/<id>.rs），一开始和起始模板相同
+    fn path(&self) -> &Path;
+    // 检查学习者在仓库中的实现（solutions/<id>.rs）
+    fn check(&self) -> Report;
+    // 用 rustc 编译并检查学习者自己的文件
+    fn check_file(&self, solution: &Path) -> Report;
+}
+
+// 写代码的练习：学习者实现 solutions/<id>.rs 中的函数，检查用例对这些函数做断言；
+// 和编译错误谜题一样用 rustc 单独编译，起始模板编译不过也不影响练习程序本身
+pub struct CodeExercise {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub topic: Topic,
+    pub lesson: u8,
+    pub difficulty: Difficulty,
+    pub tags: &'static [&'static str],
+    pub prompt: &'static str,
+    pub hints: &'static [&'static str],
+    pub starter: &'static str,
+    pub solution: &'static str,
+    // 检查用例的源码
+    pub checks: &'static str,
+    // 仓库中 solutions/<id>.rs 的绝对路径
+    pub path: &'static str,
+}
+
+impl Exercise for CodeExercise {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn topic(&self) -> Topic {
+        self.topic
+    }
+
+    fn lesson(&self) -> u8 {
+        self.lesson
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn tags(&self) -> &[&str] {
+        self.tags
+    }
+
+    fn prompt(&self) -> &str {
+        self.prompt
+    }
+
+    fn starter(&self) -> &str {
+        self.starter
+    }
+
+    fn hints(&self) -> &[&str] {
+        self.hints
+    }
+
+    fn solution(&self) -> &str {
+        self.solution
+    }
+
+    fn path(&self) -> &Path {
+        Path::new(self.path)
+    }
+
+    fn check(&self) -> Report {
+        self.check_file(self.path())
+    }
+
+    fn check_file(&self, solution: &Path) -> Report {
+        grader::grade(solution, self.checks)
+    }
+}
+
+// 编译错误谜题：起始模板是一个故意编译不过的完整程序（例如使用已经移动的值），
+// 学习者修改 solutions/<id>.rs 直到 rustc 接受它，并且输出和期望的一致
+pub struct Puzzle {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub topic: Topic,
+    pub lesson: u8,
+    pub difficulty: Difficulty,
+    pub tags: &'static [&'static str],
+    pub prompt: &'static str,
+    pub hints: &'static [&'static str],
+    pub starter: &'static str,
+    pub solution: &'static str,
+    // 起始模板中的错误代码，例如 "E0382"
+    pub error_code: &'static str,
+    pub expected_output: &'static str,
+    // 仓库中 solutions/<id>.rs 的绝对路径；程序有自己的 main，不能编译进练习程序，只能用 rustc 单独检查
+    pub path: &'static str,
+}
+
+impl Exercise for Puzzle {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn topic(&self) -> Topic {
+        self.topic
+    }
+
+    fn lesson(&self) -> u8 {
+        self.lesson
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn tags(&self) -> &[&str] {
+        self.tags
+    }
+
+    fn prompt(&self) -> &str {
+        self.prompt
+    }
+
+    fn starter(&self) -> &str {
+        self.starter
+    }
+
+    fn hints(&self) -> &[&str] {
+        self.hints
+    }
+
+    fn solution(&self) -> &str {
+        self.solution
+    }
+
+    fn path(&self) -> &Path {
+        Path::new(self.path)
+    }
+
+    fn check(&self) -> Report {
+        self.check_file(self.path())
+    }
+
+    fn check_file(&self, solution: &Path) -> Report {
+        grader::grade_program(solution, self.error_code, self.expected_output)
+    }
+}
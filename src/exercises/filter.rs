@@ -0,0 +1,118 @@
+// exercises/filter.rs - 按标签、难度和上次的结果筛选练习
+//   cargo run --bin exercises -- list --tag borrowing
+//   cargo run --bin exercises -- run --tag tokio --difficulty medium..hard
+//   cargo run --bin exercises -- all --failed-only
+//
+// 选项可以写在参数中的任何位置，用于 list、run、all 和分组：
+//   --tag <标签>          只保留带这个标签的练习；写多次时要同时带有所有标签
+//   --difficulty <范围>   easy、medium、hard 中的一个，或者用 .. 表示范围（包含两端），
+//                         例如 easy..medium、medium..（中等及以上）、..medium（中等及以下）
+//   --failed-only         只保留最近一次检查没有通过的练习（根据学习进度，没做过的不算）
+
+use std::ops::RangeInclusive;
+
+use super::exercise::{Difficulty, Exercise};
+use super::progress::Progress;
+
+pub struct Filter {
+    pub tags: Vec<String>,
+    pub difficulty: RangeInclusive<Difficulty>,
+    pub failed_only: bool,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter {
+            tags: Vec::new(),
+            difficulty: Difficulty::Easy..=Difficulty::Hard,
+            failed_only: false,
+        }
+    }
+}
+
+impl Filter {
+    // 没有指定任何筛选条件
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.difficulty == (Difficulty::Easy..=Difficulty::Hard)
+            && !self.failed_only
+    }
+
+    pub fn matches(&self, exercise: &dyn Exercise, progress: &Progress) -> bool {
+        let tags = exercise.tags();
+        self.tags.iter().all(|tag| tags.contains(&tag.as_str()))
+            && self.difficulty.contains(&exercise.difficulty())
+            && (!self.failed_only
+                || progress
+                    .get(exercise.id())
+                    .is_some_and(|entry| entry.attempts > 0 && !entry.last_passed))
+    }
+
+    // 展示用的筛选条件，例如 "标签 tokio · 难度 medium..hard · 只看没通过的"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.tags.is_empty() {
+            parts.push(format!("标签 {}", self.tags.join(" + ")));
+        }
+        let (start, end) = (*self.difficulty.start(), *self.difficulty.end());
+        if start == end {
+            parts.push(format!("难度 {}", start.name()));
+        } else if (start, end) != (Difficulty::Easy, Difficulty::Hard) {
+            parts.push(format!("难度 {}..{}", start.name(), end.name()));
+        }
+        if self.failed_only {
+            parts.push("只看没通过的".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+// 从参数中取出所有筛选选项
+pub fn take_options(args: &mut Vec<String>) -> Result<Filter, String> {
+    let mut filter = Filter::default();
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "--tag" => {
+                let tag = args
+                    .get(index + 1)
+                    .ok_or("用法: --tag <标签>，可用的标签见 list")?;
+                filter.tags.push(tag.clone());
+                args.drain(index..index + 2);
+            }
+            "--difficulty" => {
+                let range = args.get(index + 1).ok_or(
+                    "用法: --difficulty <easy|medium|hard>，也可以写范围，例如 easy..medium",
+                )?;
+                filter.difficulty = parse_difficulty(range)?;
+                args.drain(index..index + 2);
+            }
+            "--failed-only" => {
+                filter.failed_only = true;
+                args.remove(index);
+            }
+            _ => index += 1,
+        }
+    }
+    Ok(filter)
+}
+
+// "medium"、"easy..medium"、"medium.."、"..medium"
+fn parse_difficulty(s: &str) -> Result<RangeInclusive<Difficulty>, String> {
+    let Some((start, end)) = s.split_once("..") else {
+        let difficulty = s.parse::<Difficulty>()?;
+        return Ok(difficulty..=difficulty);
+    };
+    let start = match start {
+        "" => Difficulty::Easy,
+        start => start.parse()?,
+    };
+    let end = match end {
+        "" => Difficulty::Hard,
+        end => end.parse()?,
+    };
+    if start > end {
+        return Err(format!("难度范围 {} 是空的，应该从低到高写", s));
+    }
+    Ok(start..=end)
+}
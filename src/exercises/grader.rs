@@ -0,0 +1,373 @@
+// exercises/grader.rs - 编译并检查学习者自己的文件
+//   cargo run --bin exercises -- check references my_references.rs
+//
+// 1. 在临时目录中生成 main.rs：用 #[path] 把学习者的文件作为 solution 模块引入，
+//    再加上隐藏的检查用例和 harness.rs；编译器的诊断信息仍然指向学习者的文件和行号
+// 2. 用本机的 rustc 编译（可以用 RUSTC 环境变量指定）；异步练习要用 tokio，
+//    编译时链接练习程序自己用的那份 tokio（cargo 放在 target/<profile>/deps 中）。
+//    tokio 运行时不在练习程序里创建：学习者的代码编译成单独的程序运行，
+//    运行时由检查用例在那个程序中创建（current_thread + start_paused，时间是暂停的）
+// 3. 在沙箱中运行编译出的程序，它先报告用例总数，再把每个用例的结果按行打印到 stdout，这里解析后生成报告。
+//    学习者的代码和检查程序在同一个进程里，也能往 stdout 打印：每行结果都带着每次检查随机生成的标记，
+//    学习者的 println! 不会碰巧被当成结果，也没法照着格式随手伪造一行。这不是安全边界：
+//    标记就在进程自己的内存和 /proc/self/exe 里，存心要读总能读到；
+//    报告的用例数和总数对不上（例如中途 process::exit(0)）算作失败
+//
+// 编译错误谜题（见 grade_program）没有检查用例：学习者的文件本身就是完整的程序，
+// 检查的是原来的错误代码消失了、程序能编译，并且输出和期望的一致
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::exercise::Report;
+use super::harness::CaseResult;
+use super::sandbox::{self, Limits, Output};
+
+const HARNESS: &str = include_str!("harness.rs");
+
+const COMPILE_LIMITS: Limits = Limits {
+    timeout: Duration::from_secs(60),
+    max_output: 64 * 1024,
+};
+
+const RUN_LIMITS: Limits = Limits {
+    timeout: Duration::from_secs(10),
+    max_output: 64 * 1024,
+};
+
+// 报告中最多展示的程序输出行数
+const MAX_OUTPUT_LINES: usize = 40;
+
+// 生成的 main.rs；{solution} 替换为学习者文件的绝对路径（Rust 字符串字面量），
+// {nonce} 替换为这次检查的随机标记
+const MAIN_TEMPLATE: &str = r#"#[path = {solution}]
+mod solution;
+
+#[allow(dead_code)]
+#[path = "harness.rs"]
+mod harness;
+
+#[path = "checks.rs"]
+mod checks;
+
+fn main() {
+    // 局部变量：solution 模块能通过 crate:: 访问顶层的常量，访问不到这里
+    let marker = "\u{1}{nonce}\t";
+    println!("{}total\t{}", marker, checks::CASES.len());
+    for result in harness::run(checks::CASES) {
+        let (status, message) = match result.failure {
+            None => ("ok", String::new()),
+            Some(message) => ("fail", message),
+        };
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        println!(
+            "{}case\t{}\t{}\t{}\t{}",
+            marker,
+            result.name,
+            result.duration.as_nanos(),
+            status,
+            message
+        );
+    }
+}
+"#;
+
+// 临时目录，离开作用域时删除
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> io::Result<TempDir> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("rust-exercise-{}-{}", process::id(), nanos));
+        fs::create_dir(&path)?;
+        Ok(TempDir(path))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// 用 checks（检查用例的源码）检查学习者的文件
+pub fn grade(solution: &Path, checks: &str) -> Report {
+    match try_grade(solution, checks) {
+        Ok(report) => report,
+        Err(e) => Report::failed(format!("无法运行检查: {}", e)),
+    }
+}
+
+fn try_grade(solution: &Path, checks: &str) -> io::Result<Report> {
+    let solution = fs::canonicalize(solution)?;
+    let dir = TempDir::new()?;
+    let main = dir.0.join("main.rs");
+    let binary = dir.0.join(format!("solution{}", env::consts::EXE_SUFFIX));
+
+    let nonce = format!("{:016x}", rand::random::<u64>());
+    let marker = format!("\u{1}{}\t", nonce);
+    let solution_literal = format!("{:?}", solution.to_string_lossy());
+    fs::write(
+        &main,
+        MAIN_TEMPLATE
+            .replace("{solution}", &solution_literal)
+            .replace("{nonce}", &nonce),
+    )?;
+    fs::write(dir.0.join("harness.rs"), HARNESS)?;
+    fs::write(dir.0.join("checks.rs"), checks)?;
+
+    let mut report = Report::new(Vec::new());
+    if !compile(&main, &binary, &mut report)? {
+        return Ok(report);
+    }
+    // 程序在这个目录中运行，删掉源码，学习者的代码不能直接读到其中的标记（仍然可以从可执行文件中找）
+    for source in ["main.rs", "harness.rs", "checks.rs"] {
+        fs::remove_file(dir.0.join(source))?;
+    }
+
+    // 运行检查用例
+    let run = run_binary(&binary, &dir.0)?;
+    let mut total = None;
+    let mut output = Vec::new();
+    for line in run.stdout.lines() {
+        let Some(line) = line.strip_prefix(&marker) else {
+            output.push(line);
+            continue;
+        };
+        if let Some(count) = line.strip_prefix("total\t") {
+            total = count.parse::<usize>().ok();
+        } else if let Some(case) = line.strip_prefix("case\t").and_then(parse_case) {
+            report.cases.push(case);
+        }
+    }
+    output.extend(run.stderr.lines());
+    report.output = truncate_lines(output);
+    report.error = run_error(&run).or_else(|| missing_cases(&report, total));
+    Ok(report)
+}
+
+// 检查程序正常退出，但报告的用例比总数少：多半是学习者的代码提前结束了进程
+fn missing_cases(report: &Report, total: Option<usize>) -> Option<String> {
+    let Some(total) = total else {
+        return Some("检查程序没有报告任何结果".to_string());
+    };
+    (report.cases.len() != total).then(|| {
+        format!(
+            "检查程序提前退出，只报告了 {}/{} 个用例，是不是调用了 process::exit？",
+            report.cases.len(),
+            total
+        )
+    })
+}
+
+// 编译错误谜题：error_code 是起始模板中故意留下的错误（例如 "E0382"），expected 是程序应有的输出
+pub fn grade_program(source: &Path, error_code: &str, expected: &str) -> Report {
+    match try_grade_program(source, error_code, expected) {
+        Ok(report) => report,
+        Err(e) => Report::failed(format!("无法运行检查: {}", e)),
+    }
+}
+
+fn try_grade_program(source: &Path, error_code: &str, expected: &str) -> io::Result<Report> {
+    let source = fs::canonicalize(source)?;
+    let dir = TempDir::new()?;
+    let binary = dir.0.join(format!("solution{}", env::consts::EXE_SUFFIX));
+
+    let mut report = Report::new(Vec::new());
+    let compiled = compile(&source, &binary, &mut report)?;
+    // 编译成功时诊断信息中只有警告；编译失败时可能是原来的错误，也可能是改出了新的错误
+    let still_failing = report
+        .diagnostics
+        .contains(&format!("error[{}]", error_code));
+    report.cases.push(CaseResult {
+        name: format!("{} 已修复", error_code),
+        duration: Duration::ZERO,
+        failure: still_failing.then(|| format!("编译器仍然报告 {}", error_code)),
+    });
+    if !compiled {
+        return Ok(report);
+    }
+
+    let run = run_binary(&binary, &dir.0)?;
+    let actual = run.stdout.replace("\r\n", "\n");
+    let (actual, expected) = (actual.trim_end(), expected.trim_end());
+    report.cases.push(CaseResult {
+        name: "输出正确".to_string(),
+        duration: run.elapsed,
+        failure: (actual != expected)
+            .then(|| format!("期望输出:\n{}\n实际输出:\n{}", expected, actual)),
+    });
+    // stdout 已经在上面的比较中展示，这里只保留 panic 信息等 stderr 输出
+    report.output = truncate_lines(run.stderr.lines().collect());
+    report.error = run_error(&run);
+    Ok(report)
+}
+
+// 用 rustc 把 source 编译成 binary，编译用时、诊断信息和错误记在 report 中；返回是否编译成功
+fn compile(source: &Path, binary: &Path, report: &mut Report) -> io::Result<bool> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let mut command = Command::new(rustc);
+    command
+        .args([
+            "--edition",
+            "2024",
+            "--crate-name",
+            "solution",
+            "--color",
+            "never",
+        ])
+        .arg("-o")
+        .arg(binary)
+        .arg(source);
+    if let Some((deps, tokio)) = find_tokio() {
+        command
+            .arg("-L")
+            .arg(format!("dependency={}", deps.display()))
+            .arg("--extern")
+            .arg(format!("tokio={}", tokio.display()));
+    }
+    let compiled = sandbox::run(command, COMPILE_LIMITS)?;
+
+    report.compile_time = Some(compiled.elapsed);
+    report.diagnostics = compiled.stderr.trim_end().to_string();
+    if let Some(error) = limit_error(&compiled, "编译") {
+        report.error = Some(error);
+        return Ok(false);
+    }
+    if !compiled.status.is_some_and(|status| status.success()) {
+        report.error = Some("编译失败".to_string());
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+// 练习程序位于 target/<profile>/，它的依赖在旁边的 deps/ 中。
+// deps/ 里常有多份 tokio（其他程序用的那份没有 test-util），按 cargo 的指纹文件
+// .fingerprint/tokio-<hash>/lib-tokio.json 选启用了 test-util 的那份；
+// 仍有多份时按文件名取第一个，保证每次选的一样。不是用 cargo 运行时找不到，返回 None
+fn find_tokio() -> Option<(PathBuf, PathBuf)> {
+    let dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    let deps = dir.join("deps");
+    let mut candidates: Vec<PathBuf> = fs::read_dir(&deps)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with("libtokio-") && name.ends_with(".rlib")
+        })
+        .filter(|path| has_test_util(&dir, path))
+        .collect();
+    candidates.sort();
+    let tokio = candidates.into_iter().next()?;
+    Some((deps, tokio))
+}
+
+// libtokio-<hash>.rlib 对应的指纹目录是 .fingerprint/tokio-<hash>/
+fn has_test_util(dir: &Path, rlib: &Path) -> bool {
+    let Some(hash) = rlib
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.strip_prefix("libtokio-"))
+    else {
+        return false;
+    };
+    let path = dir
+        .join(".fingerprint")
+        .join(format!("tokio-{}", hash))
+        .join("lib-tokio.json");
+    let Ok(text) = fs::read_to_string(path) else {
+        return false;
+    };
+    // features 字段本身是一个字符串，例如 "[\"rt\", \"test-util\"]"
+    serde_json::from_str::<serde_json::Value>(&text)
+        .ok()
+        .and_then(|json| {
+            json["features"]
+                .as_str()
+                .map(|f| f.contains("\"test-util\""))
+        })
+        .unwrap_or(false)
+}
+
+// 在沙箱中运行编译出的程序，工作目录是临时目录
+fn run_binary(binary: &Path, dir: &Path) -> io::Result<Output> {
+    let mut command = sandbox::offline_command(binary);
+    command.current_dir(dir).env("RUST_BACKTRACE", "0");
+    sandbox::run(command, RUN_LIMITS)
+}
+
+fn run_error(run: &Output) -> Option<String> {
+    limit_error(run, "运行").or_else(|| match run.status {
+        Some(status) if !status.success() => Some(format!("检查程序异常退出: {}", status)),
+        _ => None,
+    })
+}
+
+// 最多保留 MAX_OUTPUT_LINES 行
+fn truncate_lines(mut lines: Vec<&str>) -> String {
+    if lines.len() > MAX_OUTPUT_LINES {
+        let omitted = lines.len() - MAX_OUTPUT_LINES;
+        lines.truncate(MAX_OUTPUT_LINES);
+        format!("{}\n……省略 {} 行", lines.join("\n"), omitted)
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn limit_error(output: &Output, stage: &str) -> Option<String> {
+    if output.timed_out {
+        Some(format!(
+            "{}超时（超过 {:?}），是不是有死循环？",
+            stage, output.elapsed
+        ))
+    } else if output.truncated {
+        Some(format!("{}时输出超过上限，已终止", stage))
+    } else {
+        None
+    }
+}
+
+// "名称\t纳秒\tok|fail\t消息"
+fn parse_case(line: &str) -> Option<CaseResult> {
+    let mut fields = line.splitn(4, '\t');
+    let name = fields.next()?.to_string();
+    let duration = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let failure = match fields.next()? {
+        "ok" => None,
+        _ => Some(unescape(fields.next().unwrap_or(""))),
+    };
+    Some(CaseResult {
+        name,
+        duration,
+        failure,
+    })
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
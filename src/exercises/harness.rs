@@ -0,0 +1,60 @@
+// exercises/harness.rs - 运行检查用例
+// 一个检查用例就是一个普通函数，用 assert! / assert_eq! 检查学习者的实现；
+// 函数正常返回表示通过，panic 表示失败，panic 消息就是失败原因
+//
+// 这个文件只依赖标准库
+
+use std::any::Any;
+use std::panic;
+use std::time::{Duration, Instant};
+
+// (用例名称, 用例函数)
+pub type Case = (&'static str, fn());
+
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub name: String,
+    pub duration: Duration,
+    // None 表示通过
+    pub failure: Option<String>,
+}
+
+// 依次运行所有用例；一个用例失败不影响后面的用例
+pub fn run(cases: &[Case]) -> Vec<CaseResult> {
+    // 默认的 panic 钩子会把消息和源码位置打印到 stderr，
+    // 运行期间换成空钩子，失败原因由报告统一展示
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let results = cases
+        .iter()
+        .map(|&(name, case)| {
+            let start = Instant::now();
+            let outcome = panic::catch_unwind(case);
+            CaseResult {
+                name: name.to_string(),
+                duration: start.elapsed(),
+                failure: outcome.err().map(|payload| panic_message(payload.as_ref())),
+            }
+        })
+        .collect();
+
+    panic::set_hook(hook);
+    results
+}
+
+// panic!("...") 的消息是 &str，带格式化参数时是 String
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "未知错误".to_string()
+    };
+    // 起始模板中的 todo!() 还没有被替换
+    if message.starts_with("not yet implemented") {
+        return "尚未实现 (todo!)".to_string();
+    }
+    message
+}
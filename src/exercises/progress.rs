@@ -0,0 +1,249 @@
+// exercises/progress.rs - 学习进度
+// 每次检查练习后记录结果，保存在用户数据目录下的 JSON 文件中：
+//
+//   Linux    $XDG_DATA_HOME/rust-learning/progress.json（默认 ~/.local/share/...）
+//   macOS    ~/Library/Application Support/rust-learning/progress.json
+//   Windows  %APPDATA%\rust-learning\progress.json
+//
+// 设置 RUST_LEARNING_DATA_DIR 环境变量可以换一个目录（例如给不同的学习者分别记录）
+//
+//   {
+//     "exercises": {
+//       "references": {
+//         "completed": true,
+//         "last_passed": true,
+//         "attempts": 3,
+//         "best_time_us": 12,
+//         "last_attempted": "2025-10-19",
+//         "hints_used": 1
+//       }
+//     },
+//     "quiz": {
+//       "ownership.move": {
+//         "attempts": 2,
+//         "correct": 1,
+//         "last_correct": true,
+//         "last_answered": "2025-10-19"
+//       }
+//     },
+//     "review": {
+//       "quiz:ownership.move": {
+//         "ease": 2.6,
+//         "interval": 6,
+//         "repetitions": 2,
+//         "due": "2025-10-25",
+//         "last_reviewed": "2025-10-19"
+//       }
+//     }
+//   }
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::exercise::Report;
+use super::review::{self, Card};
+
+const APP_DIR: &str = "rust-learning";
+const FILE_NAME: &str = "progress.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExerciseProgress {
+    // 至少通过过一次
+    pub completed: bool,
+    // 最近一次检查是否通过
+    pub last_passed: bool,
+    pub attempts: u32,
+    // 通过时检查用例的最短总用时（微秒）
+    pub best_time_us: Option<u64>,
+    // 最近一次检查的日期（UTC），例如 "2025-10-19"
+    pub last_attempted: Option<String>,
+    // 已经看过的提示级数；最后一级是参考答案
+    pub hints_used: u32,
+}
+
+// 一道测验题的作答记录
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuizProgress {
+    pub attempts: u32,
+    // 答对的次数
+    pub correct: u32,
+    pub last_correct: bool,
+    pub last_answered: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    // 以练习 ID 为键；BTreeMap 让文件内容的顺序保持稳定
+    pub exercises: BTreeMap<String, ExerciseProgress>,
+    // 以测验题 ID 为键
+    pub quiz: BTreeMap<String, QuizProgress>,
+    // 间隔重复的复习卡片，键的格式见 review::exercise_key 和 review::quiz_key
+    pub review: BTreeMap<String, Card>,
+    #[serde(skip)]
+    path: PathBuf,
+    // 有新的记录时才需要保存
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl Progress {
+    // 读取进度文件；文件不存在时从空白开始
+    pub fn load() -> Progress {
+        let path = data_dir().join(FILE_NAME);
+        let mut progress = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                // 不能直接覆盖：把损坏的文件留给用户处理
+                let backup = path.with_extension("json.bak");
+                eprintln!(
+                    "⚠️ 进度文件 {} 无法解析 ({})，已另存为 {}",
+                    path.display(),
+                    e,
+                    backup.display()
+                );
+                let _ = fs::rename(&path, &backup);
+                Progress::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Progress::default(),
+            Err(e) => {
+                eprintln!("⚠️ 无法读取进度文件 {}: {}", path.display(), e);
+                Progress::default()
+            }
+        };
+        progress.path = path;
+        progress
+    }
+
+    // 先写临时文件再重命名，写到一半被中断也不会损坏原来的进度
+    pub fn save(&self) -> io::Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ExerciseProgress> {
+        self.exercises.get(id)
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.get(id).is_some_and(|entry| entry.completed)
+    }
+
+    // 记录一道测验题的作答结果
+    pub fn record_quiz(&mut self, id: &str, correct: bool) {
+        self.changed = true;
+        let entry = self.quiz.entry(id.to_string()).or_default();
+        entry.attempts += 1;
+        entry.correct += u32::from(correct);
+        entry.last_correct = correct;
+        entry.last_answered = Some(today());
+        // 答错的题今天就可以复习
+        self.review
+            .entry(review::quiz_key(id))
+            .or_insert_with(|| Card::new(if correct { 1 } else { 0 }));
+    }
+
+    // 按自评分数更新一张复习卡片
+    pub fn review_card(&mut self, key: &str, grade: u8) -> &Card {
+        self.changed = true;
+        let card = self.review.entry(key.to_string()).or_default();
+        card.grade(grade);
+        card
+    }
+
+    // 多看一级提示，返回已经看过的级数；最多 levels 级
+    pub fn use_hint(&mut self, id: &str, levels: u32) -> u32 {
+        let entry = self.exercises.entry(id.to_string()).or_default();
+        if entry.hints_used < levels {
+            entry.hints_used += 1;
+            self.changed = true;
+        }
+        entry.hints_used
+    }
+
+    // 记录一次检查的结果
+    pub fn record(&mut self, id: &str, report: &Report) {
+        self.changed = true;
+        let entry = self.exercises.entry(id.to_string()).or_default();
+        entry.attempts += 1;
+        entry.last_passed = report.passed();
+        entry.last_attempted = Some(today());
+        if report.passed() {
+            entry.completed = true;
+            let time = report.duration().as_micros() as u64;
+            entry.best_time_us = Some(entry.best_time_us.map_or(time, |best| best.min(time)));
+            // 第一次通过后加入复习计划，明天复习
+            self.review
+                .entry(review::exercise_key(id))
+                .or_insert_with(|| Card::new(1));
+        }
+    }
+}
+
+// 用户数据目录下的 rust-learning 目录
+fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("RUST_LEARNING_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    let home = || env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+    } else if cfg!(target_os = "macos") {
+        home().join("Library/Application Support")
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home().join(".local/share"))
+    };
+    base.join(APP_DIR)
+}
+
+// 今天的日期（UTC），例如 "2025-10-19"
+pub fn today() -> String {
+    date_after(0)
+}
+
+// days 天之后的日期（UTC）
+pub fn date_after(days: u32) -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64 + i64::from(days));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// 把 1970-01-01 起的天数换算成 (年, 月, 日)
+// 算法来自 Howard Hinnant 的 "chrono-Compatible Low-Level Date Algorithms"
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
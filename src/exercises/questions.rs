@@ -0,0 +1,1135 @@
+// exercises/questions.rs - 测验题库
+// 按课程（src/00_hello.rs ~ src/15_std.rs）分组；id 写入学习进度，发布后不要修改
+//
+// 选择题的第一个选项是正确答案，出题时会打乱顺序；
+// 代码题都是完整的程序，答案用 rustc --edition 2024 验证过
+
+use super::quiz::{Kind, Question};
+
+pub const QUESTIONS: &[Question] = &[
+    // ========== 00_hello ==========
+    Question {
+        id: "hello.macro",
+        lesson: "hello",
+        kind: Kind::Choice {
+            question: "println! 后面的感叹号表示什么？",
+            options: &[
+                "这是一个宏调用",
+                "这个函数可能 panic",
+                "这是一个 unsafe 函数",
+                "输出后立即刷新缓冲区",
+            ],
+        },
+        explanation: "带 ! 的是宏。println! 在编译期展开，可以接收任意个参数并检查格式字符串，普通函数做不到",
+    },
+    Question {
+        id: "hello.inline_args",
+        lesson: "hello",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let name = "Rust";
+    println!("{name} {}", 2024);
+}
+"#,
+            output: "Rust 2024",
+        },
+        explanation: "格式字符串中可以直接写变量名 {name}（内联参数），{} 按顺序使用后面传入的参数",
+    },
+    Question {
+        id: "hello.arg_count",
+        lesson: "hello",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    println!("{} {}", 1);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "格式字符串中有两个 {}，但只传了一个参数；格式检查在编译期完成，参数个数不匹配直接报错",
+    },
+    Question {
+        id: "hello.debug_format",
+        lesson: "hello",
+        kind: Kind::Choice {
+            question: "{:?} 和 {} 有什么区别？",
+            options: &[
+                "{:?} 使用 Debug 格式，{} 使用 Display 格式",
+                "{:?} 会同时打印变量的类型",
+                "{:?} 只能用于数字",
+                "两者没有区别",
+            ],
+        },
+        explanation: "{} 要求类型实现 Display，{:?} 要求实现 Debug；#[derive(Debug)] 可以自动实现 Debug，{:#?} 是多行的美化格式",
+    },
+    // ========== 01_variables ==========
+    Question {
+        id: "variables.immutable",
+        lesson: "variables",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let x = 5;
+    x = 6;
+    println!("{x}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "变量默认不可变，给不可变变量第二次赋值是 E0384；需要写成 let mut x",
+    },
+    Question {
+        id: "variables.shadowing_scope",
+        lesson: "variables",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let x = 5;
+    let x = x + 1;
+    {
+        let x = x * 2;
+        println!("{x}");
+    }
+    println!("{x}");
+}
+"#,
+            output: "12\n6",
+        },
+        explanation: "每个 let 都创建一个新变量遮蔽旧的；内部作用域中的 x 是 12，离开作用域后外面的 x 仍然是 6",
+    },
+    Question {
+        id: "variables.const",
+        lesson: "variables",
+        kind: Kind::Choice {
+            question: "const 常量和不可变的 let 变量有什么区别？",
+            options: &[
+                "const 必须标注类型，值必须在编译期就能确定",
+                "const 可以加 mut 变成可修改的",
+                "let 变量不能被遮蔽，const 可以",
+                "const 只能定义在函数内部",
+            ],
+        },
+        explanation: "常量在编译期求值，可以定义在任何作用域（包括全局），不能用 mut，也必须写出类型，例如 const MAX_POINTS: u32 = 100_000;",
+    },
+    Question {
+        id: "variables.shadow_type",
+        lesson: "variables",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let spaces = "   ";
+    let spaces = spaces.len();
+    println!("{spaces}");
+}
+"#,
+            compiles: true,
+        },
+        explanation: "遮蔽创建的是新变量，类型可以不同；如果写成 let mut spaces 再赋值 spaces = spaces.len()，就会因为类型不匹配报错",
+    },
+    // ========== 02_types ==========
+    Question {
+        id: "types.wrapping",
+        lesson: "types",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let x: u8 = 255;
+    println!("{}", x.wrapping_add(1));
+}
+"#,
+            output: "0",
+        },
+        explanation: "wrapping_add 按模 256 回绕；直接写 x + 1 在 debug 构建中会因为溢出 panic，release 构建中才会回绕",
+    },
+    Question {
+        id: "types.integer_division",
+        lesson: "types",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let a = 7 / 2;
+    let b = 7.0 / 2.0;
+    println!("{a} {b}");
+}
+"#,
+            output: "3 3.5",
+        },
+        explanation: "整数除法向零截断，7 / 2 是 3；浮点数除法才得到 3.5",
+    },
+    Question {
+        id: "types.no_implicit_conversion",
+        lesson: "types",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let x: i32 = 5;
+    let y: i64 = 10;
+    println!("{}", x + y);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "Rust 不会隐式转换数字类型，i32 + i64 是 E0308/E0277；要写 i64::from(x) + y 或 x as i64 + y",
+    },
+    Question {
+        id: "types.char_size",
+        lesson: "types",
+        kind: Kind::Choice {
+            question: "char 类型占多少字节？",
+            options: &[
+                "4 字节，表示一个 Unicode 标量值",
+                "1 字节，只能表示 ASCII",
+                "2 字节，使用 UTF-16 编码",
+                "1 到 4 字节，取决于字符",
+            ],
+        },
+        explanation: "char 固定占 4 字节；String 中的字符用 UTF-8 编码存储，那里才是每个字符 1 到 4 字节",
+    },
+    // ========== 03_functions ==========
+    Question {
+        id: "functions.block_expression",
+        lesson: "functions",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let y = {
+        let x = 3;
+        x * 2
+    };
+    println!("{y}");
+}
+"#,
+            output: "6",
+        },
+        explanation: "代码块是表达式，最后一个没有分号的表达式就是它的值",
+    },
+    Question {
+        id: "functions.trailing_semicolon",
+        lesson: "functions",
+        kind: Kind::Compiles {
+            code: r#"
+fn add(a: i32, b: i32) -> i32 {
+    a + b;
+}
+
+fn main() {
+    println!("{}", add(1, 2));
+}
+"#,
+            compiles: false,
+        },
+        explanation: "a + b; 加了分号变成语句，函数体的值是 ()，和返回类型 i32 不匹配（E0308）；去掉分号即可",
+    },
+    Question {
+        id: "functions.closure_capture",
+        lesson: "functions",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let mut count = 0;
+    let mut inc = || count += 1;
+    inc();
+    inc();
+    println!("{count}");
+}
+"#,
+            output: "2",
+        },
+        explanation: "闭包以可变借用捕获 count；inc 最后一次使用之后借用就结束了，所以之后可以读取 count",
+    },
+    Question {
+        id: "functions.never_type",
+        lesson: "functions",
+        kind: Kind::Choice {
+            question: "返回类型写成 ! 的函数是什么意思？",
+            options: &[
+                "函数永远不会返回，例如总是 panic! 或者无限循环",
+                "函数返回布尔值的取反",
+                "函数可能返回错误",
+                "函数没有返回值，等同于 ()",
+            ],
+        },
+        explanation: "! 是 never 类型，这样的函数叫发散函数；返回 () 的函数会正常返回，只是没有有意义的值",
+    },
+    // ========== 04_control ==========
+    Question {
+        id: "control.loop_break_value",
+        lesson: "control",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+        if counter == 10 {
+            break counter * 2;
+        }
+    };
+    println!("{result}");
+}
+"#,
+            output: "20",
+        },
+        explanation: "loop 是表达式，break 后面的值就是整个 loop 的值",
+    },
+    Question {
+        id: "control.non_exhaustive",
+        lesson: "control",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let n: u8 = 3;
+    let size = match n {
+        0 => "零",
+        1..=9 => "小",
+    };
+    println!("{size}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "match 必须覆盖所有可能的值，10..=255 没有被覆盖（E0004）；加一个 _ => \"大\" 分支即可",
+    },
+    Question {
+        id: "control.loop_labels",
+        lesson: "control",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let mut count = 0;
+    'outer: for i in 0..3 {
+        for j in 0..3 {
+            if j == 2 {
+                continue 'outer;
+            }
+            if i == 2 {
+                break 'outer;
+            }
+            count += 1;
+        }
+    }
+    println!("{count}");
+}
+"#,
+            output: "4",
+        },
+        explanation: "i 为 0 和 1 时，j 取 0、1 各计数一次，j == 2 时跳到外层的下一轮；i 为 2 时第一轮就跳出外层循环，共 4 次",
+    },
+    Question {
+        id: "control.rev_range",
+        lesson: "control",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    for i in (1..4).rev() {
+        print!("{i} ");
+    }
+    println!();
+}
+"#,
+            output: "3 2 1",
+        },
+        explanation: "1..4 不包含 4，rev() 反转后是 3、2、1；要包含结尾要写 1..=4",
+    },
+    // ========== 05_ownership ==========
+    Question {
+        id: "ownership.move",
+        lesson: "ownership",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let s1 = String::from("hi");
+    let s2 = s1;
+    println!("{s1} {s2}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "String 在堆上保存数据，let s2 = s1 把所有权移动给 s2，之后 s1 失效（E0382）；需要两份时用 s1.clone()",
+    },
+    Question {
+        id: "ownership.copy",
+        lesson: "ownership",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let x = 5;
+    let y = x;
+    println!("{x} {y}");
+}
+"#,
+            compiles: true,
+        },
+        explanation: "i32 实现了 Copy，赋值时复制一份，x 仍然有效",
+    },
+    Question {
+        id: "ownership.copy_types",
+        lesson: "ownership",
+        kind: Kind::Choice {
+            question: "下面哪种类型赋值给另一个变量时会移动而不是复制？",
+            options: &["String", "i32", "(i32, bool)", "char"],
+        },
+        explanation: "只在栈上存储的简单类型（整数、浮点数、bool、char，以及只包含它们的元组）实现了 Copy；String 拥有堆上的数据，只能移动",
+    },
+    Question {
+        id: "ownership.mut_and_shared",
+        lesson: "ownership",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let mut s = String::from("a");
+    let r1 = &s;
+    let r2 = &mut s;
+    r2.push('b');
+    println!("{r1}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "r1 在 println! 中还要使用，这期间不能再创建可变引用 r2（E0502）；把 println! 移到 r2 之前就可以",
+    },
+    Question {
+        id: "ownership.borrow_scope",
+        lesson: "ownership",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let mut s = String::from("hello");
+    {
+        let r = &mut s;
+        r.push_str(" world");
+    }
+    let r2 = &s;
+    println!("{}", r2.len());
+}
+"#,
+            output: "11",
+        },
+        explanation: "可变引用 r 在内部作用域结束时失效，之后可以再创建不可变引用；\"hello world\" 共 11 个字节",
+    },
+    // ========== 06_references ==========
+    Question {
+        id: "references.string_slice",
+        lesson: "references",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let s = String::from("hello world");
+    let word = &s[6..];
+    println!("{word}");
+}
+"#,
+            output: "world",
+        },
+        explanation: "[6..] 从下标 6 切到结尾；切片的下标是字节位置",
+    },
+    Question {
+        id: "references.array_slice",
+        lesson: "references",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let a = [1, 2, 3, 4, 5];
+    let s = &a[1..3];
+    println!("{:?} {}", s, s.len());
+}
+"#,
+            output: "[2, 3] 2",
+        },
+        explanation: "1..3 包含下标 1 不包含 3，切片中是 a[1] 和 a[2]",
+    },
+    Question {
+        id: "references.char_boundary",
+        lesson: "references",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let s = String::from("你好");
+    let first = &s[0..1];
+    println!("{first}");
+}
+"#,
+            compiles: true,
+        },
+        explanation: "能编译，但运行时会 panic：\"你\" 在 UTF-8 中占 3 个字节，下标 1 不在字符边界上。切片下标的检查发生在运行时",
+    },
+    Question {
+        id: "references.str_param",
+        lesson: "references",
+        kind: Kind::Choice {
+            question: "函数参数写成 &str 而不是 &String 有什么好处？",
+            options: &[
+                "既能接收 &String（自动解引用），也能接收字符串字面量和切片",
+                "&str 允许修改字符串的内容",
+                "&str 会复制一份字符串，更安全",
+                "&String 不能作为函数参数",
+            ],
+        },
+        explanation: "&String 会自动解引用成 &str，反过来却不行；参数用 &str 适用的调用者最多",
+    },
+    Question {
+        id: "references.windows",
+        lesson: "references",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let v = [1, 2, 3, 4];
+    let sums: Vec<i32> = v.windows(2).map(|w| w[0] + w[1]).collect();
+    println!("{:?}", sums);
+}
+"#,
+            output: "[3, 5, 7]",
+        },
+        explanation: "windows(2) 依次产生长度为 2 的重叠切片 [1, 2]、[2, 3]、[3, 4]",
+    },
+    // ========== 07_structs ==========
+    Question {
+        id: "structs.immutable_field",
+        lesson: "structs",
+        kind: Kind::Compiles {
+            code: r#"
+struct Point {
+    x: i32,
+}
+
+fn main() {
+    let p = Point { x: 1 };
+    p.x = 2;
+    println!("{}", p.x);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "可变性属于整个绑定，不能只把某个字段标记为可变；要写 let mut p（E0594）",
+    },
+    Question {
+        id: "structs.update_syntax",
+        lesson: "structs",
+        kind: Kind::Output {
+            code: r#"
+struct Config {
+    name: String,
+    retries: u32,
+    verbose: bool,
+}
+
+fn main() {
+    let base = Config { name: String::from("base"), retries: 3, verbose: false };
+    let config = Config { verbose: true, ..base };
+    println!("{} {} {}", config.name, config.retries, config.verbose);
+}
+"#,
+            output: "base 3 true",
+        },
+        explanation: "..base 用 base 填充没有显式写出的字段；verbose 显式设为 true，其余字段来自 base",
+    },
+    Question {
+        id: "structs.update_moves",
+        lesson: "structs",
+        kind: Kind::Compiles {
+            code: r#"
+struct Config {
+    name: String,
+    retries: u32,
+}
+
+fn main() {
+    let base = Config { name: String::from("base"), retries: 3 };
+    let config = Config { retries: 5, ..base };
+    println!("{} {}", base.name, config.name);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "..base 把 name（String）移动进了 config，之后 base.name 不能再用（E0382）；只用到 Copy 字段时 base 仍然可用",
+    },
+    Question {
+        id: "structs.associated_function",
+        lesson: "structs",
+        kind: Kind::Choice {
+            question: "impl 块中没有 self 参数的函数叫什么，怎么调用？",
+            options: &[
+                "关联函数，用 类型名::函数名() 调用，例如 Rectangle::square(3)",
+                "方法，用 实例.函数名() 调用",
+                "静态变量，不能调用",
+                "私有函数，只能在 impl 内部调用",
+            ],
+        },
+        explanation: "没有 self 的是关联函数，常用作构造函数（例如 String::from）；有 self 的才是方法",
+    },
+    // ========== 08_enums ==========
+    Question {
+        id: "enums.unwrap_or",
+        lesson: "enums",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let x: Option<i32> = None;
+    println!("{}", x.unwrap_or(7));
+}
+"#,
+            output: "7",
+        },
+        explanation: "unwrap_or 在 None 时返回给定的默认值，不会 panic",
+    },
+    Question {
+        id: "enums.option_add",
+        lesson: "enums",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let a: i32 = 5;
+    let b: Option<i32> = Some(5);
+    println!("{}", a + b);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "Option<i32> 和 i32 是不同的类型（E0277），必须先处理 None 的情况，例如 b.map(|b| a + b) 或 a + b.unwrap_or(0)",
+    },
+    Question {
+        id: "enums.match_guard",
+        lesson: "enums",
+        kind: Kind::Output {
+            code: r#"
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+fn main() {
+    let shapes = [Shape::Circle(1.0), Shape::Square(2.0)];
+    for shape in &shapes {
+        let name = match shape {
+            Shape::Circle(r) if *r > 1.0 => "大圆",
+            Shape::Circle(_) => "小圆",
+            Shape::Square(_) => "方形",
+        };
+        print!("{name} ");
+    }
+    println!();
+}
+"#,
+            output: "小圆 方形",
+        },
+        explanation: "分支按顺序匹配；半径 1.0 不满足守卫条件 *r > 1.0，落到下一个 Shape::Circle(_) 分支",
+    },
+    Question {
+        id: "enums.why_option",
+        lesson: "enums",
+        kind: Kind::Choice {
+            question: "Rust 用 Option<T> 代替 null 的主要好处是？",
+            options: &[
+                "编译器强制先处理 None 的情况，才能使用里面的值",
+                "Option 比 null 占用的内存更少",
+                "Option 可以同时存多个值",
+                "Option<T> 可以自动转换成 T",
+            ],
+        },
+        explanation: "Option<T> 和 T 是不同的类型，不能直接当成 T 使用，\"忘记检查 null\" 在编译期就会被发现",
+    },
+    // ========== 09_collections ==========
+    Question {
+        id: "collections.retain",
+        lesson: "collections",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let mut v = vec![1, 2, 3];
+    v.push(4);
+    v.retain(|x| x % 2 == 0);
+    println!("{:?}", v);
+}
+"#,
+            output: "[2, 4]",
+        },
+        explanation: "retain 只保留闭包返回 true 的元素，并保持原来的顺序",
+    },
+    Question {
+        id: "collections.get",
+        lesson: "collections",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let v = vec![10, 20];
+    println!("{:?} {:?}", v.get(1), v.get(5));
+}
+"#,
+            output: "Some(20) None",
+        },
+        explanation: "get 越界时返回 None；用 v[5] 索引则会 panic",
+    },
+    Question {
+        id: "collections.string_index",
+        lesson: "collections",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let s = String::from("hello");
+    let c = s[0];
+    println!("{c}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "String 不能用整数索引（E0277），因为一个字符可能占多个字节；用 s.chars().next() 或切片 &s[0..1]",
+    },
+    Question {
+        id: "collections.string_concat",
+        lesson: "collections",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let s1 = String::from("tic");
+    let s2 = String::from("tac");
+    let s = s1 + "-" + &s2;
+    println!("{s} {s2}");
+}
+"#,
+            output: "tic-tac tac",
+        },
+        explanation: "+ 拿走左边 s1 的所有权并借用右边的 &str；s2 只是被借用，之后仍然可以使用，s1 则不行",
+    },
+    Question {
+        id: "collections.entry",
+        lesson: "collections",
+        kind: Kind::Output {
+            code: r#"
+use std::collections::HashMap;
+
+fn main() {
+    let mut counts = HashMap::new();
+    for word in "a b a".split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    println!("{}", counts["a"]);
+}
+"#,
+            output: "2",
+        },
+        explanation: "entry(key).or_insert(0) 在键不存在时插入 0，并返回值的可变引用，解引用后加一",
+    },
+    // ========== 10_errors ==========
+    Question {
+        id: "errors.question_mark",
+        lesson: "errors",
+        kind: Kind::Choice {
+            question: "? 运算符遇到 Err(e) 时会做什么？",
+            options: &[
+                "把错误（经过 From 转换）作为当前函数的返回值提前返回",
+                "调用 panic! 终止程序",
+                "忽略错误，继续执行下一行",
+                "把错误转换成 None",
+            ],
+        },
+        explanation: "? 相当于 match：Ok(v) 取出 v，Err(e) 时 return Err(From::from(e))，所以当前函数必须返回 Result（或 Option）",
+    },
+    Question {
+        id: "errors.question_mark_in_main",
+        lesson: "errors",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let n: i32 = "5".parse()?;
+    println!("{n}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: "main 返回 () 时不能使用 ?（E0277）；写成 fn main() -> Result<(), Box<dyn std::error::Error>> 并在结尾返回 Ok(())",
+    },
+    Question {
+        id: "errors.map_unwrap_or",
+        lesson: "errors",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let n = "42".parse::<i32>().map(|n| n * 2).unwrap_or(0);
+    let m = "abc".parse::<i32>().map(|n| n * 2).unwrap_or(0);
+    println!("{n} {m}");
+}
+"#,
+            output: "84 0",
+        },
+        explanation: "map 只处理 Ok 中的值，Err 原样传下去；unwrap_or 在 Err 时返回默认值",
+    },
+    Question {
+        id: "errors.collect_result",
+        lesson: "errors",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let parsed: Result<Vec<i32>, _> = ["1", "2", "x"].iter().map(|s| s.parse::<i32>()).collect();
+    println!("{}", parsed.is_err());
+}
+"#,
+            output: "true",
+        },
+        explanation: "迭代器中的 Result 可以 collect 成 Result<Vec<_>, _>：全部是 Ok 时得到 Ok(Vec)，遇到第一个 Err 就停止并返回它",
+    },
+    // ========== 11_generics ==========
+    Question {
+        id: "generics.missing_bound",
+        lesson: "generics",
+        kind: Kind::Compiles {
+            code: r#"
+fn largest<T>(list: &[T]) -> &T {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+fn main() {
+    println!("{}", largest(&[1, 5, 3]));
+}
+"#,
+            compiles: false,
+        },
+        explanation: "T 可以是任何类型，不一定能比较大小（E0369）；要加上约束 T: PartialOrd",
+    },
+    Question {
+        id: "generics.monomorphization",
+        lesson: "generics",
+        kind: Kind::Choice {
+            question: "泛型函数在运行时有什么额外开销？",
+            options: &[
+                "没有：编译器为用到的每个具体类型生成一份代码（单态化）",
+                "每次调用都要动态查找方法",
+                "参数需要在堆上分配",
+                "需要运行时类型检查",
+            ],
+        },
+        explanation: "单态化让泛型代码和手写的具体类型代码一样快，代价是编译时间和二进制体积；dyn Trait 才是运行时动态分发",
+    },
+    Question {
+        id: "generics.default_method",
+        lesson: "generics",
+        kind: Kind::Output {
+            code: r#"
+trait Greet {
+    fn name(&self) -> String;
+
+    fn hello(&self) -> String {
+        format!("你好, {}", self.name())
+    }
+}
+
+struct A;
+struct B;
+
+impl Greet for A {
+    fn name(&self) -> String {
+        String::from("A")
+    }
+}
+
+impl Greet for B {
+    fn name(&self) -> String {
+        String::from("B")
+    }
+
+    fn hello(&self) -> String {
+        String::from("嗨")
+    }
+}
+
+fn main() {
+    println!("{} {}", A.hello(), B.hello());
+}
+"#,
+            output: "你好, A 嗨",
+        },
+        explanation: "A 使用 hello 的默认实现（调用自己的 name），B 覆盖了 hello",
+    },
+    Question {
+        id: "generics.impl_trait_bound",
+        lesson: "generics",
+        kind: Kind::Compiles {
+            code: r#"
+use std::fmt::Display;
+
+struct Point;
+
+fn show(item: impl Display) {
+    println!("{item}");
+}
+
+fn main() {
+    show(Point);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "Point 没有实现 Display（E0277）；impl Display 参数只接受实现了 Display 的类型",
+    },
+    // ========== 12_lifetimes ==========
+    Question {
+        id: "lifetimes.longest",
+        lesson: "lifetimes",
+        kind: Kind::Compiles {
+            code: r#"
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() { x } else { y }
+}
+
+fn main() {
+    println!("{}", longest("ab", "c"));
+}
+"#,
+            compiles: false,
+        },
+        explanation: "有两个引用参数，编译器不知道返回值借用自哪一个（E0106）；要写成 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str",
+    },
+    Question {
+        id: "lifetimes.meaning",
+        lesson: "lifetimes",
+        kind: Kind::Choice {
+            question: "生命周期标注 'a 的作用是什么？",
+            options: &[
+                "描述多个引用的生命周期之间的关系，不会改变任何值实际存活多久",
+                "延长变量的生命周期",
+                "让引用指向的数据分配在堆上",
+                "在运行时检查引用是否有效",
+            ],
+        },
+        explanation: "生命周期标注只是给借用检查器的约束，编译后没有任何运行时开销",
+    },
+    Question {
+        id: "lifetimes.elision",
+        lesson: "lifetimes",
+        kind: Kind::Compiles {
+            code: r#"
+fn first(s: &str) -> &str {
+    &s[..1]
+}
+
+fn main() {
+    println!("{}", first("abc"));
+}
+"#,
+            compiles: true,
+        },
+        explanation: "只有一个引用参数时，省略规则把它的生命周期赋给返回值，不需要手动标注",
+    },
+    Question {
+        id: "lifetimes.struct_field",
+        lesson: "lifetimes",
+        kind: Kind::Compiles {
+            code: r#"
+struct Excerpt {
+    part: &str,
+}
+
+fn main() {
+    let e = Excerpt { part: "hi" };
+    println!("{}", e.part);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "结构体中的引用字段必须标注生命周期（E0106）：struct Excerpt<'a> { part: &'a str }",
+    },
+    // ========== 13_modules ==========
+    Question {
+        id: "modules.private_fn",
+        lesson: "modules",
+        kind: Kind::Compiles {
+            code: r#"
+mod auth {
+    fn secret() -> i32 {
+        42
+    }
+}
+
+fn main() {
+    println!("{}", auth::secret());
+}
+"#,
+            compiles: false,
+        },
+        explanation: "模块中的项默认私有，外部不能访问（E0603）；要写 pub fn secret",
+    },
+    Question {
+        id: "modules.struct_fields",
+        lesson: "modules",
+        kind: Kind::Choice {
+            question: "在模块中定义 pub struct，它的字段默认是？",
+            options: &[
+                "私有的，每个字段要单独加 pub",
+                "公开的",
+                "只读的",
+                "和结构体本身的可见性相同",
+            ],
+        },
+        explanation: "pub struct 只公开类型本身；pub enum 则不同，它的所有变体都自动公开",
+    },
+    Question {
+        id: "modules.super",
+        lesson: "modules",
+        kind: Kind::Output {
+            code: r#"
+mod outer {
+    pub fn call() -> &'static str {
+        inner::call_parent()
+    }
+
+    fn helper() -> &'static str {
+        "helper"
+    }
+
+    mod inner {
+        pub fn call_parent() -> &'static str {
+            super::helper()
+        }
+    }
+}
+
+fn main() {
+    println!("{}", outer::call());
+}
+"#,
+            output: "helper",
+        },
+        explanation: "子模块可以访问祖先模块中的私有项，super 指向父模块",
+    },
+    Question {
+        id: "modules.pub_use",
+        lesson: "modules",
+        kind: Kind::Choice {
+            question: "pub use 的作用是什么？",
+            options: &[
+                "把其他路径中的项重新导出，外部可以通过当前模块的路径使用它",
+                "把一个私有模块改成公开",
+                "导入外部 crate",
+                "让导入的项只在当前文件中可见",
+            ],
+        },
+        explanation: "pub use 常用来整理公开 API：内部模块结构可以随意调整，外部看到的路径保持不变",
+    },
+    // ========== 14_async ==========
+    Question {
+        id: "async.lazy_future",
+        lesson: "async",
+        kind: Kind::Choice {
+            question: "调用一个 async fn 但没有 .await，会发生什么？",
+            options: &[
+                "只创建了 Future，函数体不会执行（编译器会警告 Future 未被使用）",
+                "函数体在后台线程中执行",
+                "函数体立即同步执行",
+                "编译错误",
+            ],
+        },
+        explanation: "Future 是惰性的，只有被 .await 或交给运行时 poll 才会执行",
+    },
+    Question {
+        id: "async.join_select",
+        lesson: "async",
+        kind: Kind::Choice {
+            question: "tokio::join! 和 tokio::select! 有什么区别？",
+            options: &[
+                "join! 等待所有 Future 完成，select! 等到第一个完成并丢弃其余的",
+                "join! 在多个线程上并行运行，select! 只用一个线程",
+                "没有区别",
+                "select! 等待所有 Future 完成，join! 只等第一个",
+            ],
+        },
+        explanation: "两者都在当前任务中并发地 poll 多个 Future；需要真正并行时用 tokio::spawn",
+    },
+    Question {
+        id: "async.await_outside",
+        lesson: "async",
+        kind: Kind::Compiles {
+            code: r#"
+async fn answer() -> i32 {
+    42
+}
+
+fn main() {
+    let x = answer().await;
+    println!("{x}");
+}
+"#,
+            compiles: false,
+        },
+        explanation: ".await 只能在 async 函数或 async 块中使用（E0728）；main 需要用 #[tokio::main] 或手动创建运行时",
+    },
+    Question {
+        id: "async.blocking_sleep",
+        lesson: "async",
+        kind: Kind::Choice {
+            question: "在 async 代码中调用 std::thread::sleep 有什么问题？",
+            options: &[
+                "阻塞整个工作线程，同一线程上的其他任务都无法推进；应该用 tokio::time::sleep",
+                "没有问题，和 tokio::time::sleep 效果一样",
+                "编译错误",
+                "程序会 panic",
+            ],
+        },
+        explanation: "异步运行时靠任务主动让出线程来调度，阻塞调用会让其他任务饿死；CPU 密集或阻塞的工作放到 spawn_blocking 中",
+    },
+    // ========== 15_std ==========
+    Question {
+        id: "std_lib.map_filter",
+        lesson: "std_lib",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let v = vec![3, 1, 2];
+    let big: Vec<i32> = v.iter().map(|x| x * 10).filter(|x| *x > 10).collect();
+    println!("{:?}", big);
+}
+"#,
+            output: "[30, 20]",
+        },
+        explanation: "先乘以 10 得到 30、10、20，再过滤掉不大于 10 的；迭代器保持原来的顺序",
+    },
+    Question {
+        id: "std_lib.zip",
+        lesson: "std_lib",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    let a = [1, 2, 3];
+    let b = [4, 5, 6];
+    let dot: i32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    println!("{dot}");
+}
+"#,
+            output: "32",
+        },
+        explanation: "zip 把两个迭代器按位置配对：1*4 + 2*5 + 3*6 = 32",
+    },
+    Question {
+        id: "std_lib.lazy_map",
+        lesson: "std_lib",
+        kind: Kind::Compiles {
+            code: r#"
+fn main() {
+    let v = vec![1, 2, 3];
+    let doubled: Vec<i32> = v.iter().map(|x| x * 2);
+    println!("{:?}", doubled);
+}
+"#,
+            compiles: false,
+        },
+        explanation: "map 返回的是惰性的迭代器适配器，不是 Vec（E0308）；要在最后调用 .collect()",
+    },
+    Question {
+        id: "std_lib.format_spec",
+        lesson: "std_lib",
+        kind: Kind::Output {
+            code: r#"
+fn main() {
+    println!("{:.2} {:03}", 3.14159, 7);
+}
+"#,
+            output: "3.14 007",
+        },
+        explanation: "{:.2} 保留两位小数，{:03} 宽度为 3 并用 0 填充",
+    },
+];
@@ -0,0 +1,445 @@
+// exercises/quiz.rs - 测验
+//   cargo run --bin exercises -- quiz            从所有课程中抽题
+//   cargo run --bin exercises -- quiz basic      考一个分组的所有课程（basic、ownership、generics、async）
+//   cargo run --bin exercises -- quiz references 只考一课（也可以写 06 或 06_references）
+//
+// 三种题型：选择题、"这段代码会打印什么"、"这段代码能编译通过吗"。
+// 题目和选项的顺序每次随机打乱，全部答完后统一评分并给出解析；每道题的对错记录在学习进度中
+
+use std::io::{self, BufRead, Write};
+
+use rand::seq::SliceRandom;
+
+use super::exercise::Topic;
+use super::progress::Progress;
+use super::questions::QUESTIONS;
+
+// 每次测验最多的题数
+const QUIZ_LENGTH: usize = 10;
+
+// 一课对应 src/ 下的一个课程文件；name 和 Cargo.toml 中的 bin 名称一致
+pub struct Lesson {
+    pub file: &'static str,
+    pub name: &'static str,
+    pub title: &'static str,
+    // 所属的练习分组
+    pub topic: Topic,
+}
+
+pub const LESSONS: [Lesson; 16] = [
+    Lesson {
+        file: "00_hello",
+        name: "hello",
+        title: "Hello World 与环境配置",
+        topic: Topic::Basic,
+    },
+    Lesson {
+        file: "01_variables",
+        name: "variables",
+        title: "变量和可变性",
+        topic: Topic::Basic,
+    },
+    Lesson {
+        file: "02_types",
+        name: "types",
+        title: "数据类型",
+        topic: Topic::Basic,
+    },
+    Lesson {
+        file: "03_functions",
+        name: "functions",
+        title: "函数与作用域",
+        topic: Topic::Basic,
+    },
+    Lesson {
+        file: "04_control",
+        name: "control",
+        title: "流程控制",
+        topic: Topic::Basic,
+    },
+    Lesson {
+        file: "05_ownership",
+        name: "ownership",
+        title: "所有权与借用",
+        topic: Topic::Ownership,
+    },
+    Lesson {
+        file: "06_references",
+        name: "references",
+        title: "引用与切片",
+        topic: Topic::Ownership,
+    },
+    Lesson {
+        file: "07_structs",
+        name: "structs",
+        title: "结构体和方法",
+        topic: Topic::Generics,
+    },
+    Lesson {
+        file: "08_enums",
+        name: "enums",
+        title: "枚举与模式匹配",
+        topic: Topic::Generics,
+    },
+    Lesson {
+        file: "09_collections",
+        name: "collections",
+        title: "常见集合类型",
+        topic: Topic::Generics,
+    },
+    Lesson {
+        file: "10_errors",
+        name: "errors",
+        title: "错误处理",
+        topic: Topic::Generics,
+    },
+    Lesson {
+        file: "11_generics",
+        name: "generics",
+        title: "泛型与 Traits",
+        topic: Topic::Generics,
+    },
+    Lesson {
+        file: "12_lifetimes",
+        name: "lifetimes",
+        title: "生命周期",
+        topic: Topic::Async,
+    },
+    Lesson {
+        file: "13_modules",
+        name: "modules",
+        title: "模块系统",
+        topic: Topic::Async,
+    },
+    Lesson {
+        file: "14_async",
+        name: "async",
+        title: "异步编程与并发",
+        topic: Topic::Async,
+    },
+    Lesson {
+        file: "15_std",
+        name: "std_lib",
+        title: "常用标准库",
+        topic: Topic::Async,
+    },
+];
+
+// 一次测验考哪些课程
+pub enum Scope {
+    All,
+    Topic(Topic),
+    Lesson(&'static Lesson),
+}
+
+impl Scope {
+    fn includes(&self, lesson: &str) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::Topic(topic) => LESSONS
+                .iter()
+                .any(|l| l.name == lesson && l.topic == *topic),
+            Scope::Lesson(l) => l.name == lesson,
+        }
+    }
+}
+
+// 分组名（basic、ownership、generics、async）考这个分组的所有课程，与课程同名时也按分组处理；
+// 其他的按课程名称（references）、编号（05、5）或文件名（05_ownership）查找一课
+pub fn find_scope(s: &str) -> Option<Scope> {
+    match s.parse::<Topic>() {
+        Ok(topic) => Some(Scope::Topic(topic)),
+        Err(_) => find_lesson(s).map(Scope::Lesson),
+    }
+}
+
+fn find_lesson(s: &str) -> Option<&'static Lesson> {
+    LESSONS.iter().find(|lesson| {
+        let number = &lesson.file[..2];
+        lesson.name == s || lesson.file == s || number == s || number.trim_start_matches('0') == s
+    })
+}
+
+pub struct Question {
+    // 唯一标识，学习进度中按它记录，例如 "ownership.move"
+    pub id: &'static str,
+    // 所属课程的 name
+    pub lesson: &'static str,
+    pub kind: Kind,
+    // 评分时展示的解析
+    pub explanation: &'static str,
+}
+
+pub enum Kind {
+    // 选择题；options 的第一项是正确答案
+    Choice {
+        question: &'static str,
+        options: &'static [&'static str],
+    },
+    // 这段代码会打印什么；比较时忽略空白的差异，多行输出可以用空格隔开
+    Output {
+        code: &'static str,
+        output: &'static str,
+    },
+    // 这段代码能编译通过吗
+    Compiles {
+        code: &'static str,
+        compiles: bool,
+    },
+}
+
+impl Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Choice { .. } => "选择题",
+            Kind::Output { .. } => "输出题",
+            Kind::Compiles { .. } => "编译题",
+        }
+    }
+}
+
+// 一道题的作答结果
+struct Answered {
+    question: &'static Question,
+    // 学习者的回答，已经整理成展示用的文字；跳过时为 None
+    given: Option<String>,
+    expected: String,
+    correct: bool,
+}
+
+// 一次输入；q 和输入结束由 read_input 返回 None 表示
+pub enum Input {
+    Answer(String),
+    Skip,
+}
+
+// 进行一次测验，从 scope 包含的课程中抽题
+pub fn run(scope: Scope, progress: &mut Progress) {
+    let mut rng = rand::rng();
+    let mut questions: Vec<&'static Question> = QUESTIONS
+        .iter()
+        .filter(|q| scope.includes(q.lesson))
+        .collect();
+    questions.shuffle(&mut rng);
+    questions.truncate(QUIZ_LENGTH);
+
+    match scope {
+        Scope::All => println!("🧠 综合测验 (共 {} 题)", questions.len()),
+        Scope::Topic(topic) => {
+            let files: Vec<_> = LESSONS
+                .iter()
+                .filter(|lesson| lesson.topic == topic)
+                .map(|lesson| lesson.file)
+                .collect();
+            println!(
+                "🧠 测验: {} ({}，共 {} 题)",
+                topic.title(),
+                files.join("、"),
+                questions.len()
+            );
+        }
+        Scope::Lesson(lesson) => println!(
+            "🧠 测验: {} {} (共 {} 题)",
+            lesson.file,
+            lesson.title,
+            questions.len()
+        ),
+    }
+    println!("每题输入答案后回车；直接回车跳过，输入 q 提前结束");
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut answered = Vec::new();
+    for (index, question) in questions.iter().enumerate() {
+        println!();
+        println!(
+            "── 第 {}/{} 题 · {} · {} ──",
+            index + 1,
+            questions.len(),
+            question.lesson,
+            question.kind.name()
+        );
+        let Some(result) = ask(question, &mut input, &mut rng) else {
+            break;
+        };
+        progress.record_quiz(question.id, result.correct);
+        answered.push(result);
+    }
+
+    print_score(&answered);
+}
+
+pub fn find_question(id: &str) -> Option<&'static Question> {
+    QUESTIONS.iter().find(|q| q.id == id)
+}
+
+// 打印题目，返回正确答案（展示用的文字）和选择题选项的展示顺序：
+// order[i] 是第 i 个展示的选项在 options 中的位置，0 是正确答案；其他题型的 order 为空
+pub fn present(question: &Question, rng: &mut impl rand::Rng) -> (String, Vec<usize>) {
+    match &question.kind {
+        Kind::Choice {
+            question: text,
+            options,
+        } => {
+            println!("{}", text);
+            let mut order: Vec<usize> = (0..options.len()).collect();
+            order.shuffle(rng);
+            for (i, &option) in order.iter().enumerate() {
+                println!("  {}. {}", letter(i), options[option]);
+            }
+            let correct = order.iter().position(|&option| option == 0).unwrap_or(0);
+            (format!("{}. {}", letter(correct), options[0]), order)
+        }
+        Kind::Output { code, output } => {
+            print_code(code);
+            println!("这段代码会打印什么？（多行输出用空格隔开）");
+            (normalize(output), Vec::new())
+        }
+        Kind::Compiles { code, compiles } => {
+            print_code(code);
+            println!("这段代码能编译通过吗？");
+            (yes_no(*compiles).to_string(), Vec::new())
+        }
+    }
+}
+
+// 提问并读取回答；输入 q 或输入结束时返回 None
+fn ask(
+    question: &'static Question,
+    input: &mut impl BufRead,
+    rng: &mut impl rand::Rng,
+) -> Option<Answered> {
+    let (expected, order) = present(question, rng);
+    let answered = |given: Option<String>, correct: bool| Answered {
+        question,
+        given,
+        expected: expected.clone(),
+        correct,
+    };
+    match &question.kind {
+        Kind::Choice { options, .. } => loop {
+            match read_input("选择 (A-D)", input)? {
+                Input::Skip => return Some(answered(None, false)),
+                Input::Answer(answer) => match parse_choice(&answer, options.len()) {
+                    Some(choice) => {
+                        let given = format!("{}. {}", letter(choice), options[order[choice]]);
+                        return Some(answered(Some(given), order[choice] == 0));
+                    }
+                    None => println!("  请输入 A 到 {} 之间的字母", letter(options.len() - 1)),
+                },
+            }
+        },
+        Kind::Output { .. } => match read_input("输出", input)? {
+            Input::Skip => Some(answered(None, false)),
+            Input::Answer(answer) => {
+                let correct = normalize(&answer) == expected;
+                Some(answered(Some(answer), correct))
+            }
+        },
+        Kind::Compiles { compiles, .. } => loop {
+            match read_input("y/n", input)? {
+                Input::Skip => return Some(answered(None, false)),
+                Input::Answer(answer) => match parse_yes_no(&answer) {
+                    Some(answer) => {
+                        let given = yes_no(answer).to_string();
+                        return Some(answered(Some(given), answer == *compiles));
+                    }
+                    None => println!("  请输入 y（能）或 n（不能）"),
+                },
+            }
+        },
+    }
+}
+
+// 读取一行输入；输入 q 或输入结束时返回 None
+pub fn read_input(label: &str, input: &mut impl BufRead) -> Option<Input> {
+    print!("  {} > ", label);
+    // 提示符没有换行，要手动刷新才能显示出来
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    // 输入结束（Ctrl-D 或管道读完）时和 q 一样提前结束
+    if input.read_line(&mut line).ok()? == 0 {
+        println!();
+        return None;
+    }
+    match line.trim() {
+        "q" | "Q" => None,
+        "" => Some(Input::Skip),
+        answer => Some(Input::Answer(answer.to_string())),
+    }
+}
+
+fn print_score(answered: &[Answered]) {
+    println!();
+    if answered.is_empty() {
+        println!("📭 没有作答任何题目");
+        return;
+    }
+    let correct = answered.iter().filter(|a| a.correct).count();
+    println!(
+        "📊 得分 {}/{} ({}%)",
+        correct,
+        answered.len(),
+        correct * 100 / answered.len()
+    );
+    for (index, answer) in answered.iter().enumerate() {
+        let icon = if answer.correct { "✅" } else { "❌" };
+        println!();
+        println!(
+            "  {} {}. {} ({})",
+            icon,
+            index + 1,
+            answer.question.id,
+            answer.question.kind.name()
+        );
+        if !answer.correct {
+            let given = answer.given.as_deref().unwrap_or("(跳过)");
+            println!("     你的答案: {}", given);
+            println!("     正确答案: {}", answer.expected);
+        }
+        println!("     💡 {}", answer.question.explanation);
+    }
+}
+
+fn print_code(code: &str) {
+    println!("```rust");
+    println!("{}", code.trim());
+    println!("```");
+}
+
+fn letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+// 接受字母（不区分大小写）或从 1 开始的序号
+fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    let index = match answer.parse::<usize>() {
+        Ok(number) => number.checked_sub(1)?,
+        Err(_) => {
+            let mut chars = answer.chars();
+            let c = chars.next()?.to_ascii_uppercase();
+            if chars.next().is_some() || !c.is_ascii_uppercase() {
+                return None;
+            }
+            (c as u8 - b'A') as usize
+        }
+    };
+    (index < count).then_some(index)
+}
+
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" | "能" | "是" => Some(true),
+        "n" | "no" | "不能" | "否" => Some(false),
+        _ => None,
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "能编译" } else { "不能编译" }
+}
+
+// 把所有空白（包括换行）压缩成一个空格
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,429 @@
+// exercises/registry.rs - 练习注册表
+// 每个写代码的练习由四个文件组成：
+//
+//   starters/<id>.rs    起始模板：函数签名 + todo!()，有的故意编译不过（例如悬垂引用）
+//   solutions/<id>.rs   学习者的实现，仓库中放的是起始模板的副本
+//   answers/<id>.rs     参考答案，只用于展示，不参与检查
+//   checks/<id>.rs      检查用例，对 solutions/<id>.rs 中的公开函数做断言
+//
+// 编译错误谜题没有检查用例：起始模板是一个带着指定错误（例如 E0382）的完整程序，
+// answers/<id>.rs 是修好的版本，检查时比较程序的输出和注册表中的 expected
+//
+// 所有练习都用 rustc 单独编译检查（见 grader.rs），solutions/ 中的代码不会编译进练习程序，
+// 所以起始模板编译不过也不影响练习程序本身。做题时直接修改 solutions/<id>.rs 后重新运行对应的分组，
+// 想重新开始就用 starter 命令覆盖它；也可以在任意位置写自己的文件，用 check 命令单独检查。
+// 修改参考答案后用 check <id> src/exercises/answers/<id>.rs 确认它能通过
+//
+// hints 从思路提示到具体位置逐级深入，最后一级提示是完整的参考答案（由 answers/<id>.rs 自动生成）
+//
+// tags 是比分组更细的知识点标签，用于 --tag 筛选；编译错误谜题都带有 "compile-error"
+//
+// lesson 是练习对应的课程编号（src/NN_*.rs 中的 NN）；all() 按 (分组, 课程) 排序，
+// 同一课中按注册表中的顺序，写代码的练习在前、编译错误谜题在后
+
+use super::exercise::{CodeExercise, Difficulty, Exercise, Puzzle, Topic};
+
+// 起始模板、检查用例和参考答案的源码作为文本保存，用来生成起始模板、检查学习者的文件和展示答案
+macro_rules! code_exercises {
+    ($(
+        $id:ident {
+            title: $title:literal,
+            topic: $topic:ident,
+            lesson: $lesson:literal,
+            difficulty: $difficulty:ident,
+            tags: [$($tag:literal),* $(,)?],
+            prompt: $prompt:literal,
+            hints: [$($hint:literal),* $(,)?] $(,)?
+        }
+    )*) => {
+        fn code_exercises() -> Vec<CodeExercise> {
+            vec![$(
+                CodeExercise {
+                    id: stringify!($id),
+                    title: $title,
+                    topic: Topic::$topic,
+                    lesson: $lesson,
+                    difficulty: Difficulty::$difficulty,
+                    tags: &[$($tag),*],
+                    prompt: $prompt,
+                    hints: &[$($hint),*],
+                    starter: include_str!(concat!("starters/", stringify!($id), ".rs")),
+                    solution: include_str!(concat!("answers/", stringify!($id), ".rs")),
+                    checks: include_str!(concat!("checks/", stringify!($id), ".rs")),
+                    path: concat!(env!("CARGO_MANIFEST_DIR"), "/src/exercises/solutions/", stringify!($id), ".rs"),
+                },
+            )*]
+        }
+    };
+}
+
+code_exercises! {
+    variables {
+        title: "变量和可变性",
+        topic: Basic,
+        lesson: 1,
+        difficulty: Easy,
+        tags: ["basics", "variables"],
+        prompt: "定义常量 MAX_POINTS，用可变变量实现 count_up，用变量遮蔽实现 shout",
+        hints: [
+            "常量用 const 定义，必须写出类型；要修改的变量需要 let mut；同一个名字可以用 let 再次绑定（遮蔽），新的值可以是不同的类型",
+            "MAX_POINTS 写成 `pub const MAX_POINTS: u32 = 100_000;`；count_up 中先 `let mut total = 0;` 再用 `for i in 1..=n` 累加；shout 依次写 `let s = s.trim();` 和 `let s = s.to_uppercase();`",
+        ],
+    }
+    types {
+        title: "数据类型和转换",
+        topic: Basic,
+        lesson: 2,
+        difficulty: Easy,
+        tags: ["basics", "types"],
+        prompt: "解构元组、对定长数组求和，并用 as 完成 f32 → u8 → char 的转换",
+        hints: [
+            "`let (a, b, c) = t;` 可以一次解构元组；数组可以用迭代器求和；as 转换可以连着写",
+            "reverse_tuple 返回 `(c, b, a)`；array_sum 写 `a.iter().sum()`；float_to_char 写 `x as u8 as char`",
+        ],
+    }
+    functions {
+        title: "函数定义和调用",
+        topic: Basic,
+        lesson: 3,
+        difficulty: Easy,
+        tags: ["basics", "functions", "closures"],
+        prompt: "实现返回 String 的函数、以表达式结尾的函数、递归函数和返回闭包的函数",
+        hints: [
+            "函数体最后一个没有分号的表达式就是返回值；递归就是函数调用自己；闭包可以捕获外部变量，返回闭包时用 impl Fn",
+            "add_one 的函数体只需要 `x + 1`（不要加分号）；factorial 写 `if n <= 1 { 1 } else { n * factorial(n - 1) }`；multiplier 返回 `move |x| x * factor`，没有 move 时闭包借用的 factor 活不过函数返回",
+        ],
+    }
+    control_flow {
+        title: "控制流和模式匹配",
+        topic: Basic,
+        lesson: 4,
+        difficulty: Easy,
+        tags: ["basics", "control-flow", "pattern-matching"],
+        prompt: "用 match 范围模式分类数字，用 loop/while 返回结果，用 if let 处理 Option",
+        hints: [
+            "match 支持 `1..=9` 这样的范围模式；loop 可以用 `break 值` 返回结果；while 在条件为 false 时停止；`if let Some(x) = value` 只匹配 Some",
+            "classify 依次写 `0`、`1..=9`、`10..=99`、`_` 四个分支；first_power_of_two_above 从 `n = 1` 开始，`n > limit` 时 `break n`，否则 `n *= 2`；sum_present 的 else 分支给 missing 加一",
+        ],
+    }
+    ownership {
+        title: "所有权基础",
+        topic: Ownership,
+        lesson: 5,
+        difficulty: Medium,
+        tags: ["ownership", "move"],
+        prompt: "通过返回值交还所有权，用 clone 得到独立的副本，用移动交换两个 String",
+        hints: [
+            "每个值只有一个所有者；把值传给函数会转移所有权，函数可以通过返回值再交还；clone 复制堆上的数据，移动不会",
+            "takes_and_gives_back 的参数要写成 `mut s: String` 才能 push_str；duplicate 返回 `(s.clone(), s)`；swap 用 `let (a, b) = pair;` 解构后返回 `(b, a)`，不需要 clone",
+        ],
+    }
+    references {
+        title: "引用和借用",
+        topic: Ownership,
+        lesson: 6,
+        difficulty: Medium,
+        tags: ["ownership", "borrowing"],
+        prompt: "用不可变引用和可变引用代替所有权转移，并修复返回悬垂引用的 dangle 函数",
+        hints: [
+            "`&T` 借用而不取得所有权，`&mut T` 允许修改；函数内创建的局部变量在函数返回时就被释放，它的引用不能带出函数",
+            "calculate_length 用 `s.chars().count()` 数字符；change 里对 s 调用 `push_str`；dangle 的返回类型 `-> &String` 和最后一行 `&s` 都要改：直接返回 String 本身",
+        ],
+    }
+    slices {
+        title: "切片操作",
+        topic: Ownership,
+        lesson: 6,
+        difficulty: Medium,
+        tags: ["ownership", "borrowing", "strings"],
+        prompt: "返回字符串和数组的切片，按字符边界安全地截取中文字符串",
+        hints: [
+            "`&s[a..b]` 按字节下标切片，下标必须落在字符边界上；`char_indices` 可以得到每个字符的字节位置",
+            "first_word 遍历 `s.as_bytes()` 找第一个 `b' '`；middle 在 `a.len() < 2` 时返回 `&[]`，否则 `&a[1..a.len() - 1]`；prefix_chars 用 `s.char_indices().nth(n)` 找到第 n 个字符的字节位置",
+        ],
+    }
+    structs {
+        title: "结构体和方法",
+        topic: Generics,
+        lesson: 7,
+        difficulty: Easy,
+        tags: ["structs", "methods"],
+        prompt: "为 User 实现关联函数和方法，为元组结构体 Color 实现十六进制格式化",
+        hints: [
+            "方法的第一个参数是 self：只读用 `&self`，修改用 `&mut self`；没有 self 的是关联函数，用 `User::new` 调用；元组结构体的字段用 `.0`、`.1`、`.2` 访问",
+            "User::new 中 `active` 设为 true，`username` 和 `email` 要 `to_string()`；is_adult 判断 `self.age >= 18`；to_hex 用 `format!(\"#{:02x}{:02x}{:02x}\", self.0, self.1, self.2)`",
+        ],
+    }
+    enums {
+        title: "枚举和模式匹配",
+        topic: Generics,
+        lesson: 8,
+        difficulty: Medium,
+        tags: ["enums", "pattern-matching", "option"],
+        prompt: "用 match 处理带数据的枚举变体，并对 Option 做运算",
+        hints: [
+            "match 必须覆盖所有变体；带数据的变体可以在模式中直接解构出字段；Option 有 map 方法",
+            "format_ip 中 `IpAddr::V4(a, b, c, d)` 解构出四个数，`IpAddr::V6(addr)` 返回 `addr.clone()`；describe 的 Move 分支写成 `Message::Move { x, y }`；plus_one 写 `x.map(|i| i + 1)`",
+        ],
+    }
+    collections {
+        title: "集合类型",
+        topic: Generics,
+        lesson: 9,
+        difficulty: Medium,
+        tags: ["collections", "hashmap"],
+        prompt: "就地修改切片中的元素，用 HashMap 的 entry API 统计单词和条件插入",
+        hints: [
+            "`for x in values` 遍历 `&mut [i32]` 时 x 是 `&mut i32`，修改要解引用 `*x`；HashMap 的 `entry(key).or_insert(默认值)` 返回值的可变引用",
+            "double_all 写 `*x *= 2`；word_count 写 `*counts.entry(word.to_string()).or_insert(0) += 1`；insert_if_absent 返回 `*scores.entry(team.to_string()).or_insert(score)`",
+        ],
+    }
+    error_handling {
+        title: "错误处理",
+        topic: Generics,
+        lesson: 10,
+        difficulty: Medium,
+        tags: ["errors", "result"],
+        prompt: "返回 Result，用 ? 传播错误，用 map_err 转换为自定义错误类型",
+        hints: [
+            "`Result<T, E>` 用 Ok 和 Err 表示成功和失败；`?` 在 Err 时提前返回；错误类型不同时用 map_err 转换",
+            "parse_and_double 写 `let n: i32 = s.trim().parse()?;`；parse_and_divide 对每个输入写 `.parse::<i32>().map_err(|_| AppError::InvalidNumber(s.to_string()))?`，除数为 0 时 `return Err(AppError::DivisionByZero)`",
+        ],
+    }
+    generics {
+        title: "泛型和Trait",
+        topic: Generics,
+        lesson: 11,
+        difficulty: Hard,
+        tags: ["generics", "traits"],
+        prompt: "编写泛型函数和泛型方法，定义带默认实现的 Trait 并用 impl Trait 作参数",
+        hints: [
+            "泛型参数写在尖括号中并用 Trait 约束它的能力；方法可以有独立于结构体的泛型参数；Trait 方法可以提供默认实现",
+            "largest 先 `let mut items = list.iter().copied();`，用 `items.next()?` 取第一个元素再逐个比较；mixup 返回 `Point { x: self.x, y: other.y }`；summarize 的默认实现写在 trait Summary 里并调用 `self.author()`，NewsArticle 要自己实现 summarize 覆盖它",
+        ],
+    }
+    lifetimes {
+        title: "生命周期",
+        topic: Async,
+        lesson: 12,
+        difficulty: Hard,
+        tags: ["lifetimes", "borrowing"],
+        prompt: "为返回引用的函数和持有引用的结构体标注正确的生命周期",
+        hints: [
+            "返回引用时编译器要知道它借用自哪个参数；生命周期参数 'a 把参数和返回值的生命周期关联起来；结构体持有引用时也要声明生命周期参数",
+            "longest 写成 `fn longest<'a>(x: &'a str, y: &'a str) -> &'a str`；find_containing 只给 items 中的 `&str` 和返回值标 'a，keyword 不标；结构体写成 `struct ImportantExcerpt<'a> { part: &'a str }`，impl 写 `impl<'a> ImportantExcerpt<'a>`",
+        ],
+    }
+    modules {
+        title: "模块系统",
+        topic: Async,
+        lesson: 13,
+        difficulty: Medium,
+        tags: ["modules"],
+        prompt: "组织嵌套模块，控制可见性，并用 pub use 重新导出私有模块中的函数",
+        hints: [
+            "模块中的项默认私有，pub 让外部可以访问；`pub use` 可以把私有模块中的项重新导出到当前模块",
+            "把 `mod advanced` 改成 `pub mod advanced`；在 utils 中加一行 `pub use self::string_utils::{is_palindrome, reverse};`；fibonacci 用两个变量迭代，递归算 fibonacci(90) 太慢",
+        ],
+    }
+    async_await {
+        title: "异步编程",
+        topic: Async,
+        lesson: 14,
+        difficulty: Hard,
+        tags: ["async", "future"],
+        prompt: "编写 async fn 和返回 async 块的函数，理解 Future 在 poll 之前不会执行",
+        hints: [
+            "async fn 返回 Future，在里面用 .await 等待其他 Future；`async move` 块会拿走捕获变量的所有权",
+            "sum_doubled 中对每个值 `sum += double(value).await`；greeting 先 `let name = name.to_string();` 再返回 `async move { format!(\"你好, {}\", name) }`；increment 写 `counter.set(counter.get() + 1)`",
+        ],
+    }
+    async_timeout {
+        title: "超时控制",
+        topic: Async,
+        lesson: 14,
+        difficulty: Medium,
+        tags: ["async", "tokio", "timeout"],
+        prompt: "用 tokio::time::timeout 给 future 加上时限，超时后放弃它，并实现限时重试",
+        hints: [
+            "`tokio::time::timeout(时限, future)` 本身也是一个 future，超时时返回 Err(Elapsed)，原来的 future 被 drop 后就不会再执行；重试时每次都要创建新的 future",
+            "with_timeout 写 `time::timeout(limit, future).await.map_err(|_| FetchError::Timeout)`；retry_with_timeout 用 `for attempt in 0..attempts`，`if let Ok(value) = with_timeout(limit, make(attempt)).await` 时返回，循环结束后返回超时",
+        ],
+    }
+    async_join {
+        title: "并发汇总 (join!)",
+        topic: Async,
+        lesson: 14,
+        difficulty: Medium,
+        tags: ["async", "tokio", "concurrency"],
+        prompt: "用 tokio::join! 同时发出多个查询，再用 JoinSet 为每个 id 启动任务并汇总结果",
+        hints: [
+            "依次 .await 会一个接一个地等；`tokio::join!(a, b, c)` 在同一个任务中同时推进几个 future，全部完成后返回结果元组；数量不固定时用 JoinSet::spawn 启动任务，再用 join_next 逐个取回",
+            "load_dashboard 写 `let (user, orders, points) = tokio::join!(load_user(id), load_orders(id), load_points(id));`，orders 字段是 `orders.len()`；total_points 先对每个 id `tasks.spawn(load_points(id))`，再 `while let Some(points) = tasks.join_next().await` 累加 `points.unwrap()`",
+        ],
+    }
+    async_pipeline {
+        title: "有界通道流水线",
+        topic: Async,
+        lesson: 14,
+        difficulty: Hard,
+        tags: ["async", "tokio", "channels", "concurrency"],
+        prompt: "用 tokio::sync::mpsc 有界通道把生产、处理、收集三个阶段串成流水线，通道满时等待，下游关闭时停止",
+        hints: [
+            "`mpsc::channel(容量)` 返回 (Sender, Receiver)；通道满时 `send().await` 会等待，接收端关闭后返回 Err；所有 Sender 都被 drop 后 `recv().await` 返回 None",
+            "produce 和 square 在 `send(..).await.is_err()` 时 break；square 用 `while let Some(value) = rx.recv().await`；pipeline 创建两个通道，`tokio::spawn(produce(items, numbers_tx))` 和 `tokio::spawn(square(numbers_rx, squares_tx))`，再循环 `squares_rx.recv()` 收集结果",
+        ],
+    }
+    async_semaphore {
+        title: "限流抓取 (Semaphore)",
+        topic: Async,
+        lesson: 14,
+        difficulty: Hard,
+        tags: ["async", "tokio", "concurrency"],
+        prompt: "为每个网址启动一个任务，用 tokio::sync::Semaphore 限制同时抓取的数量，并按输入顺序返回结果",
+        hints: [
+            "Semaphore 有 limit 个许可，拿到许可的任务才能开始抓取，许可被 drop 时归还；spawn 的任务要求 'static，所以信号量要放进 Arc，网址要复制成 String；任务完成的顺序不固定，要记下每个结果的下标",
+            "先 `let semaphore = Arc::new(Semaphore::new(limit.max(1)));`，每个任务中 `let _permit = semaphore.acquire_owned().await.unwrap();` 再 `(index, fetch(url).await)`；用 `vec![0; urls.len()]` 接收 join_next 的结果，按 index 放回",
+        ],
+    }
+    std_lib {
+        title: "标准库综合应用",
+        topic: Async,
+        lesson: 15,
+        difficulty: Medium,
+        tags: ["iterators", "io"],
+        prompt: "用迭代器链处理数据，读写文件，构建路径",
+        hints: [
+            "迭代器适配器 filter、map 可以串起来，最后用 sum 或 collect 消费；`fs::write` 和 `fs::read_to_string` 一次读写整个文件；PathBuf 有 push 和 set_extension",
+            "even_square_sum 写 `numbers.iter().filter(|&&x| x % 2 == 0).map(|&x| x * x).sum()`；word_lengths 用 `split_whitespace` 和 `chars().count()`；write_lines 拼好内容后 `fs::write`，再用 `fs::read_to_string(path)?.lines()` 读回",
+        ],
+    }
+}
+
+// 编译错误谜题只需要起始模板和参考答案的源码
+macro_rules! puzzles {
+    ($(
+        $id:ident {
+            title: $title:literal,
+            topic: $topic:ident,
+            lesson: $lesson:literal,
+            difficulty: $difficulty:ident,
+            tags: [$($tag:literal),* $(,)?],
+            error_code: $error_code:literal,
+            prompt: $prompt:literal,
+            expected: $expected:literal,
+            hints: [$($hint:literal),* $(,)?] $(,)?
+        }
+    )*) => {
+        fn puzzles() -> Vec<Puzzle> {
+            vec![$(
+                Puzzle {
+                    id: stringify!($id),
+                    title: $title,
+                    topic: Topic::$topic,
+                    lesson: $lesson,
+                    difficulty: Difficulty::$difficulty,
+                    tags: &[$($tag),*],
+                    prompt: $prompt,
+                    hints: &[$($hint),*],
+                    starter: include_str!(concat!("starters/", stringify!($id), ".rs")),
+                    solution: include_str!(concat!("answers/", stringify!($id), ".rs")),
+                    error_code: $error_code,
+                    expected_output: $expected,
+                    path: concat!(env!("CARGO_MANIFEST_DIR"), "/src/exercises/solutions/", stringify!($id), ".rs"),
+                },
+            )*]
+        }
+    };
+}
+
+puzzles! {
+    use_after_move {
+        title: "修复：使用已经移动的值",
+        topic: Ownership,
+        lesson: 5,
+        difficulty: Easy,
+        tags: ["ownership", "move", "compile-error"],
+        error_code: "E0382",
+        prompt: "name 传给函数后所有权被移走了，修复 E0382，让程序打印 name 的长度和大写形式",
+        expected: "ferris 有 6 个字节\nferris 的大写是 FERRIS",
+        hints: [
+            "把 String 按值传给函数会移动所有权，之后原来的变量就失效了；函数只需要读取数据时，借用就够了",
+            "把 byte_len 的参数改成 `s: &str`，调用处写 `byte_len(&name)`；也可以传 `name.clone()`，但会多复制一次",
+        ],
+    }
+    two_mutable_borrows {
+        title: "修复：同时存在两个可变引用",
+        topic: Ownership,
+        lesson: 6,
+        difficulty: Medium,
+        tags: ["ownership", "borrowing", "compile-error"],
+        error_code: "E0499",
+        prompt: "first 还在使用时又创建了 last 这个可变引用，修复 E0499，让第一个和最后一个元素都加 10",
+        expected: "[11, 2, 13]",
+        hints: [
+            "同一时刻只能有一个可变引用；引用的生命从创建开始，到最后一次使用结束",
+            "把 `*first += 10;` 移到 `let last = ...` 之前，first 用完之后再借用 scores；也可以用 `split_first_mut` 一次拿到两部分",
+        ],
+    }
+    borrow_while_mutating {
+        title: "修复：持有不可变引用时修改",
+        topic: Ownership,
+        lesson: 6,
+        difficulty: Medium,
+        tags: ["ownership", "borrowing", "compile-error"],
+        error_code: "E0502",
+        prompt: "first 借用 numbers 期间调用了 push，修复 E0502，让程序打印第一个元素和元素个数",
+        expected: "第一个元素: 1, 共 4 个",
+        hints: [
+            "存在不可变引用时不能再可变借用；push 可能让 Vec 重新分配内存，旧的引用会指向已经释放的内存",
+            "`let first = &numbers[0];` 改成 `let first = numbers[0];`（i32 是 Copy，复制出值就不再借用），或者把 push 移到 println! 之后再单独打印个数",
+        ],
+    }
+    missing_lifetime {
+        title: "修复：缺少生命周期标注",
+        topic: Async,
+        lesson: 12,
+        difficulty: Medium,
+        tags: ["lifetimes", "compile-error"],
+        error_code: "E0106",
+        prompt: "first_line 有两个引用参数，编译器推断不出返回值的生命周期，修复 E0106",
+        expected: "标题: Rust 所有权\n标题: (无标题)",
+        hints: [
+            "只有一个引用参数时，返回值的生命周期可以省略；有多个时要用生命周期参数说明返回值借用自哪里",
+            "返回值可能来自 text 或 fallback，签名改成 `fn first_line<'a>(text: &'a str, fallback: &'a str) -> &'a str`",
+        ],
+    }
+    short_lived_borrow {
+        title: "修复：借用的值活得不够久",
+        topic: Async,
+        lesson: 12,
+        difficulty: Hard,
+        tags: ["lifetimes", "borrowing", "compile-error"],
+        error_code: "E0597",
+        prompt: "result 可能借用内部作用域中的 string2，修复 E0597（不要修改 longest）",
+        expected: "最长的字符串是: 'long string is long'",
+        hints: [
+            "longest 的返回值和两个参数共享同一个生命周期 'a，编译器认为 result 可能借用 string2，而 string2 在内部作用域结束时就被释放了",
+            "去掉内部的 `{ }`，让 string2 和 result 在同一个作用域中；或者把 println! 移到内部作用域里",
+        ],
+    }
+}
+
+// 按课程顺序返回所有练习：先按分组，再按课程编号；同一课中写代码的练习在前、编译错误谜题在后
+pub fn all() -> Vec<Box<dyn Exercise>> {
+    let code = code_exercises()
+        .into_iter()
+        .map(|exercise| Box::new(exercise) as Box<dyn Exercise>);
+    let puzzles = puzzles()
+        .into_iter()
+        .map(|puzzle| Box::new(puzzle) as Box<dyn Exercise>);
+    let mut exercises: Vec<_> = code.chain(puzzles).collect();
+    // 稳定排序，保持同一课中的顺序
+    exercises.sort_by_key(|exercise| (exercise.topic(), exercise.lesson()));
+    exercises
+}
+
+pub fn find(id: &str) -> Option<Box<dyn Exercise>> {
+    all().into_iter().find(|exercise| exercise.id() == id)
+}
@@ -0,0 +1,260 @@
+// exercises/results.rs - 把检查结果导出为文件，给 CI 和学习看板使用
+//   cargo run --bin exercises -- all --report json results.json
+//   cargo run --bin exercises -- ownership --report junit results.xml
+//
+// JSON:
+//   {
+//     "summary": { "total": 21, "passed": 20, "failed": 1, "errors": 0, "duration_ms": 812.4 },
+//     "exercises": [
+//       {
+//         "id": "references",
+//         "title": "引用和借用",
+//         "topic": "ownership",
+//         "status": "failed",               // passed / failed / error
+//         "duration_ms": 0.05,
+//         "failure": "dangle: ...",         // 通过时为 null
+//         "cases": [{ "name": "...", "status": "passed", "duration_ms": 0.01, "failure": null }]
+//       }
+//     ]
+//   }
+//
+// JUnit XML 每个分组是一个 <testsuite>，每个练习是一个 <testcase>；
+// 断言失败记为 <failure>，编译失败、超时等没能跑完检查的记为 <error>
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use super::exercise::{Exercise, Report, Topic};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Json,
+    Junit,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "junit" => Ok(Format::Junit),
+            _ => Err(format!("未知报告格式: {}（可选 json、junit）", s)),
+        }
+    }
+}
+
+// 一个练习的检查结果
+pub struct ExerciseResult {
+    pub id: String,
+    pub title: String,
+    pub topic: Topic,
+    pub report: Report,
+}
+
+impl ExerciseResult {
+    pub fn new(exercise: &dyn Exercise, report: Report) -> ExerciseResult {
+        ExerciseResult {
+            id: exercise.id().to_string(),
+            title: exercise.title().to_string(),
+            topic: exercise.topic(),
+            report,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.report.passed()
+    }
+
+    fn status(&self) -> &'static str {
+        if self.report.error.is_some() {
+            "error"
+        } else if self.report.passed() {
+            "passed"
+        } else {
+            "failed"
+        }
+    }
+
+    // 没通过的原因：没能跑完检查时是错误信息，否则是每个失败用例的 "名称: 消息"
+    fn failure(&self) -> Option<String> {
+        if let Some(error) = &self.report.error {
+            return Some(error.clone());
+        }
+        if self.report.passed() {
+            return None;
+        }
+        let failures: Vec<_> = self
+            .report
+            .cases
+            .iter()
+            .filter_map(|case| {
+                let message = case.failure.as_ref()?;
+                Some(format!("{}: {}", case.name, message))
+            })
+            .collect();
+        if failures.is_empty() {
+            // 没有任何用例
+            Some("没有运行任何检查".to_string())
+        } else {
+            Some(failures.join("\n"))
+        }
+    }
+}
+
+pub fn write(format: Format, path: &Path, results: &[ExerciseResult]) -> io::Result<()> {
+    let content = match format {
+        Format::Json => {
+            serde_json::to_string_pretty(&to_json(results)).map_err(io::Error::other)? + "\n"
+        }
+        Format::Junit => to_junit(results),
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, content)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn to_json(results: &[ExerciseResult]) -> Value {
+    let count = |status: &str| results.iter().filter(|r| r.status() == status).count();
+    let total: Duration = results.iter().map(|r| r.report.duration()).sum();
+    let exercises: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let cases: Vec<Value> = result
+                .report
+                .cases
+                .iter()
+                .map(|case| {
+                    json!({
+                        "name": case.name,
+                        "status": if case.failure.is_none() { "passed" } else { "failed" },
+                        "duration_ms": millis(case.duration),
+                        "failure": case.failure,
+                    })
+                })
+                .collect();
+            json!({
+                "id": result.id,
+                "title": result.title,
+                "topic": result.topic.name(),
+                "status": result.status(),
+                "duration_ms": millis(result.report.duration()),
+                "failure": result.failure(),
+                "cases": cases,
+            })
+        })
+        .collect();
+    json!({
+        "summary": {
+            "total": results.len(),
+            "passed": count("passed"),
+            "failed": count("failed"),
+            "errors": count("error"),
+            "duration_ms": millis(total),
+        },
+        "exercises": exercises,
+    })
+}
+
+fn to_junit(results: &[ExerciseResult]) -> String {
+    let count = |results: &[&ExerciseResult], status: &str| {
+        results.iter().filter(|r| r.status() == status).count()
+    };
+    let seconds = |results: &[&ExerciseResult]| {
+        results
+            .iter()
+            .map(|r| r.report.duration())
+            .sum::<Duration>()
+            .as_secs_f64()
+    };
+
+    let all: Vec<_> = results.iter().collect();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"exercises\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">\n",
+        all.len(),
+        count(&all, "failed"),
+        count(&all, "error"),
+        seconds(&all)
+    ));
+    for topic in Topic::ALL {
+        let in_topic: Vec<_> = results.iter().filter(|r| r.topic == topic).collect();
+        if in_topic.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">\n",
+            topic.name(),
+            in_topic.len(),
+            count(&in_topic, "failed"),
+            count(&in_topic, "error"),
+            seconds(&in_topic)
+        ));
+        for result in in_topic {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+                topic.name(),
+                escape_xml(&result.id),
+                result.report.duration().as_secs_f64()
+            ));
+            let tag = match result.status() {
+                "error" => "error",
+                "failed" => "failure",
+                _ => {
+                    out.push_str("/>\n");
+                    continue;
+                }
+            };
+            let failure = result.failure().unwrap_or_default();
+            // 编译失败时把编译器的诊断信息放在正文中
+            let mut body = failure.clone();
+            if !result.report.diagnostics.is_empty() {
+                body = format!("{}\n\n{}", body, result.report.diagnostics);
+            }
+            out.push_str(&format!(
+                ">\n      <{0} message=\"{1}\">{2}</{0}>\n",
+                tag,
+                escape_xml(failure.lines().next().unwrap_or_default()),
+                escape_xml(&body)
+            ));
+            if !result.report.output.is_empty() {
+                out.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    escape_xml(&result.report.output)
+                ));
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+// XML 1.0 不允许除制表符和换行以外的控制字符，用 U+FFFD 代替
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => out.push('\u{FFFD}'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
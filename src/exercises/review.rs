@@ -0,0 +1,237 @@
+// exercises/review.rs - 间隔重复复习
+//   cargo run --bin exercises -- review
+//
+// 通过的练习和做过的测验题会变成复习卡片，按 SM-2 算法安排下一次复习的日期：
+// 每次复习时学习者先自己回想，再对照答案给自己打分（0-5），
+// 记得越牢间隔越长（1 天、6 天、之后每次乘以难度系数），忘记了就从 1 天重新开始
+//
+// SM-2 算法见 https://super-memory.com/english/ol/sm2.htm
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::exercise::Exercise;
+use super::progress::{self, Progress};
+use super::quiz::{self, Input, Question};
+use super::registry;
+
+// 每次复习最多的卡片数
+const SESSION_LENGTH: usize = 20;
+
+// 新卡片的难度系数；SM-2 规定不低于 1.3
+const INITIAL_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+
+// 自评分数的含义；3 分及以上算记住了
+const GRADES: [&str; 6] = [
+    "完全不记得",
+    "想错了，看到答案才想起来",
+    "想错了，但答案很眼熟",
+    "想起来了，但很吃力",
+    "想起来了，稍有犹豫",
+    "轻松想起",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Card {
+    // 难度系数：每次记住后间隔乘以它
+    pub ease: f64,
+    // 当前间隔（天）
+    pub interval: u32,
+    // 连续记住的次数；忘记时清零
+    pub repetitions: u32,
+    // 下一次复习的日期（UTC），例如 "2025-10-19"
+    pub due: String,
+    pub last_reviewed: Option<String>,
+}
+
+impl Default for Card {
+    fn default() -> Card {
+        Card::new(0)
+    }
+}
+
+impl Card {
+    // due_in 天后第一次复习
+    pub fn new(due_in: u32) -> Card {
+        Card {
+            ease: INITIAL_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: progress::date_after(due_in),
+            last_reviewed: None,
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        // 日期都是 "YYYY-MM-DD" 格式，按字符串比较就是按日期比较
+        self.due <= progress::today()
+    }
+
+    // 按自评分数（0-5）更新难度系数和下一次复习的日期
+    pub fn grade(&mut self, quality: u8) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+        let q = f64::from(5 - quality);
+        let ease = (self.ease + 0.1 - q * (0.08 + q * 0.02)).max(MIN_EASE);
+        // 保留两位小数，进度文件中不会出现 1.7000000000000002 这样的值
+        self.ease = (ease * 100.0).round() / 100.0;
+        self.due = progress::date_after(self.interval);
+        self.last_reviewed = Some(progress::today());
+    }
+}
+
+// 卡片的键：练习是 "exercise:<练习ID>"，测验题是 "quiz:<题目ID>"
+pub fn exercise_key(id: &str) -> String {
+    format!("exercise:{}", id)
+}
+
+pub fn quiz_key(id: &str) -> String {
+    format!("quiz:{}", id)
+}
+
+enum Item {
+    Exercise(Box<dyn Exercise>),
+    Question(&'static Question),
+}
+
+// 找到卡片对应的练习或测验题；已经从注册表或题库中删除的返回 None
+fn item(key: &str) -> Option<Item> {
+    if let Some(id) = key.strip_prefix("exercise:") {
+        registry::find(id).map(Item::Exercise)
+    } else if let Some(id) = key.strip_prefix("quiz:") {
+        quiz::find_question(id).map(Item::Question)
+    } else {
+        None
+    }
+}
+
+// 到期的卡片，最早到期的在前
+fn due_items(progress: &Progress) -> Vec<(String, Item)> {
+    let mut due: Vec<_> = progress
+        .review
+        .iter()
+        .filter(|(_, card)| card.is_due())
+        .collect();
+    due.sort_by(|(_, a), (_, b)| a.due.cmp(&b.due));
+    due.into_iter()
+        .filter_map(|(key, _)| Some((key.clone(), item(key)?)))
+        .collect()
+}
+
+pub fn due_count(progress: &Progress) -> usize {
+    due_items(progress).len()
+}
+
+// 复习到期的卡片
+pub fn run(progress: &mut Progress) {
+    let mut due = due_items(progress);
+    if due.is_empty() {
+        match progress.review.values().map(|card| &card.due).min() {
+            Some(next) => println!(
+                "🎉 今天没有需要复习的内容，下一次复习在 {}（共 {} 张卡片）",
+                next,
+                progress.review.len()
+            ),
+            None => println!("📭 还没有复习卡片：通过练习或做过测验后，它们会自动加入复习计划"),
+        }
+        return;
+    }
+    let total_due = due.len();
+    due.truncate(SESSION_LENGTH);
+
+    println!(
+        "🔁 复习 (今天到期 {} 项，本次复习 {} 项)",
+        total_due,
+        due.len()
+    );
+    println!("先自己回想，按回车对照答案，再给自己打分 (0-5)；输入 q 提前结束");
+
+    let mut rng = rand::rng();
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut grades = Vec::new();
+    'session: for (index, (key, item)) in due.iter().enumerate() {
+        println!();
+        match item {
+            Item::Question(question) => {
+                println!(
+                    "── {}/{} · 测验 {} · {} ──",
+                    index + 1,
+                    due.len(),
+                    question.id,
+                    question.kind.name()
+                );
+                let (answer, _) = quiz::present(question, &mut rng);
+                if quiz::read_input("按回车显示答案", &mut input).is_none() {
+                    break;
+                }
+                println!("  ✔ 答案: {}", answer);
+                println!("  💡 {}", question.explanation);
+            }
+            Item::Exercise(exercise) => {
+                println!(
+                    "── {}/{} · 练习 {} · {} ──",
+                    index + 1,
+                    due.len(),
+                    exercise.id(),
+                    exercise.title()
+                );
+                println!("  🎯 任务: {}", exercise.prompt());
+                println!("回想一下解题思路和关键的写法");
+                if quiz::read_input("按回车对照提示", &mut input).is_none() {
+                    break;
+                }
+                for hint in exercise.hints() {
+                    println!("  💡 {}", hint);
+                }
+                println!(
+                    "  想动手再做一遍: cargo run --bin exercises -- starter {0} > my_{0}.rs",
+                    exercise.id()
+                );
+            }
+        }
+
+        for (grade, meaning) in GRADES.iter().enumerate() {
+            println!("    {} {}", grade, meaning);
+        }
+        let grade = loop {
+            match quiz::read_input("评分 (0-5)", &mut input) {
+                None => break 'session,
+                Some(Input::Answer(answer)) => match answer.parse::<u8>() {
+                    Ok(grade) if grade <= 5 => break grade,
+                    _ => println!("  请输入 0 到 5 之间的数字"),
+                },
+                Some(Input::Skip) => println!("  请输入 0 到 5 之间的数字"),
+            }
+        };
+        let card = progress.review_card(key, grade);
+        println!("  📅 下次复习: {}（{} 天后）", card.due, card.interval);
+        grades.push(grade);
+    }
+
+    println!();
+    if grades.is_empty() {
+        println!("📭 没有复习任何卡片");
+        return;
+    }
+    let remembered = grades.iter().filter(|&&grade| grade >= 3).count();
+    println!(
+        "📊 本次复习 {} 项，记住 {} 项；今天还有 {} 项到期",
+        grades.len(),
+        remembered,
+        due_count(progress)
+    );
+}
@@ -0,0 +1,136 @@
+// exercises/sandbox.rs - 受限地运行子进程
+// 编译器和学习者的程序都在子进程中运行，并且受到以下限制：
+//   - 运行时间：超时后杀掉进程（死循环）
+//   - 输出大小：stdout、stderr 各自最多保留 max_output 字节，超过后杀掉进程（刷屏）
+//   - 网络：Linux 上用 unshare 把程序放进一个新的网络命名空间，里面只有一个未启用的回环接口
+//
+// 这不是安全边界：程序仍然可以读写文件。它只是防止练习代码中的错误拖垮终端或者意外地访问网络
+
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub timeout: Duration,
+    pub max_output: usize,
+}
+
+pub struct Output {
+    // 被杀掉时为 None
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+    pub timed_out: bool,
+    // 输出超过上限被截断
+    pub truncated: bool,
+    pub elapsed: Duration,
+}
+
+// 运行命令直到退出、超时或输出超过上限
+pub fn run(mut command: Command, limits: Limits) -> io::Result<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let overflow = Arc::new(AtomicBool::new(false));
+    let stdout = capture(child.stdout.take(), limits.max_output, &overflow);
+    let stderr = capture(child.stderr.take(), limits.max_output, &overflow);
+
+    let deadline = start + limits.timeout;
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if overflow.load(Ordering::Relaxed) || Instant::now() >= deadline {
+            timed_out = !overflow.load(Ordering::Relaxed);
+            // 进程可能刚好在这之间退出，kill 出错可以忽略
+            let _ = child.kill();
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let elapsed = start.elapsed();
+
+    let text = |handle: JoinHandle<Vec<u8>>| {
+        String::from_utf8_lossy(&handle.join().unwrap_or_default()).into_owned()
+    };
+    Ok(Output {
+        status,
+        stdout: text(stdout),
+        stderr: text(stderr),
+        timed_out,
+        truncated: overflow.load(Ordering::Relaxed),
+        elapsed,
+    })
+}
+
+// 在后台线程中读取管道；stdout 和 stderr 要同时读，否则一个管道写满后子进程会阻塞
+fn capture<R: Read + Send + 'static>(
+    pipe: Option<R>,
+    limit: usize,
+    overflow: &Arc<AtomicBool>,
+) -> JoinHandle<Vec<u8>> {
+    let overflow = Arc::clone(overflow);
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let Some(mut pipe) = pipe else {
+            return output;
+        };
+        let mut chunk = [0u8; 8192];
+        loop {
+            match pipe.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    let room = limit - output.len();
+                    output.extend_from_slice(&chunk[..n.min(room)]);
+                    if n > room {
+                        overflow.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                }
+            }
+        }
+        output
+    })
+}
+
+// 不能访问网络的命令；当前系统不支持时返回普通命令，并且只提示一次
+pub fn offline_command(program: &Path) -> Command {
+    if network_isolation_available() {
+        let mut command = Command::new("unshare");
+        // --map-root-user 让普通用户也能创建网络命名空间
+        command
+            .args(["--net", "--map-root-user", "--"])
+            .arg(program);
+        return command;
+    }
+    static WARNED: OnceLock<()> = OnceLock::new();
+    WARNED.get_or_init(|| {
+        eprintln!("⚠️ 无法隔离网络（需要 Linux 的 unshare 命令和非特权用户命名空间），检查程序可以访问网络");
+    });
+    Command::new(program)
+}
+
+// 试着创建一次网络命名空间；结果缓存起来
+fn network_isolation_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        cfg!(target_os = "linux")
+            && Command::new("unshare")
+                .args(["--net", "--map-root-user", "true"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success())
+    })
+}
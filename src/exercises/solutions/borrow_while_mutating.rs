@@ -0,0 +1,10 @@
+// 编译错误谜题: 持有不可变引用时修改 (E0502)
+// TODO: 修复编译错误，让程序打印:
+//   第一个元素: 1, 共 4 个
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let first = &numbers[0];
+    numbers.push(4);
+    println!("第一个元素: {}, 共 {} 个", first, numbers.len());
+}
@@ -0,0 +1,21 @@
+// 练习: 控制流和模式匹配
+
+// 0 → "零"，1~9 → "个位数"，10~99 → "两位数"，其他 → "很大"；用 match 的范围模式
+pub fn classify(n: u32) -> &'static str {
+    todo!()
+}
+
+// 第一个大于 limit 的 2 的幂；用 loop 和 break 返回值
+pub fn first_power_of_two_above(limit: u32) -> u32 {
+    todo!()
+}
+
+// 从 from 倒数到 1，例如 countdown(3) == [3, 2, 1]；用 while
+pub fn countdown(from: u32) -> Vec<u32> {
+    todo!()
+}
+
+// 返回 (所有 Some 中的值之和, None 的个数)；用 if let ... else
+pub fn sum_present(values: &[Option<i32>]) -> (i32, usize) {
+    todo!()
+}
@@ -0,0 +1,21 @@
+// 练习: 函数定义和调用
+
+// greet("学习者") == "你好, 学习者! 欢迎学习 Rust!"
+pub fn greet(name: &str) -> String {
+    todo!()
+}
+
+// 不要写 return
+pub fn add_one(x: i32) -> i32 {
+    todo!()
+}
+
+// 用递归实现，factorial(0) == 1
+pub fn factorial(n: u32) -> u32 {
+    todo!()
+}
+
+// 返回一个把参数乘以 factor 的闭包
+pub fn multiplier(factor: i32) -> impl Fn(i32) -> i32 {
+    |x| todo!()
+}
@@ -0,0 +1,55 @@
+// 练习: 泛型和Trait
+
+// 最大的元素；空切片返回 None
+pub fn largest<T: PartialOrd + Copy>(list: &[T]) -> Option<T> {
+    todo!()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Point<T, U> {
+    pub x: T,
+    pub y: U,
+}
+
+impl<T, U> Point<T, U> {
+    // 取 self 的 x 和 other 的 y 组成新的点
+    pub fn mixup<V, W>(self, other: Point<V, W>) -> Point<T, W> {
+        todo!()
+    }
+}
+
+pub trait Summary {
+    fn author(&self) -> String;
+
+    // TODO: 默认实现返回 "(阅读更多来自 {author} 的内容...)"
+    fn summarize(&self) -> String;
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+}
+
+// author 是 "@用户名"；summarize 使用默认实现
+impl Summary for Tweet {
+    fn author(&self) -> String {
+        todo!()
+    }
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub author: String,
+}
+
+// summarize 返回 "标题, by 作者"
+impl Summary for NewsArticle {
+    fn author(&self) -> String {
+        todo!()
+    }
+}
+
+// 返回 "突发新闻! " 加上 item 的摘要
+pub fn notify(item: &impl Summary) -> String {
+    todo!()
+}
@@ -0,0 +1,15 @@
+// 编译错误谜题: 缺少生命周期标注 (E0106)
+// TODO: 修复编译错误，让程序打印:
+//   标题: Rust 所有权
+//   标题: (无标题)
+
+// 返回 text 的第一行；text 为空时返回 fallback
+fn first_line(text: &str, fallback: &str) -> &str {
+    text.lines().next().unwrap_or(fallback)
+}
+
+fn main() {
+    let article = String::from("Rust 所有权\n每个值都有一个所有者");
+    println!("标题: {}", first_line(&article, "(无标题)"));
+    println!("标题: {}", first_line("", "(无标题)"));
+}
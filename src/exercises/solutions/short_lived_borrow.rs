@@ -0,0 +1,17 @@
+// 编译错误谜题: 借用的值活得不够久 (E0597)
+// TODO: 修复编译错误，不要修改 longest，让程序打印:
+//   最长的字符串是: 'long string is long'
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+fn main() {
+    let string1 = String::from("long string is long");
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = longest(string1.as_str(), string2.as_str());
+    }
+    println!("最长的字符串是: '{}'", result);
+}
@@ -0,0 +1,17 @@
+// 练习: 切片操作
+
+// 第一个空格之前的部分；没有空格时返回整个字符串
+pub fn first_word(s: &str) -> &str {
+    todo!()
+}
+
+// 去掉第一个和最后一个元素；不足两个元素时返回空切片
+pub fn middle(a: &[i32]) -> &[i32] {
+    todo!()
+}
+
+// 前 n 个字符（不是字节）；s 不足 n 个字符时返回整个 s
+// 提示: 中文字符占 3 个字节，&s[..n] 可能切在字符中间而 panic
+pub fn prefix_chars(s: &str, n: usize) -> &str {
+    todo!()
+}
@@ -0,0 +1,24 @@
+// 练习: 标准库综合应用
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+// 偶数的平方和；用 filter、map、sum 组成迭代器链
+pub fn even_square_sum(numbers: &[i32]) -> i32 {
+    todo!()
+}
+
+// 每个单词（按空白分隔）的字符数
+pub fn word_lengths(text: &str) -> Vec<usize> {
+    todo!()
+}
+
+// 把每一行写入文件（每行以 '\n' 结尾），再按行读回
+pub fn write_lines(path: &Path, lines: &[&str]) -> io::Result<Vec<String>> {
+    todo!()
+}
+
+// dir/name.ext，例如 ("/tmp", "test", "rs") → "/tmp/test.rs"
+pub fn build_path(dir: &str, name: &str, ext: &str) -> PathBuf {
+    todo!()
+}
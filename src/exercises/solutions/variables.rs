@@ -0,0 +1,14 @@
+// 练习: 变量和可变性
+
+// TODO: 定义常量 MAX_POINTS，类型为 u32，值为十万
+// pub const MAX_POINTS ...
+
+// 1 + 2 + ... + n，用一个可变变量累加
+pub fn count_up(n: u32) -> u32 {
+    todo!()
+}
+
+// 去掉首尾空白、转为大写后加上 "!"；每一步都用同一个名字 s 遮蔽上一步的值
+pub fn shout(s: &str) -> String {
+    todo!()
+}
@@ -0,0 +1,22 @@
+// 练习: 异步编程
+
+use std::cell::Cell;
+
+pub async fn double(x: i32) -> i32 {
+    todo!()
+}
+
+// 依次 await 每个数的 double，返回它们的和
+pub async fn sum_doubled(values: &[i32]) -> i32 {
+    todo!()
+}
+
+// 结果为 "你好, {name}"；返回的 Future 不能借用 name（注意返回类型中的 'static）
+pub fn greeting(name: &str) -> impl Future<Output = String> + 'static {
+    async { todo!() }
+}
+
+// 计数器加一
+pub async fn increment(counter: &Cell<u32>) {
+    todo!()
+}
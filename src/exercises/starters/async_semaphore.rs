@@ -0,0 +1,20 @@
+// 练习: 限流抓取 (Semaphore)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time;
+
+// 模拟抓取一个网址：耗时 100 毫秒，返回网址的长度
+pub async fn fetch(url: String) -> usize {
+    time::sleep(Duration::from_millis(100)).await;
+    url.len()
+}
+
+// 为每个网址启动一个任务，但用 Semaphore 保证最多只有 limit 个同时在抓取；
+// 结果按 urls 的顺序返回。limit 为 0 时按 1 处理
+pub async fn fetch_all(urls: &[&str], limit: usize) -> Vec<usize> {
+    todo!()
+}
@@ -0,0 +1,31 @@
+// 练习: 枚举和模式匹配
+
+#[derive(Debug)]
+pub enum IpAddr {
+    V4(u8, u8, u8, u8),
+    V6(String),
+}
+
+// IPv4 格式化为 "127.0.0.1"，IPv6 原样返回
+pub fn format_ip(ip: &IpAddr) -> String {
+    todo!()
+}
+
+#[derive(Debug)]
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(u8, u8, u8),
+}
+
+// Quit → "退出"，Move → "移动到 (10, 20)"，Write → "写入: 你好"，
+// ChangeColor → "颜色 RGB(255, 0, 0)"
+pub fn describe(msg: &Message) -> String {
+    todo!()
+}
+
+// None 保持 None，Some(i) 变成 Some(i + 1)
+pub fn plus_one(x: Option<i32>) -> Option<i32> {
+    todo!()
+}
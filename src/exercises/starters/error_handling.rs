@@ -0,0 +1,25 @@
+// 练习: 错误处理
+
+use std::num::ParseIntError;
+
+// 除数为 0 时返回 Err("除数不能为零")
+pub fn divide(numerator: f64, denominator: f64) -> Result<f64, String> {
+    todo!()
+}
+
+// 解析整数后乘以 2，允许首尾空白；用 ? 传播解析错误
+pub fn parse_and_double(s: &str) -> Result<i32, ParseIntError> {
+    todo!()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AppError {
+    DivisionByZero,
+    // 无法解析的原始输入
+    InvalidNumber(String),
+}
+
+// 解析两个整数并相除；解析失败返回 InvalidNumber(原始输入)，除数为 0 返回 DivisionByZero
+pub fn parse_and_divide(a: &str, b: &str) -> Result<i32, AppError> {
+    todo!()
+}
@@ -0,0 +1,29 @@
+// 练习: 生命周期
+// TODO: 这个文件编译不过，为下面的函数和结构体加上生命周期标注
+
+// 返回较长的字符串，一样长时返回 x
+pub fn longest(x: &str, y: &str) -> &str {
+    if x.len() >= y.len() { x } else { y }
+}
+
+// items 中第一个包含 keyword 的字符串；返回值在 keyword 被释放后也要能继续使用
+pub fn find_containing(items: &[&str], keyword: &str) -> Option<&str> {
+    items.iter().copied().find(|item| item.contains(keyword))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ImportantExcerpt {
+    pub part: &str,
+}
+
+impl ImportantExcerpt {
+    // 原文的第一句话（第一个 '。' 之前的部分）
+    pub fn first_sentence(text: &str) -> ImportantExcerpt {
+        let part = text.split('。').next().unwrap_or(text);
+        ImportantExcerpt { part }
+    }
+
+    pub fn part(&self) -> &str {
+        self.part
+    }
+}
@@ -0,0 +1,39 @@
+// 练习: 模块系统
+
+pub mod math {
+    pub mod basic {
+        pub fn add(a: i32, b: i32) -> i32 {
+            todo!()
+        }
+
+        pub fn multiply(a: i32, b: i32) -> i32 {
+            todo!()
+        }
+    }
+
+    // TODO: 让外部可以访问 advanced 模块
+    mod advanced {
+        pub fn factorial(n: u32) -> u64 {
+            todo!()
+        }
+
+        // fibonacci(0) == 0，fibonacci(1) == 1
+        pub fn fibonacci(n: u32) -> u64 {
+            todo!()
+        }
+    }
+}
+
+pub mod utils {
+    // TODO: 重新导出 string_utils 中的函数，让外部可以写 utils::reverse
+
+    mod string_utils {
+        pub fn reverse(s: &str) -> String {
+            todo!()
+        }
+
+        pub fn is_palindrome(s: &str) -> bool {
+            todo!()
+        }
+    }
+}
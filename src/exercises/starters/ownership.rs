@@ -0,0 +1,21 @@
+// 练习: 所有权基础
+
+// 返回 "give ownership"
+pub fn gives_ownership() -> String {
+    todo!()
+}
+
+// 在 s 后面追加 " (returned)" 再交还
+pub fn takes_and_gives_back(s: String) -> String {
+    todo!()
+}
+
+// 返回两个相互独立的 String：修改其中一个不影响另一个
+pub fn duplicate(s: String) -> (String, String) {
+    todo!()
+}
+
+// 交换两个 String；不要 clone
+pub fn swap(pair: (String, String)) -> (String, String) {
+    todo!()
+}
@@ -0,0 +1,18 @@
+// 练习: 引用和借用
+
+// 返回 s 的字符数（不是字节数）
+pub fn calculate_length(s: &str) -> usize {
+    todo!()
+}
+
+// 在调用者的 String 后面追加 ", world"
+pub fn change(s: &mut String) {
+    todo!()
+}
+
+// TODO: 这个函数编译不过（error[E0106]），修复它，让它返回 "dangle test"
+pub fn dangle() -> &String {
+    let mut s = String::from("dangle");
+    s.push_str(" test");
+    &s
+}
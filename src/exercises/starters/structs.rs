@@ -0,0 +1,36 @@
+// 练习: 结构体和方法
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub username: String,
+    pub email: String,
+    pub age: u32,
+    pub active: bool,
+}
+
+impl User {
+    // 新用户默认是激活状态
+    pub fn new(username: &str, email: &str, age: u32) -> User {
+        todo!()
+    }
+
+    // 18 岁及以上是成年人
+    pub fn is_adult(&self) -> bool {
+        todo!()
+    }
+
+    pub fn deactivate(&mut self) {
+        todo!()
+    }
+}
+
+// 元组结构体：RGB 颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    // 例如 Color(255, 0, 16) → "#ff0010"
+    pub fn to_hex(self) -> String {
+        todo!()
+    }
+}
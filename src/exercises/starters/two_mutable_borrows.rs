@@ -0,0 +1,12 @@
+// 编译错误谜题: 同时存在两个可变引用 (E0499)
+// TODO: 修复编译错误，让程序打印:
+//   [11, 2, 13]
+
+fn main() {
+    let mut scores = vec![1, 2, 3];
+    let first = &mut scores[0];
+    let last = scores.last_mut().unwrap();
+    *first += 10;
+    *last += 10;
+    println!("{:?}", scores);
+}
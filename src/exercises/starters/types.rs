@@ -0,0 +1,16 @@
+// 练习: 数据类型和转换
+
+// 解构元组，再按相反的顺序组合成新元组
+pub fn reverse_tuple(t: (i32, f64, bool)) -> (bool, f64, i32) {
+    todo!()
+}
+
+// 数组中所有元素的和
+pub fn array_sum(a: [i32; 5]) -> i32 {
+    todo!()
+}
+
+// 先用 as 把 x 转为 u8，再转为 char，例如 65.4321 → 'A'
+pub fn float_to_char(x: f32) -> char {
+    todo!()
+}
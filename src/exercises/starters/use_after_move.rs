@@ -0,0 +1,15 @@
+// 编译错误谜题: 使用已经移动的值 (E0382)
+// TODO: 修复编译错误，让程序打印:
+//   ferris 有 6 个字节
+//   ferris 的大写是 FERRIS
+
+fn byte_len(s: String) -> usize {
+    s.len()
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let len = byte_len(name);
+    println!("{} 有 {} 个字节", name, len);
+    println!("{} 的大写是 {}", name, name.to_uppercase());
+}
@@ -0,0 +1,445 @@
+// projects/web/books.rs - 图书馆 REST API
+// 把 07_structs.rs 中的 Library / Book 通过 HTTP + JSON 暴露出来：
+//   GET    /books         列出所有书籍（可用 ?author= 过滤）
+//   GET    /books.csv     以 CSV 格式流式导出所有书籍
+//   POST   /books         新增书籍
+//   GET    /books/:isbn   查询单本书
+//   PUT    /books/:isbn   替换书籍信息
+//   DELETE /books/:isbn   删除书籍
+// 以及用模板渲染的 HTML 页面：
+//   GET    /library       书籍列表（同样支持 ?author=）
+//   POST   /library       通过 HTML 表单新增书籍
+//   GET    /library/:isbn 书籍详情
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::form;
+use super::http::{Request, Response, StatusCode};
+use super::router::Router;
+use super::template::Templates;
+
+// ========== 数据模型 ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Book {
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+}
+
+#[derive(Debug)]
+pub struct Library {
+    name: String,
+    books: Vec<Book>,
+}
+
+impl Library {
+    pub fn new(name: String) -> Library {
+        Library {
+            name,
+            books: Vec::new(),
+        }
+    }
+
+    pub fn add_book(&mut self, book: Book) {
+        self.books.push(book);
+    }
+
+    pub fn find_by_isbn(&self, isbn: &str) -> Option<&Book> {
+        self.books.iter().find(|book| book.isbn == isbn)
+    }
+
+    fn find_by_isbn_mut(&mut self, isbn: &str) -> Option<&mut Book> {
+        self.books.iter_mut().find(|book| book.isbn == isbn)
+    }
+
+    // 删除书籍，返回被删除的书
+    fn remove_by_isbn(&mut self, isbn: &str) -> Option<Book> {
+        let index = self.books.iter().position(|book| book.isbn == isbn)?;
+        Some(self.books.remove(index))
+    }
+}
+
+// 多个连接共享同一个图书馆：读多写少，使用 RwLock
+// 某个处理函数 panic 导致锁中毒时，数据本身仍然可用，因此直接取出继续使用
+#[derive(Clone)]
+pub struct SharedLibrary(Arc<RwLock<Library>>);
+
+impl SharedLibrary {
+    pub fn new(library: Library) -> SharedLibrary {
+        SharedLibrary(Arc::new(RwLock::new(library)))
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Library> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Library> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ========== 输入校验 ==========
+
+// 请求体中的字段都是可选的，缺失的字段在校验阶段统一报告
+#[derive(Debug, Serialize, Deserialize)]
+struct BookInput {
+    title: Option<String>,
+    author: Option<String>,
+    isbn: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct FieldError {
+    field: &'static str,
+    message: String,
+}
+
+impl FieldError {
+    fn new(field: &'static str, message: &str) -> FieldError {
+        FieldError {
+            field,
+            message: message.to_string(),
+        }
+    }
+}
+
+// ISBN-10 / ISBN-13：允许连字符，ISBN-10 最后一位可以是 X
+fn is_valid_isbn(isbn: &str) -> bool {
+    if !isbn
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '-' || c == 'X')
+    {
+        return false;
+    }
+    let digits: Vec<char> = isbn.chars().filter(|c| *c != '-').collect();
+    match digits.len() {
+        10 => digits[..9].iter().all(|c| c.is_ascii_digit()),
+        13 => digits.iter().all(|c| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+// 校验并构造 Book
+// path_isbn 为 PUT 请求路径中的 ISBN：请求体可以省略 isbn，但给出时必须一致
+fn validate(input: BookInput, path_isbn: Option<&str>) -> Result<Book, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    let mut required = |field: &'static str, value: Option<String>| match value {
+        Some(v) if !v.trim().is_empty() => v.trim().to_string(),
+        _ => {
+            errors.push(FieldError::new(field, "不能为空"));
+            String::new()
+        }
+    };
+
+    let title = required("title", input.title);
+    let author = required("author", input.author);
+
+    let isbn = match (path_isbn, input.isbn) {
+        (Some(path), Some(body)) if body.trim() != path => {
+            errors.push(FieldError::new("isbn", "必须与路径中的 ISBN 一致"));
+            String::new()
+        }
+        (Some(path), _) => path.to_string(),
+        (None, isbn) => required("isbn", isbn),
+    };
+    if !isbn.is_empty() && !is_valid_isbn(&isbn) {
+        errors.push(FieldError::new("isbn", "必须是 10 位或 13 位的 ISBN"));
+    }
+
+    if errors.is_empty() {
+        Ok(Book {
+            title,
+            author,
+            isbn,
+        })
+    } else {
+        Err(errors)
+    }
+}
+
+// ========== 错误响应 ==========
+
+fn error_response(status: StatusCode, error: &str, message: &str) -> Response {
+    Response::json(status, &json!({ "error": error, "message": message }))
+}
+
+fn validation_error(details: Vec<FieldError>) -> Response {
+    Response::json(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        &json!({
+            "error": "validation_failed",
+            "message": "请求数据校验失败",
+            "details": details,
+        }),
+    )
+}
+
+fn not_found(isbn: &str) -> Response {
+    error_response(
+        StatusCode::NOT_FOUND,
+        "not_found",
+        &format!("未找到 ISBN 为 {} 的书籍", isbn),
+    )
+}
+
+// 解析 JSON 请求体：
+// - Content-Type 不是 JSON：415
+// - JSON 语法错误：400
+// - 语法正确但字段类型不对：422
+fn parse_input(request: &Request) -> Result<BookInput, Response> {
+    if let Some(content_type) = request.header("Content-Type")
+        && !content_type.starts_with("application/json")
+    {
+        return Err(error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_media_type",
+            "请求体必须是 application/json",
+        ));
+    }
+
+    request.json::<BookInput>().map_err(|e| {
+        if e.is_data() {
+            validation_error(vec![FieldError::new("body", &e.to_string())])
+        } else {
+            error_response(StatusCode::BAD_REQUEST, "invalid_json", &e.to_string())
+        }
+    })
+}
+
+// ========== 处理函数 ==========
+
+// 支持 ?author=xxx 按作者过滤
+fn filter_by_author<'a>(library: &'a Library, author: Option<&str>) -> Vec<&'a Book> {
+    library
+        .books
+        .iter()
+        .filter(|book| author.is_none_or(|a| book.author == a))
+        .collect()
+}
+
+fn list_books(library: &SharedLibrary, request: &Request) -> Response {
+    let library = library.read();
+    let books = filter_by_author(&library, request.query_param("author").as_deref());
+
+    Response::json(
+        StatusCode::OK,
+        &json!({
+            "library": library.name,
+            "count": books.len(),
+            "books": books,
+        }),
+    )
+}
+
+// 逐行写出 CSV，每一行作为一个 chunk 发送
+fn export_csv(library: &SharedLibrary) -> Response {
+    let books = library.read().books.clone();
+
+    Response::stream(StatusCode::OK, "text/csv; charset=utf-8", move |w| {
+        w.write_all(b"isbn,title,author\n")?;
+        for book in books {
+            let line = format!(
+                "{},{},{}\n",
+                csv_field(&book.isbn),
+                csv_field(&book.title),
+                csv_field(&book.author)
+            );
+            w.write_all(line.as_bytes())?;
+        }
+        Ok(())
+    })
+}
+
+// 含逗号、引号或换行的字段需要用双引号包裹，内部的双引号写成两个
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn create_book(library: &SharedLibrary, request: &Request) -> Response {
+    let input = match parse_input(request) {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+    let book = match validate(input, None) {
+        Ok(book) => book,
+        Err(details) => return validation_error(details),
+    };
+
+    let mut library = library.write();
+    if library.find_by_isbn(&book.isbn).is_some() {
+        return error_response(
+            StatusCode::CONFLICT,
+            "conflict",
+            &format!("ISBN 为 {} 的书籍已存在", book.isbn),
+        );
+    }
+
+    let response = Response::json(StatusCode::CREATED, &book)
+        .with_header("Location", &format!("/books/{}", book.isbn));
+    library.add_book(book);
+    response
+}
+
+fn get_book(library: &SharedLibrary, request: &Request) -> Response {
+    let isbn = request.param("isbn").unwrap_or_default();
+    match library.read().find_by_isbn(isbn) {
+        Some(book) => Response::json(StatusCode::OK, book),
+        None => not_found(isbn),
+    }
+}
+
+fn update_book(library: &SharedLibrary, request: &Request) -> Response {
+    let isbn = request.param("isbn").unwrap_or_default();
+    let input = match parse_input(request) {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+    let book = match validate(input, Some(isbn)) {
+        Ok(book) => book,
+        Err(details) => return validation_error(details),
+    };
+
+    match library.write().find_by_isbn_mut(isbn) {
+        Some(existing) => {
+            *existing = book;
+            Response::json(StatusCode::OK, existing)
+        }
+        None => not_found(isbn),
+    }
+}
+
+fn delete_book(library: &SharedLibrary, request: &Request) -> Response {
+    let isbn = request.param("isbn").unwrap_or_default();
+    match library.write().remove_by_isbn(isbn) {
+        Some(_) => Response::new(StatusCode::NO_CONTENT),
+        None => not_found(isbn),
+    }
+}
+
+// ========== HTML 页面 ==========
+
+// errors 和 input 用于表单提交失败时显示错误并保留用户填写的内容
+fn render_library(
+    library: &SharedLibrary,
+    templates: &Templates,
+    status: StatusCode,
+    author: Option<String>,
+    errors: &[FieldError],
+    input: serde_json::Value,
+) -> Response {
+    let library = library.read();
+    let books = filter_by_author(&library, author.as_deref());
+
+    templates.page(
+        status,
+        "library.html",
+        &json!({
+            "library": library.name,
+            "author": author,
+            "count": books.len(),
+            "books": books,
+            "errors": errors,
+            "input": input,
+        }),
+    )
+}
+
+fn library_page(library: &SharedLibrary, templates: &Templates, request: &Request) -> Response {
+    let author = request.query_param("author");
+    render_library(library, templates, StatusCode::OK, author, &[], json!({}))
+}
+
+// 表单提交成功后 303 跳转到详情页，刷新页面不会重复提交
+fn create_from_form(library: &SharedLibrary, templates: &Templates, request: &Request) -> Response {
+    let form = match form::urlencoded(request) {
+        Ok(form) => form,
+        Err(response) => return response,
+    };
+    let field = |name: &str| form.get(name).map(str::to_string);
+    let input = BookInput {
+        title: field("title"),
+        author: field("author"),
+        isbn: field("isbn"),
+    };
+    let submitted = json!(input);
+
+    let (status, errors) = match validate(input, None) {
+        Ok(book) => {
+            let mut lib = library.write();
+            if lib.find_by_isbn(&book.isbn).is_none() {
+                let location = format!("/library/{}", book.isbn);
+                lib.add_book(book);
+                return Response::new(StatusCode::SEE_OTHER).with_header("Location", &location);
+            }
+            (
+                StatusCode::CONFLICT,
+                vec![FieldError::new("isbn", "该 ISBN 的书籍已存在")],
+            )
+        }
+        Err(errors) => (StatusCode::UNPROCESSABLE_ENTITY, errors),
+    };
+    render_library(library, templates, status, None, &errors, submitted)
+}
+
+fn book_page(library: &SharedLibrary, templates: &Templates, request: &Request) -> Response {
+    let isbn = request.param("isbn").unwrap_or_default();
+    let library = library.read();
+    let book = library.find_by_isbn(isbn);
+    let status = if book.is_some() {
+        StatusCode::OK
+    } else {
+        StatusCode::NOT_FOUND
+    };
+
+    templates.page(
+        status,
+        "book.html",
+        &json!({
+            "library": library.name,
+            "isbn": isbn,
+            "book": book,
+        }),
+    )
+}
+
+// 把图书馆相关的路由注册到路由表
+pub fn register(router: &mut Router, library: SharedLibrary, templates: Arc<Templates>) {
+    let lib = library.clone();
+    router.get("/books", move |req| list_books(&lib, req));
+
+    let lib = library.clone();
+    router.get("/books.csv", move |_| export_csv(&lib));
+
+    let lib = library.clone();
+    router.post("/books", move |req| create_book(&lib, req));
+
+    let lib = library.clone();
+    router.get("/books/:isbn", move |req| get_book(&lib, req));
+
+    let lib = library.clone();
+    router.put("/books/:isbn", move |req| update_book(&lib, req));
+
+    let lib = library.clone();
+    router.delete("/books/:isbn", move |req| delete_book(&lib, req));
+
+    let lib = library.clone();
+    let tpl = Arc::clone(&templates);
+    router.get("/library", move |req| library_page(&lib, &tpl, req));
+
+    let lib = library.clone();
+    let tpl = Arc::clone(&templates);
+    router.post("/library", move |req| create_from_form(&lib, &tpl, req));
+
+    router.get("/library/:isbn", move |req| {
+        book_page(&library, &templates, req)
+    });
+}
@@ -0,0 +1,639 @@
+// projects/web/client.rs - 最小的 HTTP/1.1 客户端
+// 支持 GET/POST 等方法、自定义头部、JSON 请求体、超时、重定向，
+// 以及按 "主机:端口" 复用空闲的持久连接
+//
+// 这个文件只依赖标准库和 serde，不引用 web 项目的其他模块，
+// 其他程序可以直接用 #[path] 引入：
+//   #[path = "projects/web/client.rs"]
+//   mod http_client;
+//
+// 端到端测试的用法（见 e2e_tests.rs）：用 --port 0 启动 project_web，从输出中读出实际地址，再发请求
+//   let client = Client::new(ClientConfig::default());
+//   let res = client.get("http://127.0.0.1:54321/books").send()?;
+//   assert_eq!(res.status, 200);
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+// 响应头部总大小上限
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+// 响应体大小上限
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+// ========== 错误类型 ==========
+
+#[derive(Debug)]
+pub enum ClientError {
+    Io(io::Error),
+    Timeout,
+    InvalidUrl(String),
+    InvalidResponse(String),
+    TooManyRedirects(usize),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Io(e) => write!(f, "IO 错误: {}", e),
+            ClientError::Timeout => write!(f, "请求超时"),
+            ClientError::InvalidUrl(url) => write!(f, "无效的 URL: {}", url),
+            ClientError::InvalidResponse(msg) => write!(f, "无效的响应: {}", msg),
+            ClientError::TooManyRedirects(max) => write!(f, "重定向次数超过 {} 次", max),
+            ClientError::Json(e) => write!(f, "JSON 错误: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        // 设置了读写超时的套接字在超时时返回 WouldBlock（Unix）或 TimedOut（Windows）
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => ClientError::Timeout,
+            _ => ClientError::Io(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(e: serde_json::Error) -> Self {
+        ClientError::Json(e)
+    }
+}
+
+impl ClientError {
+    // 复用的连接可能已经被服务器关闭（例如空闲超时），
+    // 这时还没有收到任何响应，GET 和 HEAD 可以换一条新连接重试
+    fn is_stale_connection(&self) -> bool {
+        matches!(
+            self,
+            ClientError::Io(e) if matches!(
+                e.kind(),
+                io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+            )
+        )
+    }
+}
+
+type Result<T> = std::result::Result<T, ClientError>;
+
+// ========== URL ==========
+
+// 只支持 http://主机[:端口][/路径][?查询]
+#[derive(Debug, Clone)]
+struct Url {
+    host: String,
+    port: u16,
+    // 路径和查询字符串，例如 "/books?author=Rust"
+    target: String,
+}
+
+impl Url {
+    fn parse(url: &str) -> Result<Url> {
+        let invalid = || ClientError::InvalidUrl(url.to_string());
+
+        let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+        // 片段 (#...) 只在客户端使用，不发送给服务器
+        let rest = rest.split('#').next().unwrap_or("");
+        let (authority, target) = match rest.find(['/', '?']) {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let target = if target.starts_with('?') {
+            format!("/{}", target)
+        } else {
+            target.to_string()
+        };
+
+        // IPv6 地址写在方括号里：http://[::1]:8080/
+        let (host, port) = match authority.rfind(':') {
+            Some(i) if !authority[i..].contains(']') => {
+                let port = authority[i + 1..].parse().map_err(|_| invalid())?;
+                (&authority[..i], port)
+            }
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Url {
+            host: host.to_string(),
+            port,
+            target,
+        })
+    }
+
+    // 解析 Location 头：可能是绝对 URL、绝对路径或相对路径
+    fn join(&self, location: &str) -> Result<Url> {
+        if location.starts_with("http://") {
+            return Url::parse(location);
+        }
+        if location.contains("://") {
+            return Err(ClientError::InvalidUrl(location.to_string()));
+        }
+
+        let target = if location.starts_with('/') {
+            location.to_string()
+        } else {
+            let path = self.target.split('?').next().unwrap_or("/");
+            let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+            format!("{}{}", dir, location)
+        };
+        Ok(Url {
+            host: self.host.clone(),
+            port: self.port,
+            target,
+        })
+    }
+
+    // Host 头：默认端口可以省略
+    fn authority(&self) -> String {
+        if self.port == 80 {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    // 连接池的键
+    fn origin(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "http://{}{}", self.authority(), self.target)
+    }
+}
+
+// ========== 响应 ==========
+
+#[derive(Debug)]
+pub struct ClientResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    // 跟随重定向之后最终请求的 URL
+    pub url: String,
+}
+
+impl ClientResponse {
+    // 头部名称不区分大小写
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+// ========== 客户端 ==========
+
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    // 连接、发送和等待响应各自的超时时间
+    pub timeout: Duration,
+    // 最多跟随几次重定向；0 表示不跟随，直接返回 3xx 响应
+    pub max_redirects: usize,
+    // 每个主机最多保留几条空闲连接
+    pub max_idle_per_host: usize,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            timeout: Duration::from_secs(10),
+            max_redirects: 5,
+            max_idle_per_host: 4,
+        }
+    }
+}
+
+type Connection = BufReader<TcpStream>;
+
+// 客户端可以在多个线程之间共享（例如放进 Arc），空闲连接由互斥锁保护
+pub struct Client {
+    config: ClientConfig,
+    idle: Mutex<HashMap<String, Vec<Connection>>>,
+}
+
+impl Client {
+    pub fn new(config: ClientConfig) -> Client {
+        Client {
+            config,
+            idle: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn request(&self, method: &str, url: &str) -> RequestBuilder<'_> {
+        RequestBuilder {
+            client: self,
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+            timeout: self.config.timeout,
+            error: None,
+        }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder<'_> {
+        self.request("GET", url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder<'_> {
+        self.request("POST", url)
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder<'_> {
+        self.request("PUT", url)
+    }
+
+    pub fn delete(&self, url: &str) -> RequestBuilder<'_> {
+        self.request("DELETE", url)
+    }
+
+    // 当前保留的空闲连接总数
+    pub fn idle_connections(&self) -> usize {
+        self.lock_idle().values().map(Vec::len).sum()
+    }
+
+    fn lock_idle(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<Connection>>> {
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn checkout(&self, origin: &str) -> Option<Connection> {
+        self.lock_idle().get_mut(origin)?.pop()
+    }
+
+    fn checkin(&self, origin: String, connection: Connection) {
+        let mut idle = self.lock_idle();
+        let connections = idle.entry(origin).or_default();
+        if connections.len() < self.config.max_idle_per_host {
+            connections.push(connection);
+        }
+    }
+
+    fn connect(&self, url: &Url, timeout: Duration) -> Result<Connection> {
+        let mut last_error = None;
+        for addr in (url.host.as_str(), url.port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => {
+                    stream.set_nodelay(true)?;
+                    return Ok(BufReader::new(stream));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error
+            .map(ClientError::from)
+            .unwrap_or_else(|| ClientError::InvalidUrl(url.to_string())))
+    }
+
+    // 发送请求并跟随重定向
+    fn execute(&self, mut request: RequestBuilder<'_>) -> Result<ClientResponse> {
+        if let Some(error) = request.error.take() {
+            return Err(error);
+        }
+
+        let mut url = Url::parse(&request.url)?;
+        let mut redirects = 0;
+        loop {
+            let response = self.send_once(&request, &url)?;
+
+            let location = match response.status {
+                301 | 302 | 303 | 307 | 308 => response.header("Location"),
+                _ => None,
+            };
+            let Some(location) = location else {
+                return Ok(response);
+            };
+            if self.config.max_redirects == 0 {
+                return Ok(response);
+            }
+            if redirects == self.config.max_redirects {
+                return Err(ClientError::TooManyRedirects(redirects));
+            }
+            redirects += 1;
+
+            let next = url.join(location)?;
+            // 303，以及 POST 收到的 301/302，按浏览器的惯例改为不带请求体的 GET；
+            // 307/308 要求保持方法和请求体不变
+            let to_get = response.status == 303
+                || (matches!(response.status, 301 | 302) && request.method == "POST");
+            if to_get {
+                request.method = "GET".to_string();
+                request.body.clear();
+                request.remove_header("Content-Type");
+            }
+            // 跳到其他主机时不要把凭据带过去
+            if next.origin() != url.origin() {
+                request.remove_header("Authorization");
+                request.remove_header("Cookie");
+            }
+            url = next;
+        }
+    }
+
+    // 发送一次请求：优先复用空闲连接。复用的连接已失效时，只有 GET 和 HEAD 换一条新连接重试：
+    // 其他方法的请求可能已经到达服务器并被处理，重发会让 POST 之类的操作执行两次
+    fn send_once(&self, request: &RequestBuilder<'_>, url: &Url) -> Result<ClientResponse> {
+        let origin = url.origin();
+        if let Some(mut connection) = self.checkout(&origin) {
+            match self.exchange(&mut connection, request, url) {
+                Err(e) if e.is_stale_connection() && request.is_idempotent() => {}
+                Ok((response, keep_alive)) => {
+                    if keep_alive {
+                        self.checkin(origin, connection);
+                    }
+                    return Ok(response);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut connection = self.connect(url, request.timeout)?;
+        let (response, keep_alive) = self.exchange(&mut connection, request, url)?;
+        if keep_alive {
+            self.checkin(origin, connection);
+        }
+        Ok(response)
+    }
+
+    // 在一条连接上写出请求、读取完整的响应；返回响应以及连接能否继续使用
+    fn exchange(
+        &self,
+        connection: &mut Connection,
+        request: &RequestBuilder<'_>,
+        url: &Url,
+    ) -> Result<(ClientResponse, bool)> {
+        let stream = connection.get_ref();
+        stream.set_read_timeout(Some(request.timeout))?;
+        stream.set_write_timeout(Some(request.timeout))?;
+
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\n",
+            request.method,
+            url.target,
+            url.authority()
+        );
+        // 调用方没有指定 User-Agent 时使用默认值（反向代理会转发浏览器的 User-Agent）
+        if !request.has_header("User-Agent") {
+            head.push_str("User-Agent: rust-learing-client\r\n");
+        }
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if !request.body.is_empty() || matches!(request.method.as_str(), "POST" | "PUT" | "PATCH") {
+            head.push_str(&format!("Content-Length: {}\r\n", request.body.len()));
+        }
+        head.push_str("\r\n");
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&request.body);
+        connection.get_mut().write_all(&bytes)?;
+        connection.get_mut().flush()?;
+
+        read_response(connection, &request.method, url)
+    }
+}
+
+// ========== 请求构建 ==========
+
+pub struct RequestBuilder<'a> {
+    client: &'a Client,
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    timeout: Duration,
+    // 构建过程中的错误（例如 JSON 序列化失败）推迟到 send 时返回
+    error: Option<ClientError>,
+}
+
+impl RequestBuilder<'_> {
+    // 设置头部，同名的旧值会被替换
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.remove_header(name);
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(self, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        let mut builder = self.header("Content-Type", content_type);
+        builder.body = body.into();
+        builder
+    }
+
+    pub fn json<T: Serialize>(self, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => self.body("application/json", body),
+            Err(e) => RequestBuilder {
+                error: Some(e.into()),
+                ..self
+            },
+        }
+    }
+
+    // 只对这一个请求生效的超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn send(self) -> Result<ClientResponse> {
+        self.client.execute(self)
+    }
+
+    // 失效连接上可以安全重发的请求
+    fn is_idempotent(&self) -> bool {
+        matches!(self.method.as_str(), "GET" | "HEAD")
+    }
+
+    fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(n, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    fn remove_header(&mut self, name: &str) {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+}
+
+// ========== 读取响应 ==========
+
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String, total: &mut usize) -> Result<usize> {
+    line.clear();
+    let n = reader
+        .by_ref()
+        .take((MAX_HEADER_BYTES - *total) as u64 + 1)
+        .read_line(line)?;
+    *total += n;
+    if *total > MAX_HEADER_BYTES {
+        return Err(ClientError::InvalidResponse("响应头部过大".to_string()));
+    }
+    Ok(n)
+}
+
+fn read_response<R: BufRead>(
+    reader: &mut R,
+    method: &str,
+    url: &Url,
+) -> Result<(ClientResponse, bool)> {
+    let mut total = 0;
+    let mut line = String::new();
+
+    // 跳过 100 Continue 之类的临时响应
+    let (version, status, headers) = loop {
+        if read_line(reader, &mut line, &mut total)? == 0 {
+            // 一个字节都没收到连接就关闭了：多半是服务器关闭了空闲连接
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        let mut parts = line.trim_end().splitn(3, ' ');
+        let version = parts.next().unwrap_or("").to_string();
+        let status: u16 = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .filter(|_| version.starts_with("HTTP/1."))
+            .ok_or_else(|| ClientError::InvalidResponse(format!("状态行: {}", line.trim_end())))?;
+
+        let mut headers = Vec::new();
+        loop {
+            if read_line(reader, &mut line, &mut total)? == 0 {
+                return Err(ClientError::InvalidResponse("头部未结束".to_string()));
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header
+                .split_once(':')
+                .ok_or_else(|| ClientError::InvalidResponse(format!("头部: {}", header)))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        if (100..200).contains(&status) && status != 101 {
+            continue;
+        }
+        break (version, status, headers);
+    };
+
+    let mut response = ClientResponse {
+        status,
+        headers,
+        body: Vec::new(),
+        url: url.to_string(),
+    };
+
+    let connection = response.header("Connection").unwrap_or("");
+    let has = |token: &str| {
+        connection
+            .split(',')
+            .any(|t| t.trim().eq_ignore_ascii_case(token))
+    };
+    let mut keep_alive = if version == "HTTP/1.0" {
+        has("keep-alive")
+    } else {
+        !has("close")
+    };
+
+    // HEAD、1xx、204 和 304 响应没有响应体
+    let no_body = method == "HEAD" || status < 200 || status == 204 || status == 304;
+    let chunked = response
+        .header("Transfer-Encoding")
+        .is_some_and(|te| te.to_ascii_lowercase().contains("chunked"));
+
+    if no_body {
+        // 101 之后连接交给了其他协议，不能再发 HTTP 请求
+        keep_alive &= status != 101;
+    } else if chunked {
+        response.body = read_chunked_body(reader)?;
+    } else if let Some(length) = response.header("Content-Length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| ClientError::InvalidResponse(format!("Content-Length: {}", length)))?;
+        if length > MAX_BODY_BYTES {
+            return Err(ClientError::InvalidResponse("响应体过大".to_string()));
+        }
+        response.body = vec![0; length];
+        reader.read_exact(&mut response.body)?;
+    } else {
+        // 既没有长度也不是 chunked：一直读到服务器关闭连接
+        reader
+            .take(MAX_BODY_BYTES as u64 + 1)
+            .read_to_end(&mut response.body)?;
+        if response.body.len() > MAX_BODY_BYTES {
+            return Err(ClientError::InvalidResponse("响应体过大".to_string()));
+        }
+        keep_alive = false;
+    }
+
+    Ok((response, keep_alive))
+}
+
+// 读取 chunked 编码的响应体，格式与请求体相同
+fn read_chunked_body<R: BufRead>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(ClientError::InvalidResponse(
+                "chunked 响应体未结束".to_string(),
+            ));
+        }
+        let size = line.trim_end().split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| ClientError::InvalidResponse(format!("无效的 chunk 长度: {}", size)))?;
+
+        if size == 0 {
+            loop {
+                let mut trailer = String::new();
+                if reader.read_line(&mut trailer)? == 0 || trailer.trim_end().is_empty() {
+                    return Ok(body);
+                }
+            }
+        }
+
+        // 恶意的 chunk 长度（例如 ffffffffffffffff）会让加法溢出
+        let end = body
+            .len()
+            .checked_add(size)
+            .filter(|end| *end <= MAX_BODY_BYTES)
+            .ok_or_else(|| ClientError::InvalidResponse("响应体过大".to_string()))?;
+        let start = body.len();
+        body.resize(end, 0);
+        reader.read_exact(&mut body[start..])?;
+
+        let mut crlf = [0; 2];
+        reader.read_exact(&mut crlf)?;
+        if &crlf != b"\r\n" {
+            return Err(ClientError::InvalidResponse(
+                "chunk 数据后缺少 CRLF".to_string(),
+            ));
+        }
+    }
+}
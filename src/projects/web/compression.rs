@@ -0,0 +1,266 @@
+// projects/web/compression.rs - 响应压缩
+// 根据请求的 Accept-Encoding 选择 gzip 或 deflate 压缩文本类响应，
+// 已经压缩过的格式（图片、压缩包等）和很小的响应不再压缩
+
+use std::io::{self, Write};
+
+use flate2::Compression as Level;
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+use super::http::{Body, Request, Response, StatusCode};
+use super::middleware::{Middleware, Next};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Gzip,
+    // HTTP 中的 "deflate" 实际上是 zlib 格式 (RFC 1950)
+    Deflate,
+}
+
+impl Encoding {
+    fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    fn compress(self, data: &[u8], level: Level) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Encoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+// 解析 Accept-Encoding，返回客户端可接受的最佳编码
+//   Accept-Encoding: gzip;q=0.8, deflate, br;q=1.0, *;q=0
+// q 值相同时优先 gzip；q=0 表示明确拒绝
+fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let mut gzip = None;
+    let mut deflate = None;
+    let mut wildcard = None;
+
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        let q = parts
+            .find_map(|p| p.trim().strip_prefix("q=").map(str::to_string))
+            .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok());
+        let Some(q) = q else {
+            continue; // 无法解析的 q 值，忽略这一项
+        };
+
+        match coding.as_str() {
+            "gzip" | "x-gzip" => gzip = Some(q),
+            "deflate" => deflate = Some(q),
+            "*" => wildcard = Some(q),
+            _ => {}
+        }
+    }
+
+    let gzip = gzip.or(wildcard).unwrap_or(0.0);
+    let deflate = deflate.or(wildcard).unwrap_or(0.0);
+
+    if gzip <= 0.0 && deflate <= 0.0 {
+        None
+    } else if gzip >= deflate {
+        Some(Encoding::Gzip)
+    } else {
+        Some(Encoding::Deflate)
+    }
+}
+
+// 文本类内容压缩效果好；图片、视频、压缩包本身已经压缩过，再压缩只会浪费 CPU
+fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json"
+                | "application/javascript"
+                | "application/xml"
+                | "image/svg+xml"
+                | "application/wasm"
+        )
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionConfig {
+    // 小于这个大小的响应不压缩：压缩头本身就有开销
+    pub min_size: usize,
+    // 压缩级别 0-9
+    pub level: u32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            min_size: 1024,
+            level: 6,
+        }
+    }
+}
+
+pub struct Compression {
+    config: CompressionConfig,
+}
+
+impl Compression {
+    pub fn new(config: CompressionConfig) -> Compression {
+        Compression { config }
+    }
+}
+
+impl Middleware for Compression {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let encoding = request.header("Accept-Encoding").and_then(negotiate);
+        let mut response = next.run(request);
+
+        let compressible = response.status != StatusCode::NO_CONTENT
+            && response.header("Content-Encoding").is_none()
+            && response.header("Content-Type").is_some_and(is_compressible);
+        if !compressible {
+            return response;
+        }
+
+        // 同一个 URL 的响应内容取决于 Accept-Encoding，缓存必须区分
+        response.add_vary("Accept-Encoding");
+        let Some(encoding) = encoding else {
+            return response;
+        };
+        let level = Level::new(self.config.level);
+
+        match std::mem::replace(&mut response.body, Body::Bytes(Vec::new())) {
+            Body::Bytes(body) if body.len() >= self.config.min_size => {
+                match encoding.compress(&body, level) {
+                    // 压缩后反而更大时保留原始内容
+                    Ok(compressed) if compressed.len() < body.len() => {
+                        response.body = Body::Bytes(compressed);
+                        response.set_header("Content-Encoding", encoding.as_str());
+                    }
+                    _ => response.body = Body::Bytes(body),
+                }
+            }
+            // 流式响应事先不知道大小，一律边生成边压缩
+            Body::Stream(f) => {
+                response.body = Body::Stream(Box::new(move |w| match encoding {
+                    Encoding::Gzip => {
+                        let mut encoder = GzEncoder::new(w, level);
+                        f(&mut encoder)?;
+                        encoder.finish().map(drop)
+                    }
+                    Encoding::Deflate => {
+                        let mut encoder = ZlibEncoder::new(w, level);
+                        f(&mut encoder)?;
+                        encoder.finish().map(drop)
+                    }
+                }));
+                response.set_header("Content-Encoding", encoding.as_str());
+            }
+            body => response.body = body,
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::super::middleware::Pipeline;
+    use super::super::router::Router;
+    use super::*;
+
+    #[test]
+    fn negotiates_by_q_value() {
+        assert_eq!(negotiate("gzip, deflate"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("deflate"), Some(Encoding::Deflate));
+        assert_eq!(
+            negotiate("gzip;q=0.5, deflate;q=0.8"),
+            Some(Encoding::Deflate)
+        );
+        assert_eq!(negotiate("GZIP ; q=1.0"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("x-gzip"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("br, identity"), None);
+        assert_eq!(negotiate(""), None);
+    }
+
+    #[test]
+    fn q_zero_and_wildcard() {
+        assert_eq!(negotiate("gzip;q=0, deflate"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("gzip;q=0, deflate;q=0"), None);
+        assert_eq!(negotiate("*"), Some(Encoding::Gzip));
+        // 明确列出的编码优先于通配符
+        assert_eq!(negotiate("gzip;q=0, *;q=0.5"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("*;q=0"), None);
+        // 无法解析的 q 值忽略这一项
+        assert_eq!(
+            negotiate("gzip;q=abc, deflate;q=0.1"),
+            Some(Encoding::Deflate)
+        );
+    }
+
+    #[test]
+    fn compresses_only_text_types() {
+        assert!(is_compressible("text/html; charset=utf-8"));
+        assert!(is_compressible("application/json"));
+        assert!(is_compressible("application/problem+json"));
+        assert!(!is_compressible("image/png"));
+        assert!(!is_compressible("application/zip"));
+    }
+
+    fn get(accept_encoding: Option<&str>, body: &str) -> Response {
+        let body = body.to_string();
+        let mut router = Router::new();
+        router.get("/", move |_| Response::text(StatusCode::OK, &body));
+        let pipeline = Pipeline::new(router).wrap(Compression::new(CompressionConfig::default()));
+
+        let mut raw = "GET / HTTP/1.1\r\n".to_string();
+        if let Some(value) = accept_encoding {
+            raw.push_str(&format!("Accept-Encoding: {}\r\n", value));
+        }
+        raw.push_str("\r\n");
+        let mut request = Request::read_from(&mut raw.as_bytes()).unwrap().unwrap();
+        pipeline.handle(&mut request)
+    }
+
+    #[test]
+    fn middleware_compresses_large_responses() {
+        let text = "Rust ".repeat(1000);
+        let response = get(Some("gzip"), &text);
+        assert_eq!(response.header("Content-Encoding"), Some("gzip"));
+        assert_eq!(response.header("Vary"), Some("Accept-Encoding"));
+        let Body::Bytes(compressed) = response.body else {
+            panic!("应当是定长响应体");
+        };
+        let mut decoded = String::new();
+        GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, text);
+
+        // 太小的响应和不接受压缩的客户端都拿到原始内容
+        assert_eq!(get(Some("gzip"), "short").header("Content-Encoding"), None);
+        let response = get(None, &text);
+        assert_eq!(response.header("Content-Encoding"), None);
+        assert_eq!(response.header("Vary"), Some("Accept-Encoding"));
+    }
+}
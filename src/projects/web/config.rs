@@ -0,0 +1,312 @@
+// projects/web/config.rs - 服务器配置
+// 优先级：命令行参数 > 环境变量 > 默认值
+//
+//   命令行参数           环境变量           默认值
+//   --host <地址>        WEB_HOST          127.0.0.1
+//   --port <端口>        WEB_PORT          7878（0 表示由系统分配）
+//   --workers <数量>     WEB_WORKERS       4
+//   --static-root <目录> WEB_STATIC_ROOT   不提供静态文件
+//   --log-level <级别>   WEB_LOG_LEVEL     info
+//   --templates <目录>   WEB_TEMPLATES     src/projects/web/templates
+//   --upload-dir <目录>  WEB_UPLOAD_DIR    uploads
+//   --gradebook <目录>   WEB_GRADEBOOK     不显示成绩册（每个子目录是一个学习者的练习数据目录）
+//   --dev                WEB_DEV           关闭（开启后模板修改自动生效）
+//   --session-secret <密钥> WEB_SESSION_SECRET 每次启动随机生成（重启后会话失效）
+//   --secure-cookies     WEB_SECURE_COOKIES 关闭（放在 HTTPS 反向代理后面时开启）
+//   --same-site <值>     WEB_SAME_SITE     lax
+//   --vhost <主机=上游>  WEB_VHOSTS        无（可重复；环境变量中多个用 ';' 分隔）
+//   --proxy-timeout <秒> WEB_PROXY_TIMEOUT 30
+//   --shutdown-timeout <秒> WEB_SHUTDOWN_TIMEOUT 10（关闭时等待进行中请求的最长时间）
+//   --rate-limit-rps <次数> WEB_RATE_LIMIT_RPS 10（每个客户端 IP 每秒的平均请求数，可以是小数）
+//   --rate-limit-burst <次数> WEB_RATE_LIMIT_BURST 20（每个客户端 IP 允许的突发请求数）
+//
+// 虚拟主机示例：--vhost docs.localhost=127.0.0.1:8001,127.0.0.1:8002
+
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::cookie::SameSite;
+use super::logging::LogLevel;
+use super::rate_limit::RateLimitConfig;
+
+// 结果类型别名，与 project_cli 一致
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// 太短的密钥可以被暴力猜出
+const MIN_SECRET_LEN: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub workers: usize,
+    pub static_root: Option<PathBuf>,
+    pub log_level: LogLevel,
+    pub templates: PathBuf,
+    pub upload_dir: PathBuf,
+    // 成绩册目录，见 gradebook.rs
+    pub gradebook: Option<PathBuf>,
+    pub dev: bool,
+    // 会话 Cookie 的签名密钥；建议用环境变量提供，避免出现在进程列表中
+    pub session_secret: Option<String>,
+    pub secure_cookies: bool,
+    pub same_site: SameSite,
+    // 按 Host 头转发到上游服务器的虚拟主机
+    pub vhosts: Vec<VirtualHost>,
+    pub proxy_timeout: Duration,
+    pub shutdown_timeout: Duration,
+    // 按客户端 IP 限流的令牌桶参数
+    pub rate_limit_rps: f64,
+    pub rate_limit_burst: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct VirtualHost {
+    // 主机名，可以用 "*.example.com" 匹配所有子域名
+    pub host: String,
+    // 上游服务器地址 "主机:端口"，按轮询分配请求
+    pub upstreams: Vec<String>,
+}
+
+impl FromStr for VirtualHost {
+    type Err = String;
+
+    // "docs.localhost=127.0.0.1:8001,127.0.0.1:8002"
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("虚拟主机格式应为 主机=上游[,上游...]: {}", s);
+        let (host, upstreams) = s.split_once('=').ok_or_else(invalid)?;
+        let host = host.trim();
+        let upstreams: Vec<String> = upstreams
+            .split(',')
+            .map(|upstream| {
+                let upstream = upstream.trim();
+                upstream.strip_prefix("http://").unwrap_or(upstream)
+            })
+            .filter(|upstream| !upstream.is_empty())
+            .map(str::to_string)
+            .collect();
+        if host.is_empty() || upstreams.is_empty() {
+            return Err(invalid());
+        }
+        // 上游必须带端口，例如 127.0.0.1:8001
+        for upstream in &upstreams {
+            let port = upstream
+                .rsplit_once(':')
+                .map(|(_, port)| port.parse::<u16>());
+            if !matches!(port, Some(Ok(_))) {
+                return Err(format!("上游地址应为 主机:端口: {}", upstream));
+            }
+        }
+        Ok(VirtualHost {
+            host: host.to_string(),
+            upstreams,
+        })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            host: "127.0.0.1".to_string(),
+            port: 7878,
+            workers: 4,
+            static_root: None,
+            log_level: LogLevel::Info,
+            templates: PathBuf::from("src/projects/web/templates"),
+            upload_dir: PathBuf::from("uploads"),
+            gradebook: None,
+            dev: false,
+            session_secret: None,
+            secure_cookies: false,
+            same_site: SameSite::Lax,
+            vhosts: Vec::new(),
+            proxy_timeout: Duration::from_secs(30),
+            shutdown_timeout: Duration::from_secs(10),
+            rate_limit_rps: RateLimitConfig::default().requests_per_second,
+            rate_limit_burst: RateLimitConfig::default().burst,
+        }
+    }
+}
+
+// 解析结果：正常启动，或者只显示帮助
+pub enum ParsedArgs {
+    Run(Box<Config>),
+    Help,
+}
+
+impl Config {
+    // 读取环境变量和命令行参数（args 不包含程序名）
+    pub fn load(args: &[String]) -> Result<ParsedArgs> {
+        let mut config = Config::default();
+        config.apply_env()?;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            // 同时支持 "--port 8080" 和 "--port=8080"
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if flag == "--help" || flag == "-h" {
+                return Ok(ParsedArgs::Help);
+            }
+            // 开关参数可以不带值
+            if inline.is_none() && matches!(flag, "--dev" | "--secure-cookies") {
+                config.set(flag, "true")?;
+                continue;
+            }
+
+            let value = match inline {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| format!("{} 需要一个参数值", flag))?,
+            };
+            config.set(flag, &value)?;
+        }
+
+        if config.workers == 0 {
+            return Err("工作线程数量必须大于 0".into());
+        }
+        if config.proxy_timeout.is_zero() {
+            return Err("上游超时时间必须大于 0".into());
+        }
+        // NaN 和无穷大也要拒绝，它们会让令牌桶的计算失去意义
+        if !(config.rate_limit_rps.is_finite() && config.rate_limit_rps > 0.0) {
+            return Err("限流速率必须是大于 0 的数".into());
+        }
+        if config.rate_limit_burst == 0 {
+            return Err("限流突发容量必须大于 0".into());
+        }
+        Ok(ParsedArgs::Run(Box::new(config)))
+    }
+
+    fn apply_env(&mut self) -> Result<()> {
+        const VARS: [(&str, &str); 17] = [
+            ("WEB_HOST", "--host"),
+            ("WEB_PORT", "--port"),
+            ("WEB_WORKERS", "--workers"),
+            ("WEB_STATIC_ROOT", "--static-root"),
+            ("WEB_LOG_LEVEL", "--log-level"),
+            ("WEB_TEMPLATES", "--templates"),
+            ("WEB_UPLOAD_DIR", "--upload-dir"),
+            ("WEB_GRADEBOOK", "--gradebook"),
+            ("WEB_DEV", "--dev"),
+            ("WEB_SESSION_SECRET", "--session-secret"),
+            ("WEB_SECURE_COOKIES", "--secure-cookies"),
+            ("WEB_SAME_SITE", "--same-site"),
+            ("WEB_VHOSTS", "--vhost"),
+            ("WEB_PROXY_TIMEOUT", "--proxy-timeout"),
+            ("WEB_SHUTDOWN_TIMEOUT", "--shutdown-timeout"),
+            ("WEB_RATE_LIMIT_RPS", "--rate-limit-rps"),
+            ("WEB_RATE_LIMIT_BURST", "--rate-limit-burst"),
+        ];
+        for (var, flag) in VARS {
+            if let Ok(value) = env::var(var) {
+                self.set(flag, &value)
+                    .map_err(|e| format!("环境变量 {}: {}", var, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, flag: &str, value: &str) -> Result<()> {
+        match flag {
+            "--host" => self.host = value.to_string(),
+            "--port" | "-p" => self.port = parse(flag, value)?,
+            "--workers" | "-w" => self.workers = parse(flag, value)?,
+            "--static-root" => self.static_root = Some(PathBuf::from(value)),
+            "--log-level" => self.log_level = value.parse()?,
+            "--templates" => self.templates = PathBuf::from(value),
+            "--upload-dir" => self.upload_dir = PathBuf::from(value),
+            "--gradebook" => self.gradebook = Some(PathBuf::from(value)),
+            "--dev" => self.dev = parse_bool(flag, value)?,
+            "--secure-cookies" => self.secure_cookies = parse_bool(flag, value)?,
+            "--same-site" => self.same_site = value.parse()?,
+            "--vhost" => {
+                for vhost in value.split(';').filter(|v| !v.trim().is_empty()) {
+                    self.vhosts.push(vhost.parse()?);
+                }
+            }
+            "--proxy-timeout" => {
+                self.proxy_timeout = Duration::from_secs(parse(flag, value)?);
+            }
+            "--shutdown-timeout" => {
+                self.shutdown_timeout = Duration::from_secs(parse(flag, value)?);
+            }
+            "--rate-limit-rps" => self.rate_limit_rps = parse(flag, value)?,
+            "--rate-limit-burst" => self.rate_limit_burst = parse(flag, value)?,
+            "--session-secret" => {
+                if value.len() < MIN_SECRET_LEN {
+                    return Err(format!("会话密钥至少需要 {} 个字符", MIN_SECRET_LEN).into());
+                }
+                self.session_secret = Some(value.to_string());
+            }
+            _ => return Err(format!("未知参数: {}", flag).into()),
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: FromStr>(flag: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| format!("{} 的值无效: {}", flag, value).into())
+}
+
+fn parse_bool(flag: &str, value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("{} 的值无效: {}", flag, value).into()),
+    }
+}
+
+pub fn show_help() {
+    println!("📖 使用方法:");
+    println!("  cargo run --bin project_web -- [选项...]");
+    println!();
+    println!("🔧 可用选项（括号内为对应的环境变量）:");
+    println!("  --host <地址>          监听地址，默认 127.0.0.1 (WEB_HOST)");
+    println!("  -p, --port <端口>      监听端口，默认 7878，0 表示随机端口 (WEB_PORT)");
+    println!("  -w, --workers <数量>   工作线程数量，默认 4 (WEB_WORKERS)");
+    println!("  --static-root <目录>   在 /static/ 下提供该目录中的文件 (WEB_STATIC_ROOT)");
+    println!("  --log-level <级别>     error、warn、info、debug，默认 info (WEB_LOG_LEVEL)");
+    println!(
+        "  --templates <目录>     HTML 模板目录，默认 src/projects/web/templates (WEB_TEMPLATES)"
+    );
+    println!("  --upload-dir <目录>    练习提交的保存目录，默认 uploads (WEB_UPLOAD_DIR)");
+    println!("  --gradebook <目录>     在 /gradebook 显示该目录中学习者的练习进度 (WEB_GRADEBOOK)");
+    println!("  --dev                  开发模式：模板修改后自动重新加载 (WEB_DEV)");
+    println!(
+        "  --session-secret <密钥> 会话 Cookie 签名密钥，至少 {} 个字符 (WEB_SESSION_SECRET)",
+        MIN_SECRET_LEN
+    );
+    println!("  --secure-cookies       Cookie 只通过 HTTPS 发送 (WEB_SECURE_COOKIES)");
+    println!(
+        "  --same-site <值>       会话 Cookie 的 SameSite：strict、lax、none，默认 lax (WEB_SAME_SITE)"
+    );
+    println!("  --vhost <主机=上游,...> 按 Host 头反向代理到上游服务器，可重复 (WEB_VHOSTS)");
+    println!("  --proxy-timeout <秒>   等待上游服务器的超时时间，默认 30 (WEB_PROXY_TIMEOUT)");
+    println!(
+        "  --shutdown-timeout <秒> 关闭时等待进行中请求的最长时间，默认 10 (WEB_SHUTDOWN_TIMEOUT)"
+    );
+    println!(
+        "  --rate-limit-rps <次数> 每个客户端 IP 每秒的平均请求数，默认 10 (WEB_RATE_LIMIT_RPS)"
+    );
+    println!(
+        "  --rate-limit-burst <次数> 每个客户端 IP 允许的突发请求数，默认 20 (WEB_RATE_LIMIT_BURST)"
+    );
+    println!("  -h, --help             显示此帮助信息");
+    println!();
+    println!("💡 示例:");
+    println!("  cargo run --bin project_web -- --port 0");
+    println!("  WEB_LOG_LEVEL=debug cargo run --bin project_web -- --static-root docs");
+    println!("  cargo run --bin project_web -- --dev");
+    println!(
+        "  cargo run --bin project_web -- --vhost docs.localhost=127.0.0.1:8001,127.0.0.1:8002"
+    );
+}
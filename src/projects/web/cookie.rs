@@ -0,0 +1,163 @@
+// projects/web/cookie.rs - Cookie 解析与 Set-Cookie 生成
+// 浏览器把所有 Cookie 放在一个请求头里发回：
+//   Cookie: sid=abc; theme=dark
+// 服务器每设置一个 Cookie 就发送一个 Set-Cookie 响应头（不能合并成一行）：
+//   Set-Cookie: sid=abc; Path=/; Max-Age=1800; HttpOnly; SameSite=Lax
+//
+//   HttpOnly   JavaScript 读不到，XSS 偷不走
+//   Secure     只通过 HTTPS 发送
+//   SameSite   跨站请求是否携带：Strict 从不，Lax 只有顶层导航，None 总是（必须同时 Secure）
+//   Max-Age    多少秒后过期；0 表示立即删除，不设置则浏览器关闭时删除
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::http::{Request, Response};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl FromStr for SameSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Ok(SameSite::Strict),
+            "lax" => Ok(SameSite::Lax),
+            "none" => Ok(SameSite::None),
+            _ => Err(format!(
+                "未知的 SameSite 值: {}（可选 strict、lax、none）",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SameSite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub path: Option<String>,
+    pub max_age: Option<Duration>,
+    pub http_only: bool,
+    pub secure: bool,
+    pub same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    // 名字和值由调用方保证只包含安全字符（不含 ';'、','、空白和引号）
+    pub fn new(name: &str, value: &str) -> Cookie {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            path: None,
+            max_age: None,
+            http_only: false,
+            secure: false,
+            same_site: None,
+        }
+    }
+
+    // 删除 Cookie：发送同名、同路径、Max-Age=0 的 Cookie
+    pub fn removal(name: &str, path: &str) -> Cookie {
+        Cookie::new(name, "")
+            .with_path(path)
+            .with_max_age(Duration::ZERO)
+    }
+
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn http_only(mut self) -> Self {
+        self.http_only = true;
+        self
+    }
+
+    pub fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    pub fn with_same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+// Set-Cookie 头的值
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        // 浏览器会拒绝没有 Secure 的 SameSite=None
+        if self.secure || self.same_site == Some(SameSite::None) {
+            write!(f, "; Secure")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        Ok(())
+    }
+}
+
+// 解析 Cookie 请求头，返回 (名字, 值) 列表
+pub fn parse(header: &str) -> Vec<(String, String)> {
+    header
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+            // 值可以用双引号包起来
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (!name.is_empty()).then(|| (name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+// 读取请求中的某个 Cookie；同名 Cookie 出现多次时返回第一个（路径最具体的那个）
+pub fn get(request: &Request, name: &str) -> Option<String> {
+    parse(request.header("Cookie")?)
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value)
+}
+
+// 在响应中添加一个 Set-Cookie 头，不覆盖已有的
+pub fn set(response: &mut Response, cookie: &Cookie) {
+    response.append_header("Set-Cookie", &cookie.to_string());
+}
@@ -0,0 +1,151 @@
+// projects/web/e2e_tests.rs - HTTP 客户端与服务器的端到端测试
+// 在随机端口上启动真正的 Server，用 client.rs 通过 TCP 发请求：
+//   cargo test --bin project_web
+//
+// client.rs 也被 14_async.rs 通过 #[path] 引入，测试要用到服务器的模块，所以放在这里
+
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use super::client::{Client, ClientConfig, ClientError};
+use super::http::{Response, StatusCode};
+use super::metrics::Metrics;
+use super::middleware::Pipeline;
+use super::router::Router;
+use super::server::{KeepAlive, Server};
+
+// 启动测试服务器，返回 "http://127.0.0.1:端口"
+fn start(idle_timeout: Duration) -> String {
+    let mut router = Router::new();
+    router
+        .get("/hello", |_| {
+            Response::json(StatusCode::OK, &json!({"message": "hello"}))
+        })
+        .post("/echo", |request| match request.json::<Value>() {
+            Ok(value) => Response::json(StatusCode::CREATED, &value),
+            Err(_) => Response::text(StatusCode::BAD_REQUEST, "invalid json"),
+        })
+        .post("/old", |_| {
+            Response::new(StatusCode::SEE_OTHER).with_header("Location", "/hello")
+        })
+        .get("/peer", |request| {
+            let port = request.peer_addr.map_or(0, |addr| addr.port());
+            Response::text(StatusCode::OK, &port.to_string())
+        })
+        .get("/slow", |_| {
+            thread::sleep(Duration::from_millis(500));
+            Response::text(StatusCode::OK, "slow")
+        });
+
+    let server = Arc::new(Server {
+        pipeline: Pipeline::new(router),
+        keep_alive: KeepAlive {
+            idle_timeout,
+            max_requests: 100,
+        },
+        metrics: Arc::new(Metrics::new(Arc::new(AtomicUsize::new(0)))),
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let server = Arc::clone(&server);
+            thread::spawn(move || server.handle_connection(stream));
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn get_and_post_json() {
+    let base = start(Duration::from_secs(5));
+    let client = Client::new(ClientConfig::default());
+
+    let res = client.get(&format!("{}/hello", base)).send().unwrap();
+    assert_eq!(res.status, 200);
+    assert_eq!(res.json::<Value>().unwrap(), json!({"message": "hello"}));
+
+    let book = json!({"title": "Rust 程序设计", "year": 2024});
+    let res = client
+        .post(&format!("{}/echo", base))
+        .json(&book)
+        .send()
+        .unwrap();
+    assert_eq!(res.status, 201);
+    assert_eq!(res.json::<Value>().unwrap(), book);
+}
+
+#[test]
+fn follows_redirect() {
+    let base = start(Duration::from_secs(5));
+    let client = Client::new(ClientConfig::default());
+
+    // 303 之后改为 GET
+    let res = client
+        .post(&format!("{}/old", base))
+        .json(&json!({}))
+        .send()
+        .unwrap();
+    assert_eq!(res.status, 200);
+    assert_eq!(res.url, format!("{}/hello", base));
+
+    let client = Client::new(ClientConfig {
+        max_redirects: 0,
+        ..ClientConfig::default()
+    });
+    let res = client.post(&format!("{}/old", base)).send().unwrap();
+    assert_eq!(res.status, 303);
+    assert_eq!(res.header("location"), Some("/hello"));
+}
+
+#[test]
+fn reuses_keep_alive_connection() {
+    let base = start(Duration::from_secs(5));
+    let client = Client::new(ClientConfig::default());
+
+    let first = client.get(&format!("{}/peer", base)).send().unwrap();
+    assert_eq!(client.idle_connections(), 1);
+    let second = client.get(&format!("{}/peer", base)).send().unwrap();
+    assert_eq!(client.idle_connections(), 1);
+    // 服务器看到的客户端端口相同，说明是同一条连接
+    assert_eq!(first.text(), second.text());
+}
+
+#[test]
+fn times_out() {
+    let base = start(Duration::from_secs(5));
+    let client = Client::new(ClientConfig::default());
+
+    let result = client
+        .get(&format!("{}/slow", base))
+        .timeout(Duration::from_millis(100))
+        .send();
+    assert!(matches!(result, Err(ClientError::Timeout)), "{:?}", result);
+}
+
+#[test]
+fn retries_stale_connection_only_for_get() {
+    let base = start(Duration::from_millis(100));
+    let client = Client::new(ClientConfig::default());
+
+    // 服务器在空闲 100ms 后关闭连接，池里的连接随之失效
+    client.get(&format!("{}/hello", base)).send().unwrap();
+    thread::sleep(Duration::from_millis(300));
+    let res = client.get(&format!("{}/hello", base)).send().unwrap();
+    assert_eq!(res.status, 200);
+
+    // POST 可能已经被处理，不能自动重发
+    thread::sleep(Duration::from_millis(300));
+    let result = client
+        .post(&format!("{}/echo", base))
+        .json(&json!({}))
+        .send();
+    // 连接被服务器关闭：读到 EOF 或连接被重置
+    assert!(matches!(result, Err(ClientError::Io(_))), "{:?}", result);
+}
@@ -0,0 +1,380 @@
+// projects/web/form.rs - HTML 表单解析
+// 浏览器提交表单时使用两种格式：
+//
+//   application/x-www-form-urlencoded   与查询字符串相同：title=Rust&author=Steve+Klabnik
+//
+//   multipart/form-data                  上传文件时使用，各部分之间用 boundary 分隔：
+//     --BOUNDARY\r\n
+//     Content-Disposition: form-data; name="file"; filename="main.rs"\r\n
+//     Content-Type: text/plain\r\n
+//     \r\n
+//     <文件内容>\r\n
+//     --BOUNDARY--\r\n
+//
+// multipart 请求体已经由 http.rs 写入临时文件，这里边读边处理，文件内容不会整个放进内存
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use super::http::{HttpError, Request, Response, StatusCode, url_decode};
+
+// 每个部分的头部大小上限
+const MAX_PART_HEADER_BYTES: usize = 8 * 1024;
+
+// ========== urlencoded ==========
+
+#[derive(Debug, Default)]
+pub struct Form {
+    fields: Vec<(String, String)>,
+}
+
+impl Form {
+    pub fn parse(s: &str) -> Form {
+        let fields = s
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (url_decode(key), url_decode(value))
+            })
+            .collect();
+        Form { fields }
+    }
+
+    // 同名字段出现多次时返回第一个
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn content_type(request: &Request) -> String {
+    request
+        .header("Content-Type")
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+// 解析 urlencoded 表单请求体
+pub fn urlencoded(request: &Request) -> Result<Form, Response> {
+    if !content_type(request).starts_with("application/x-www-form-urlencoded") {
+        return Err(Response::text(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "请求体必须是 application/x-www-form-urlencoded",
+        ));
+    }
+    let body = std::str::from_utf8(&request.body)
+        .map_err(|_| Response::text(StatusCode::BAD_REQUEST, "表单不是有效的 UTF-8"))?;
+    Ok(Form::parse(body))
+}
+
+// ========== multipart ==========
+
+// 一个部分的头部信息；普通字段没有 filename
+#[derive(Debug)]
+pub struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+}
+
+pub struct Multipart<'a> {
+    reader: &'a File,
+    buf: Vec<u8>,
+    // 部分之间的分隔符："\r\n--" + boundary
+    delimiter: Vec<u8>,
+    // 当前部分的数据还没有读完
+    in_part: bool,
+    done: bool,
+}
+
+// 开始解析 multipart 请求体
+pub fn multipart(request: &Request) -> Result<Multipart<'_>, Response> {
+    let content_type = content_type(request);
+    if !content_type.starts_with("multipart/form-data") {
+        return Err(Response::text(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "请求体必须是 multipart/form-data",
+        ));
+    }
+    // boundary 区分大小写，要从原始头部中取
+    let boundary = request
+        .header("Content-Type")
+        .and_then(|ct| header_param(ct, "boundary"))
+        .filter(|b| !b.is_empty() && b.len() <= 70)
+        .ok_or_else(|| Response::text(StatusCode::BAD_REQUEST, "缺少 multipart boundary"))?;
+
+    let Some(upload) = &request.upload else {
+        return Err(Response::text(StatusCode::BAD_REQUEST, "缺少请求体"));
+    };
+    let mut reader = upload.file();
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| HttpError::from(e).to_response())?;
+
+    Ok(Multipart {
+        reader,
+        // 在开头补一个 CRLF，这样第一个 "--boundary" 也能按普通分隔符匹配
+        buf: b"\r\n".to_vec(),
+        delimiter: format!("\r\n--{}", boundary).into_bytes(),
+        // 第一个分隔符之前的内容（preamble）当作一个需要跳过的部分
+        in_part: true,
+        done: false,
+    })
+}
+
+// 从 `form-data; name="file"; filename="a.rs"` 这样的头部值中取出参数
+fn header_param(value: &str, name: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| {
+            let value = value.trim();
+            value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_string()
+        })
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn malformed(message: &str) -> HttpError {
+    HttpError::BadRequest(format!("multipart 格式错误: {}", message))
+}
+
+impl Multipart<'_> {
+    // 从文件中再读一块到缓冲区；返回读到的字节数，0 表示已经读完
+    fn fill(&mut self) -> io::Result<usize> {
+        let mut chunk = [0; 8 * 1024];
+        let n = self.reader.read(&mut chunk)?;
+        self.buf.extend_from_slice(&chunk[..n]);
+        Ok(n)
+    }
+
+    // 读取下一个部分的头部；没有更多部分时返回 None
+    // 上一个部分的数据没有读取时会被自动跳过
+    pub fn next_part(&mut self) -> Result<Option<Part>, HttpError> {
+        if self.in_part {
+            self.read_to(&mut io::sink(), u64::MAX)?;
+        }
+        if self.done {
+            return Ok(None);
+        }
+
+        // 分隔符后面是 "--" 表示结束，是 CRLF 表示后面还有一个部分
+        while self.buf.len() < 2 {
+            if self.fill()? == 0 {
+                return Err(malformed("缺少结束分隔符"));
+            }
+        }
+        if self.buf.starts_with(b"--") {
+            self.done = true;
+            return Ok(None);
+        }
+        if !self.buf.starts_with(b"\r\n") {
+            return Err(malformed("分隔符后缺少 CRLF"));
+        }
+
+        let end = loop {
+            if let Some(end) = find(&self.buf, b"\r\n\r\n") {
+                break end;
+            }
+            if self.buf.len() > MAX_PART_HEADER_BYTES {
+                return Err(malformed("部分头部过大"));
+            }
+            if self.fill()? == 0 {
+                return Err(malformed("部分头部未结束"));
+            }
+        };
+        let head = String::from_utf8_lossy(&self.buf[2..end]).into_owned();
+        self.buf.drain(..end + 4);
+        self.in_part = true;
+
+        // 只关心 Content-Disposition，其他头部（例如 Content-Type）忽略
+        let disposition = head
+            .split("\r\n")
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-disposition"))
+            .map(|(_, value)| value.trim().to_string())
+            .ok_or_else(|| malformed("缺少 Content-Disposition"))?;
+        let name =
+            header_param(&disposition, "name").ok_or_else(|| malformed("缺少字段名 name"))?;
+
+        Ok(Some(Part {
+            name,
+            filename: header_param(&disposition, "filename"),
+        }))
+    }
+
+    // 把当前部分的数据写入 out，返回写入的字节数；超过 limit 时返回 PayloadTooLarge
+    pub fn read_to<W: Write + ?Sized>(
+        &mut self,
+        out: &mut W,
+        limit: u64,
+    ) -> Result<u64, HttpError> {
+        let mut total = 0;
+        let mut write = |out: &mut W, data: &[u8]| -> Result<(), HttpError> {
+            total += data.len() as u64;
+            if total > limit {
+                return Err(HttpError::PayloadTooLarge);
+            }
+            out.write_all(data)?;
+            Ok(())
+        };
+
+        loop {
+            if let Some(pos) = find(&self.buf, &self.delimiter) {
+                write(out, &self.buf[..pos])?;
+                self.buf.drain(..pos + self.delimiter.len());
+                self.in_part = false;
+                return Ok(total);
+            }
+
+            // 缓冲区末尾可能是分隔符的前半段，先保留下来
+            let keep = self.delimiter.len() - 1;
+            if self.buf.len() > keep {
+                let n = self.buf.len() - keep;
+                write(out, &self.buf[..n])?;
+                self.buf.drain(..n);
+            }
+            if self.fill()? == 0 {
+                return Err(malformed("缺少结束分隔符"));
+            }
+        }
+    }
+
+    // 读取普通文本字段
+    pub fn read_text(&mut self, limit: u64) -> Result<String, HttpError> {
+        let mut data = Vec::new();
+        self.read_to(&mut data, limit)?;
+        String::from_utf8(data).map_err(|_| malformed("字段不是有效的 UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(content_type: &str, body: &[u8]) -> Request {
+        let mut raw = format!(
+            "POST / HTTP/1.1\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            content_type,
+            body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(body);
+        Request::read_from(&mut &raw[..]).unwrap().unwrap()
+    }
+
+    #[test]
+    fn parses_urlencoded_fields() {
+        let form = Form::parse("title=Rust+%E5%AE%9E%E6%88%98&author=&flag&title=second&");
+        assert_eq!(form.get("title"), Some("Rust 实战"));
+        assert_eq!(form.get("author"), Some(""));
+        assert_eq!(form.get("flag"), Some(""));
+        assert_eq!(form.get("missing"), None);
+    }
+
+    #[test]
+    fn urlencoded_checks_content_type() {
+        let ok = request("application/x-www-form-urlencoded; charset=utf-8", b"a=1");
+        assert_eq!(urlencoded(&ok).unwrap().get("a"), Some("1"));
+
+        let json = request("application/json", b"{}");
+        assert_eq!(
+            urlencoded(&json).unwrap_err().status,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[test]
+    fn reads_fields_and_files() {
+        let body = b"preamble\r\n\
+            --XyZ\r\n\
+            Content-Disposition: form-data; name=\"exercise\"\r\n\r\n\
+            ownership-1\r\n\
+            --XyZ\r\n\
+            content-disposition: form-data; name=\"file\"; filename=\"main.rs\"\r\n\
+            Content-Type: text/plain\r\n\r\n\
+            fn main() {}\r\n--Xy\r\n\
+            --XyZ\r\n\
+            Content-Disposition: form-data; name=\"skipped\"\r\n\r\n\
+            ignored\r\n\
+            --XyZ--\r\n";
+        let request = request("multipart/form-data; boundary=\"XyZ\"", body);
+        let mut parts = multipart(&request).unwrap();
+
+        let part = parts.next_part().unwrap().unwrap();
+        assert_eq!((part.name.as_str(), part.filename), ("exercise", None));
+        assert_eq!(parts.read_text(100).unwrap(), "ownership-1");
+
+        let part = parts.next_part().unwrap().unwrap();
+        assert_eq!(part.name, "file");
+        assert_eq!(part.filename.as_deref(), Some("main.rs"));
+        let mut data = Vec::new();
+        parts.read_to(&mut data, 100).unwrap();
+        // 和分隔符开头相同但不完整的内容属于文件数据
+        assert_eq!(data, b"fn main() {}\r\n--Xy");
+
+        // 没有读取的部分被自动跳过
+        assert_eq!(parts.next_part().unwrap().unwrap().name, "skipped");
+        assert!(parts.next_part().unwrap().is_none());
+    }
+
+    #[test]
+    fn reads_large_parts_across_buffer_boundaries() {
+        let content: Vec<u8> = (0..50_000u32).map(|i| b'a' + (i % 26) as u8).collect();
+        let mut body =
+            b"--b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\r\n"
+                .to_vec();
+        body.extend_from_slice(&content);
+        body.extend_from_slice(b"\r\n--b--\r\n");
+        let request = request("multipart/form-data; boundary=b", &body);
+
+        let mut parts = multipart(&request).unwrap();
+        parts.next_part().unwrap().unwrap();
+        let mut data = Vec::new();
+        assert_eq!(parts.read_to(&mut data, u64::MAX).unwrap(), 50_000);
+        assert_eq!(data, content);
+
+        let mut parts = multipart(&request).unwrap();
+        parts.next_part().unwrap().unwrap();
+        assert!(matches!(
+            parts.read_to(&mut io::sink(), 1000),
+            Err(HttpError::PayloadTooLarge)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_multipart() {
+        let no_boundary = request("multipart/form-data", b"");
+        assert_eq!(
+            multipart(&no_boundary).err().unwrap().status,
+            StatusCode::BAD_REQUEST
+        );
+
+        let unterminated = request(
+            "multipart/form-data; boundary=b",
+            b"--b\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue",
+        );
+        let mut parts = multipart(&unterminated).unwrap();
+        parts.next_part().unwrap().unwrap();
+        assert!(matches!(
+            parts.read_text(100),
+            Err(HttpError::BadRequest(_))
+        ));
+
+        let no_name = request(
+            "multipart/form-data; boundary=b",
+            b"--b\r\nContent-Disposition: form-data\r\n\r\nvalue\r\n--b--\r\n",
+        );
+        assert!(multipart(&no_name).unwrap().next_part().is_err());
+    }
+}
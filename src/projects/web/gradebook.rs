@@ -0,0 +1,202 @@
+// projects/web/gradebook.rs - 成绩册页面
+//   GET /gradebook            所有学习者的进度汇总
+//   GET /gradebook/:learner   一个学习者每道练习的记录
+//
+// 数据来自练习程序（cargo run --bin exercises）保存的 progress.json（格式见 exercises/progress.rs）。
+// 用 --gradebook <目录> 指定成绩册目录，其中每个子目录是一个学习者的数据目录，
+// 也就是运行练习程序时 RUST_LEARNING_DATA_DIR 指向的目录：
+//
+//   gradebook/
+//     alice/progress.json
+//     bob/progress.json
+//
+// 每次请求都重新读取文件，学习者检查练习后刷新页面就能看到新的成绩
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::http::{Request, Response, StatusCode};
+use super::router::Router;
+use super::template::Templates;
+
+const FILE_NAME: &str = "progress.json";
+
+// 只读取成绩册需要的字段，其余字段忽略
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Progress {
+    exercises: BTreeMap<String, ExerciseProgress>,
+    quiz: BTreeMap<String, QuizProgress>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ExerciseProgress {
+    completed: bool,
+    last_passed: bool,
+    attempts: u32,
+    best_time_us: Option<u64>,
+    last_attempted: Option<String>,
+    hints_used: u32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct QuizProgress {
+    attempts: u32,
+    correct: u32,
+}
+
+// 汇总表中的一行
+#[derive(Default, Serialize)]
+struct Summary {
+    name: String,
+    completed: usize,
+    exercises: usize,
+    attempts: u32,
+    hints_used: u32,
+    quiz_correct: u32,
+    quiz_attempts: u32,
+    last_attempted: Option<String>,
+    // progress.json 无法读取或解析时的错误信息
+    error: Option<String>,
+}
+
+// 成绩册目录下的学习者（子目录名），按名称排序
+fn learners(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().join(FILE_NAME).is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn load(dir: &Path, learner: &str) -> Result<Progress, String> {
+    let path = dir.join(learner).join(FILE_NAME);
+    let content = fs::read_to_string(&path).map_err(|e| format!("无法读取: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("无法解析: {}", e))
+}
+
+fn summarize(name: String, progress: Result<Progress, String>) -> Summary {
+    let progress = match progress {
+        Ok(progress) => progress,
+        Err(error) => {
+            log_warn!("⚠️ 成绩册中 {} 的进度文件有问题: {}", name, error);
+            return Summary {
+                name,
+                error: Some(error),
+                ..Summary::default()
+            };
+        }
+    };
+    let exercises = progress.exercises.values();
+    Summary {
+        name,
+        completed: exercises.clone().filter(|e| e.completed).count(),
+        exercises: progress.exercises.len(),
+        attempts: exercises.clone().map(|e| e.attempts).sum(),
+        hints_used: exercises.clone().map(|e| e.hints_used).sum(),
+        quiz_correct: progress.quiz.values().map(|q| q.correct).sum(),
+        quiz_attempts: progress.quiz.values().map(|q| q.attempts).sum(),
+        // 日期格式是 "2025-10-19"，按字符串比较就是按日期比较
+        last_attempted: exercises.filter_map(|e| e.last_attempted.clone()).max(),
+        error: None,
+    }
+}
+
+fn gradebook_page(dir: &Path, templates: &Templates) -> Response {
+    let summaries: Vec<Summary> = learners(dir)
+        .into_iter()
+        .map(|name| {
+            let progress = load(dir, &name);
+            summarize(name, progress)
+        })
+        .collect();
+    templates.page(
+        StatusCode::OK,
+        "gradebook.html",
+        &json!({
+            "dir": dir.display().to_string(),
+            "learners": summaries,
+        }),
+    )
+}
+
+fn learner_page(dir: &Path, templates: &Templates, request: &Request) -> Response {
+    let name = request.param("learner").unwrap_or_default();
+    // 名称来自 URL：只接受成绩册目录中确实存在的学习者，不会拼出目录外的路径
+    let progress = if learners(dir).iter().any(|learner| learner == name) {
+        load(dir, name).map(Some)
+    } else {
+        Ok(None)
+    };
+    let status = match &progress {
+        Ok(Some(_)) => StatusCode::OK,
+        Ok(None) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    let (exercises, error) = match progress {
+        Ok(progress) => (progress.map(|p| p.exercises), None),
+        Err(error) => (None, Some(error)),
+    };
+    let rows: Option<Vec<_>> = exercises.map(|exercises| {
+        exercises
+            .into_iter()
+            .map(|(id, exercise)| {
+                json!({
+                    "id": id,
+                    "best_time": exercise.best_time_us.map(format_micros),
+                    "progress": exercise,
+                })
+            })
+            .collect()
+    });
+
+    templates.page(
+        status,
+        "learner.html",
+        &json!({
+            "name": name,
+            "exercises": rows,
+            "error": error,
+        }),
+    )
+}
+
+// 最短用时：小于 1 毫秒时用微秒
+fn format_micros(us: u64) -> String {
+    if us < 1000 {
+        format!("{}µs", us)
+    } else {
+        format!("{:.2}ms", us as f64 / 1000.0)
+    }
+}
+
+// 注册成绩册路由；dir 为 None 时页面只显示如何配置
+pub fn register(router: &mut Router, dir: Option<PathBuf>, templates: Arc<Templates>) {
+    let Some(dir) = dir else {
+        router.get("/gradebook", move |_| {
+            templates.page(StatusCode::NOT_FOUND, "gradebook.html", &json!({}))
+        });
+        return;
+    };
+
+    let d = dir.clone();
+    let tpl = Arc::clone(&templates);
+    router.get("/gradebook", move |_| gradebook_page(&d, &tpl));
+
+    router.get("/gradebook/:learner", move |req| {
+        learner_page(&dir, &templates, req)
+    });
+}
@@ -0,0 +1,896 @@
+// projects/web/http.rs - HTTP 请求解析与响应构建
+// 只实现本项目需要的 HTTP/1.1 子集：请求行、头部、Content-Length 与 chunked 请求体，
+// 以及定长响应和 chunked 流式响应
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::session::Session;
+
+// 请求头部总大小上限，防止恶意客户端发送无限长的头部
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+// 请求体大小上限
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+// multipart/form-data 上传的大小上限；上传内容直接写入临时文件，不占用内存
+const MAX_UPLOAD_BYTES: u64 = 8 * 1024 * 1024;
+
+// ========== 错误类型 ==========
+
+#[derive(Debug)]
+pub enum HttpError {
+    Io(io::Error),
+    BadRequest(String),
+    PayloadTooLarge,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::Io(e) => write!(f, "IO 错误: {}", e),
+            HttpError::BadRequest(msg) => write!(f, "错误的请求: {}", msg),
+            HttpError::PayloadTooLarge => write!(f, "请求体过大"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<io::Error> for HttpError {
+    fn from(e: io::Error) -> Self {
+        HttpError::Io(e)
+    }
+}
+
+impl HttpError {
+    // 把解析错误转换成返回给客户端的响应
+    pub fn to_response(&self) -> Response {
+        match self {
+            HttpError::Io(_) => Response::text(StatusCode::BAD_REQUEST, "Bad Request"),
+            HttpError::BadRequest(msg) => Response::text(StatusCode::BAD_REQUEST, msg),
+            HttpError::PayloadTooLarge => {
+                Response::text(StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large")
+            }
+        }
+    }
+}
+
+// ========== 请求方法 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Head,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Options,
+    Other(String),
+}
+
+impl Method {
+    fn parse(s: &str) -> Method {
+        match s {
+            "GET" => Method::Get,
+            "HEAD" => Method::Head,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "PATCH" => Method::Patch,
+            "DELETE" => Method::Delete,
+            "OPTIONS" => Method::Options,
+            other => Method::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+            Method::Options => "OPTIONS",
+            Method::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ========== 状态码 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusCode(pub u16);
+
+impl StatusCode {
+    pub const SWITCHING_PROTOCOLS: StatusCode = StatusCode(101);
+    pub const OK: StatusCode = StatusCode(200);
+    pub const CREATED: StatusCode = StatusCode(201);
+    pub const NO_CONTENT: StatusCode = StatusCode(204);
+    pub const SEE_OTHER: StatusCode = StatusCode(303);
+    pub const NOT_MODIFIED: StatusCode = StatusCode(304);
+    pub const BAD_REQUEST: StatusCode = StatusCode(400);
+    pub const NOT_FOUND: StatusCode = StatusCode(404);
+    pub const METHOD_NOT_ALLOWED: StatusCode = StatusCode(405);
+    pub const CONFLICT: StatusCode = StatusCode(409);
+    pub const PAYLOAD_TOO_LARGE: StatusCode = StatusCode(413);
+    pub const UNSUPPORTED_MEDIA_TYPE: StatusCode = StatusCode(415);
+    pub const UPGRADE_REQUIRED: StatusCode = StatusCode(426);
+    pub const UNPROCESSABLE_ENTITY: StatusCode = StatusCode(422);
+    pub const TOO_MANY_REQUESTS: StatusCode = StatusCode(429);
+    pub const INTERNAL_SERVER_ERROR: StatusCode = StatusCode(500);
+    pub const BAD_GATEWAY: StatusCode = StatusCode(502);
+    pub const SERVICE_UNAVAILABLE: StatusCode = StatusCode(503);
+    pub const GATEWAY_TIMEOUT: StatusCode = StatusCode(504);
+
+    pub fn reason(&self) -> &'static str {
+        match self.0 {
+            101 => "Switching Protocols",
+            200 => "OK",
+            201 => "Created",
+            204 => "No Content",
+            301 => "Moved Permanently",
+            302 => "Found",
+            303 => "See Other",
+            304 => "Not Modified",
+            307 => "Temporary Redirect",
+            308 => "Permanent Redirect",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            415 => "Unsupported Media Type",
+            422 => "Unprocessable Entity",
+            426 => "Upgrade Required",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Unknown",
+        }
+    }
+}
+
+// ========== 请求 ==========
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub version: String,
+    // 头部名统一转为小写，方便不区分大小写地查找
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    // multipart/form-data 请求的请求体，保存在临时文件中（此时 body 为空）
+    pub upload: Option<TempFile>,
+    // 路由匹配出的路径参数，例如 /books/:isbn 中的 isbn
+    pub params: HashMap<String, String>,
+    // 匹配到的路由模式，例如 /books/:isbn；没有匹配到路由时为 None
+    pub route: Option<String>,
+    // 客户端地址，由服务器在读取请求后填写
+    pub peer_addr: Option<SocketAddr>,
+    // 当前会话，由 Sessions 中间件填写
+    pub session: Option<Session>,
+}
+
+impl Request {
+    // 从连接中读取并解析一个完整请求
+    // 连接在收到任何数据前被关闭时返回 Ok(None)
+    pub fn read_from<R: BufRead>(reader: &mut R) -> Result<Option<Request>, HttpError> {
+        let mut header_bytes = 0;
+
+        let mut request_line = String::new();
+        if read_line_limited(reader, &mut request_line, MAX_HEADER_BYTES, "请求行过长")? == 0 {
+            return Ok(None);
+        }
+        header_bytes += request_line.len();
+
+        let mut parts = request_line.split_whitespace();
+        let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(m), Some(t), Some(v)) => (m, t, v),
+            _ => return Err(HttpError::BadRequest("无效的请求行".to_string())),
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(HttpError::BadRequest(format!(
+                "不支持的协议版本: {}",
+                version
+            )));
+        }
+
+        let (path, query) = match target.split_once('?') {
+            Some((p, q)) => (p.to_string(), Some(q.to_string())),
+            None => (target.to_string(), None),
+        };
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            // 最多再读剩余的额度加一个字节，超出时下面的检查会报错
+            let remaining = MAX_HEADER_BYTES.saturating_sub(header_bytes) + 1;
+            let n = reader
+                .by_ref()
+                .take(remaining as u64)
+                .read_line(&mut line)?;
+            if n == 0 {
+                return Err(HttpError::BadRequest("头部未结束连接就关闭了".to_string()));
+            }
+            header_bytes += n;
+            if header_bytes > MAX_HEADER_BYTES {
+                return Err(HttpError::BadRequest("请求头部过大".to_string()));
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                break;
+            }
+            match line.split_once(':') {
+                Some((name, value)) => {
+                    let name = name.trim().to_ascii_lowercase();
+                    let value = value.trim().to_string();
+                    // 多个不同的 Content-Length 无法确定请求体在哪里结束（请求走私的常见手法）
+                    if name == "content-length"
+                        && headers
+                            .get(&name)
+                            .is_some_and(|existing| *existing != value)
+                    {
+                        return Err(HttpError::BadRequest("Content-Length 头部冲突".to_string()));
+                    }
+                    headers.insert(name, value);
+                }
+                None => return Err(HttpError::BadRequest(format!("无效的头部: {}", line))),
+            }
+        }
+
+        // 文件上传可能很大，边读边写入临时文件；其他请求体放在内存中
+        let is_upload = headers
+            .get("content-type")
+            .is_some_and(|ct| ct.to_ascii_lowercase().starts_with("multipart/form-data"));
+        let mut body = Vec::new();
+        let mut upload = None;
+        if is_upload {
+            let mut file = TempFile::create()?;
+            read_body(reader, &headers, &mut file, MAX_UPLOAD_BYTES)?;
+            upload = Some(file);
+        } else {
+            read_body(reader, &headers, &mut body, MAX_BODY_BYTES)?;
+        }
+
+        Ok(Some(Request {
+            method: Method::parse(method),
+            path,
+            query,
+            version: version.to_string(),
+            headers,
+            body,
+            upload,
+            params: HashMap::new(),
+            route: None,
+            peer_addr: None,
+            session: None,
+        }))
+    }
+
+    // 不区分大小写地获取头部
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(|v| v.as_str())
+    }
+
+    // 客户端是否希望保持连接：
+    // HTTP/1.1 默认保持，除非 Connection: close；HTTP/1.0 默认关闭，除非 Connection: keep-alive
+    pub fn wants_keep_alive(&self) -> bool {
+        let connection = self.header("Connection").unwrap_or("");
+        let has = |token: &str| {
+            connection
+                .split(',')
+                .any(|t| t.trim().eq_ignore_ascii_case(token))
+        };
+        if self.version == "HTTP/1.0" {
+            has("keep-alive")
+        } else {
+            !has("close")
+        }
+    }
+
+    // 获取查询参数，例如 /books?author=Klabnik 中的 author
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        let query = self.query.as_deref()?;
+        query.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (url_decode(key) == name).then(|| url_decode(value))
+        })
+    }
+
+    // 获取路径参数
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|v| v.as_str())
+    }
+
+    // 把请求体解析为 JSON
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+// 按 Transfer-Encoding / Content-Length 读取请求体，写入 out
+fn read_body<R: BufRead, W: Write>(
+    reader: &mut R,
+    headers: &HashMap<String, String>,
+    out: &mut W,
+    limit: u64,
+) -> Result<(), HttpError> {
+    // 同时出现 Transfer-Encoding 和 Content-Length 时无法确定请求边界，直接拒绝，
+    // 避免请求走私 (request smuggling)
+    match (
+        headers.get("transfer-encoding"),
+        headers.get("content-length"),
+    ) {
+        (Some(_), Some(_)) => Err(HttpError::BadRequest(
+            "不能同时使用 Transfer-Encoding 和 Content-Length".to_string(),
+        )),
+        (Some(te), None) if te.eq_ignore_ascii_case("chunked") => {
+            read_chunked_body(reader, out, limit)
+        }
+        (Some(te), None) => Err(HttpError::BadRequest(format!(
+            "不支持的 Transfer-Encoding: {}",
+            te
+        ))),
+        (None, Some(len)) => {
+            let len = len
+                .parse::<u64>()
+                .map_err(|_| HttpError::BadRequest("无效的 Content-Length".to_string()))?;
+            if len > limit {
+                return Err(HttpError::PayloadTooLarge);
+            }
+            if io::copy(&mut reader.take(len), out)? < len {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            Ok(())
+        }
+        (None, None) => Ok(()),
+    }
+}
+
+// 读取 chunked 编码的请求体：
+//   <十六进制长度>[;扩展]\r\n<数据>\r\n ... 0\r\n[尾部头部]\r\n
+fn read_chunked_body<R: BufRead, W: Write>(
+    reader: &mut R,
+    out: &mut W,
+    limit: u64,
+) -> Result<(), HttpError> {
+    let mut total: u64 = 0;
+
+    loop {
+        let mut line = String::new();
+        if read_line_limited(reader, &mut line, MAX_HEADER_BYTES, "chunk 长度行过长")? == 0 {
+            return Err(HttpError::BadRequest("chunked 请求体未结束".to_string()));
+        }
+        let size = line.trim_end().split(';').next().unwrap_or("").trim();
+        let size = u64::from_str_radix(size, 16)
+            .map_err(|_| HttpError::BadRequest(format!("无效的 chunk 长度: {}", size)))?;
+
+        if size == 0 {
+            // 跳过尾部头部 (trailers)，直到空行
+            loop {
+                let mut trailer = String::new();
+                if read_line_limited(reader, &mut trailer, MAX_HEADER_BYTES, "尾部头部过长")? == 0
+                    || trailer.trim_end().is_empty()
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        // size 来自客户端，累加时可能溢出
+        total = total
+            .checked_add(size)
+            .filter(|total| *total <= limit)
+            .ok_or(HttpError::PayloadTooLarge)?;
+        if io::copy(&mut reader.take(size), out)? < size {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let mut crlf = [0; 2];
+        reader.read_exact(&mut crlf)?;
+        if &crlf != b"\r\n" {
+            return Err(HttpError::BadRequest("chunk 数据后缺少 CRLF".to_string()));
+        }
+    }
+}
+
+// 读取一行，最多 limit 字节；没读到换行就达到上限时返回 BadRequest(too_long)，
+// 防止客户端发送不带换行的超长行耗尽内存
+fn read_line_limited<R: BufRead>(
+    reader: &mut R,
+    line: &mut String,
+    limit: usize,
+    too_long: &str,
+) -> Result<usize, HttpError> {
+    let n = reader.by_ref().take(limit as u64).read_line(line)?;
+    if n >= limit && !line.ends_with('\n') {
+        return Err(HttpError::BadRequest(too_long.to_string()));
+    }
+    Ok(n)
+}
+
+// ========== 临时文件 ==========
+
+// 上传内容的临时文件，请求处理完（被 drop）时自动删除
+#[derive(Debug)]
+pub struct TempFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl TempFile {
+    fn create() -> io::Result<TempFile> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let path = env::temp_dir().join(format!(
+            "rust-web-upload-{}-{}",
+            process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        Ok(TempFile { path, file })
+    }
+
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+}
+
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// 解码 URL 编码：'+' 表示空格，%XX 表示一个字节
+pub fn url_decode(s: &str) -> String {
+    fn hex(b: u8) -> Option<u8> {
+        (b as char).to_digit(16).map(|d| d as u8)
+    }
+
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                (Some(hi), Some(lo)) => {
+                    out.push(hi << 4 | lo);
+                    i += 2;
+                }
+                _ => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ========== 响应 ==========
+
+// 流式响应体的生成函数：每次 write 调用都会作为一个 chunk 发送给客户端
+pub type StreamFn = Box<dyn FnOnce(&mut dyn Write) -> io::Result<()> + Send>;
+
+// 协议升级（例如 WebSocket）：101 响应写出后，连接交给这个函数接管
+pub type UpgradeFn = Box<dyn FnOnce(Upgraded) + Send>;
+
+pub enum Body {
+    Bytes(Vec<u8>),
+    Stream(StreamFn),
+    Upgrade(UpgradeFn),
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Body::Bytes(bytes) => write!(f, "Bytes({} bytes)", bytes.len()),
+            Body::Stream(_) => f.write_str("Stream"),
+            Body::Upgrade(_) => f.write_str("Upgrade"),
+        }
+    }
+}
+
+// 升级后的连接
+// 读取 HTTP 请求时 BufReader 可能已经多读了一些属于新协议的数据，需要先把它们读出来
+pub struct Upgraded {
+    buffered: io::Cursor<Vec<u8>>,
+    stream: TcpStream,
+}
+
+impl Upgraded {
+    pub fn new(buffered: Vec<u8>, stream: TcpStream) -> Upgraded {
+        Upgraded {
+            buffered: io::Cursor::new(buffered),
+            stream,
+        }
+    }
+
+    pub fn stream(&self) -> &TcpStream {
+        &self.stream
+    }
+}
+
+impl Read for Upgraded {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if (self.buffered.position() as usize) < self.buffered.get_ref().len() {
+            return self.buffered.read(buf);
+        }
+        self.stream.read(buf)
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: Vec<(String, String)>,
+    pub body: Body,
+}
+
+impl Response {
+    pub fn new(status: StatusCode) -> Response {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: Body::Bytes(Vec::new()),
+        }
+    }
+
+    pub fn text(status: StatusCode, body: &str) -> Response {
+        Response::new(status)
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body(body.as_bytes().to_vec())
+    }
+
+    pub fn html(status: StatusCode, body: &str) -> Response {
+        Response::new(status)
+            .with_header("Content-Type", "text/html; charset=utf-8")
+            .with_body(body.as_bytes().to_vec())
+    }
+
+    pub fn json<T: Serialize>(status: StatusCode, value: &T) -> Response {
+        match serde_json::to_vec(value) {
+            Ok(body) => Response::new(status)
+                .with_header("Content-Type", "application/json")
+                .with_body(body),
+            Err(e) => Response::text(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("JSON 序列化失败: {}", e),
+            ),
+        }
+    }
+
+    // 流式响应：不需要事先把全部内容放进内存
+    pub fn stream<F>(status: StatusCode, content_type: &str, f: F) -> Response
+    where
+        F: FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static,
+    {
+        Response {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: Body::Stream(Box::new(f)),
+        }
+    }
+
+    // 101 Switching Protocols 响应，连接由 on_upgrade 接管
+    pub fn upgrade<F>(on_upgrade: F) -> Response
+    where
+        F: FnOnce(Upgraded) + Send + 'static,
+    {
+        Response {
+            status: StatusCode::SWITCHING_PROTOCOLS,
+            headers: Vec::new(),
+            body: Body::Upgrade(Box::new(on_upgrade)),
+        }
+    }
+
+    // 取出升级函数，响应体替换为空
+    pub fn take_upgrade(&mut self) -> Option<UpgradeFn> {
+        match std::mem::replace(&mut self.body, Body::Bytes(Vec::new())) {
+            Body::Upgrade(f) => Some(f),
+            body => {
+                self.body = body;
+                None
+            }
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Response {
+        self.set_header(name, value);
+        self
+    }
+
+    pub fn with_body(mut self, body: Vec<u8>) -> Response {
+        self.body = Body::Bytes(body);
+        self
+    }
+
+    // 设置头部，同名头部（不区分大小写）会被替换
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    // 添加一个头部，保留同名的已有头部（例如多个 Set-Cookie）
+    pub fn append_header(&mut self, name: &str, value: &str) {
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    // 向 Vary 头追加一项；多个中间件都可能设置 Vary，不能互相覆盖
+    pub fn add_vary(&mut self, value: &str) {
+        let vary = match self.header("Vary") {
+            Some(existing)
+                if existing
+                    .split(',')
+                    .any(|v| v.trim().eq_ignore_ascii_case(value)) =>
+            {
+                return;
+            }
+            Some(existing) => format!("{}, {}", existing, value),
+            None => value.to_string(),
+        };
+        self.set_header("Vary", &vary);
+    }
+
+    // 序列化并写入连接
+    // 定长响应体使用 Content-Length；流式响应体在 chunked 为 true 时使用 chunked 编码，
+    // 否则（HTTP/1.0 客户端）直接写出原始数据，由调用方在结束后关闭连接来标记响应结束
+    // HEAD 请求（head 为 true）以及 1xx、204、304 响应只写出头部：客户端不会读取响应体，
+    // 多写的字节会被当成下一个响应的开头
+    pub fn write_to<W: Write>(self, writer: &mut W, chunked: bool, head: bool) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\n",
+            self.status.0,
+            self.status.reason()
+        )?;
+        for (name, value) in &self.headers {
+            write!(writer, "{}: {}\r\n", name, value)?;
+        }
+
+        let bodyless_status = self.status.0 < 200
+            || self.status == StatusCode::NO_CONTENT
+            || self.status == StatusCode::NOT_MODIFIED;
+        let write_body = !head && !bodyless_status;
+        match self.body {
+            Body::Bytes(body) => {
+                // 1xx、204 和 304 响应不带 Content-Length；HEAD 响应带上 GET 时的长度
+                if !bodyless_status {
+                    write!(writer, "Content-Length: {}\r\n", body.len())?;
+                }
+                writer.write_all(b"\r\n")?;
+                if write_body {
+                    writer.write_all(&body)?;
+                }
+            }
+            Body::Stream(f) if write_body && chunked => {
+                writer.write_all(b"Transfer-Encoding: chunked\r\n\r\n")?;
+                let mut chunks = ChunkedWriter {
+                    inner: &mut *writer,
+                };
+                f(&mut chunks)?;
+                chunks.finish()?;
+            }
+            Body::Stream(f) if write_body => {
+                writer.write_all(b"\r\n")?;
+                f(writer)?;
+            }
+            // 不需要响应体时流式响应体的生成函数不会被调用
+            Body::Stream(_) => writer.write_all(b"\r\n")?,
+            // 升级函数应当先通过 take_upgrade 取出，这里只写出头部
+            Body::Upgrade(_) => writer.write_all(b"\r\n")?,
+        }
+        writer.flush()
+    }
+}
+
+// 把每次 write 包装成一个 chunk
+struct ChunkedWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> ChunkedWriter<W> {
+    // 写入长度为 0 的结束 chunk
+    fn finish(mut self) -> io::Result<()> {
+        self.inner.write_all(b"0\r\n\r\n")
+    }
+}
+
+impl<W: Write> Write for ChunkedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // 空 chunk 会被当作结束标记，必须跳过
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        write!(self.inner, "{:X}\r\n", buf.len())?;
+        self.inner.write_all(buf)?;
+        self.inner.write_all(b"\r\n")?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Result<Option<Request>, HttpError> {
+        Request::read_from(&mut raw.as_bytes())
+    }
+
+    fn bad_request(result: Result<Option<Request>, HttpError>) -> String {
+        match result {
+            Err(HttpError::BadRequest(message)) => message,
+            other => panic!("应当是 BadRequest: {:?}", other.map(|r| r.map(|r| r.path))),
+        }
+    }
+
+    fn written(response: Response, chunked: bool, head: bool) -> String {
+        let mut out = Vec::new();
+        response.write_to(&mut out, chunked, head).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parses_request_line_headers_and_body() {
+        let request =
+            parse("POST /books?author=a%20b HTTP/1.1\r\nHost: x\r\nContent-Length: 2\r\n\r\n{}")
+                .unwrap()
+                .unwrap();
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.path, "/books");
+        assert_eq!(request.query_param("author").as_deref(), Some("a b"));
+        assert_eq!(request.header("HOST"), Some("x"));
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[test]
+    fn closed_connection_is_not_a_request() {
+        assert!(parse("").unwrap().is_none());
+    }
+
+    #[test]
+    fn decodes_chunked_body_with_extensions_and_trailers() {
+        let request = parse(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+             5;name=value\r\nhello\r\n7\r\n, world\r\n0\r\nX-Trailer: 1\r\n\r\n",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.body, b"hello, world");
+    }
+
+    #[test]
+    fn rejects_malformed_chunks() {
+        let message = bad_request(parse(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n",
+        ));
+        assert!(message.contains("chunk 长度"), "{}", message);
+
+        let message = bad_request(parse(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nabXX0\r\n\r\n",
+        ));
+        assert!(message.contains("CRLF"), "{}", message);
+    }
+
+    #[test]
+    fn chunked_body_is_limited() {
+        let huge = format!(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:X}\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(matches!(parse(&huge), Err(HttpError::PayloadTooLarge)));
+    }
+
+    #[test]
+    fn rejects_overlong_lines() {
+        let long_target = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEADER_BYTES));
+        assert_eq!(bad_request(parse(&long_target)), "请求行过长");
+
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(MAX_HEADER_BYTES)
+        );
+        assert_eq!(bad_request(parse(&long_header)), "请求头部过大");
+
+        let long_chunk_line = format!(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n1;{}\r\n",
+            "x".repeat(MAX_HEADER_BYTES)
+        );
+        assert_eq!(bad_request(parse(&long_chunk_line)), "chunk 长度行过长");
+    }
+
+    #[test]
+    fn rejects_ambiguous_body_length() {
+        bad_request(parse(
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 1\r\n\r\n",
+        ));
+        bad_request(parse(
+            "POST / HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\nab",
+        ));
+        // 重复但相同的 Content-Length 没有歧义
+        let request = parse("POST / HTTP/1.1\r\nContent-Length: 2\r\nContent-Length: 2\r\n\r\nab")
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.body, b"ab");
+    }
+
+    #[test]
+    fn writes_chunked_stream() {
+        let response = Response::stream(StatusCode::OK, "text/plain", |out| {
+            out.write_all(b"ab")?;
+            out.write_all(b"")?;
+            out.write_all(b"cde")
+        });
+        let text = written(response, true, false);
+        assert!(text.contains("Transfer-Encoding: chunked\r\n"));
+        assert!(text.ends_with("\r\n\r\n2\r\nab\r\n3\r\ncde\r\n0\r\n\r\n"));
+    }
+
+    #[test]
+    fn omits_body_for_head_and_bodyless_statuses() {
+        let text = written(Response::text(StatusCode::OK, "hello"), false, true);
+        assert!(text.contains("Content-Length: 5\r\n"));
+        assert!(text.ends_with("\r\n\r\n"));
+
+        for status in [StatusCode::NO_CONTENT, StatusCode::NOT_MODIFIED] {
+            let text = written(Response::new(status).with_body(b"x".to_vec()), false, false);
+            assert!(!text.contains("Content-Length"), "{}", text);
+            assert!(text.ends_with("\r\n\r\n"));
+        }
+
+        let response = Response::stream(StatusCode::OK, "text/plain", |_| {
+            panic!("HEAD 请求不应该生成响应体")
+        });
+        assert!(written(response, true, true).ends_with("\r\n\r\n"));
+    }
+}
@@ -0,0 +1,250 @@
+// projects/web/metrics.rs - 监控指标与健康检查
+// 以 Prometheus 文本格式在 /metrics 暴露：
+//   http_requests_total              按方法、路由、状态码统计的请求数 (counter)
+//   http_request_duration_seconds    按路由统计的处理耗时 (histogram)
+//   http_active_connections          当前打开的连接数 (gauge)
+//   thread_pool_queue_depth          等待工作线程处理的连接数 (gauge)
+// 以及 /healthz（进程存活）和 /readyz（是否可以接收新流量）
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use super::http::{Method, Request, Response, StatusCode};
+use super::middleware::{Middleware, Next};
+use super::router::Router;
+
+// 直方图的桶上界（秒）
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+// 没有匹配到路由的请求（404、被限流等）统一记在这个标签下，
+// 否则随便构造的 URL 都会产生新的时间序列，指标数量会无限增长
+const UNMATCHED_ROUTE: &str = "unmatched";
+// 同样的道理，客户端自己编造的请求方法统一记为 OTHER
+const OTHER_METHOD: &str = "OTHER";
+
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    // 每个桶单独计数，输出时再累加成 Prometheus 要求的累计值
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|&le| value <= le) {
+            self.buckets[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct RequestStats {
+    // (方法, 路由, 状态码) → 请求数；BTreeMap 让输出顺序稳定
+    counts: BTreeMap<(String, String, u16), u64>,
+    latency: BTreeMap<String, Histogram>,
+}
+
+pub struct Metrics {
+    requests: Mutex<RequestStats>,
+    active_connections: AtomicUsize,
+    queue_depth: Arc<AtomicUsize>,
+    draining: AtomicBool,
+}
+
+impl Metrics {
+    // queue_depth 由线程池维护
+    pub fn new(queue_depth: Arc<AtomicUsize>) -> Metrics {
+        Metrics {
+            requests: Mutex::new(RequestStats::default()),
+            active_connections: AtomicUsize::new(0),
+            queue_depth,
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    fn record(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        let mut stats = self.requests.lock().unwrap_or_else(PoisonError::into_inner);
+        *stats
+            .counts
+            .entry((method.to_string(), route.to_string(), status))
+            .or_insert(0) += 1;
+        stats
+            .latency
+            .entry(route.to_string())
+            .or_default()
+            .observe(seconds);
+    }
+
+    // 返回的守卫在连接结束（被 drop）时自动减少计数
+    pub fn track_connection(&self) -> ConnectionGuard<'_> {
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard { metrics: self }
+    }
+
+    // 进入排空状态：/readyz 开始返回 503，让负载均衡器停止发送新请求
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    // 正在处理和排队等待处理的连接数；降到 0 说明关闭时可以安全退出
+    pub fn in_flight(&self) -> usize {
+        self.active_connections.load(Ordering::Relaxed) + self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    // 生成 Prometheus 文本格式
+    pub fn render(&self) -> String {
+        let stats = self.requests.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut out = String::new();
+
+        out.push_str("# HELP http_requests_total 已处理的 HTTP 请求总数\n");
+        out.push_str("# TYPE http_requests_total counter\n");
+        for ((method, route, status), count) in &stats.counts {
+            let _ = writeln!(
+                out,
+                "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape_label(method),
+                escape_label(route),
+                status,
+                count
+            );
+        }
+
+        out.push_str("# HELP http_request_duration_seconds HTTP 请求处理耗时\n");
+        out.push_str("# TYPE http_request_duration_seconds histogram\n");
+        for (route, histogram) in &stats.latency {
+            let route = escape_label(route);
+            let mut cumulative = 0;
+            for (le, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "http_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                    route, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_bucket{{route=\"{}\",le=\"+Inf\"}} {}",
+                route, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_sum{{route=\"{}\"}} {}",
+                route, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_count{{route=\"{}\"}} {}",
+                route, histogram.count
+            );
+        }
+
+        out.push_str("# HELP http_active_connections 当前打开的连接数\n");
+        out.push_str("# TYPE http_active_connections gauge\n");
+        let _ = writeln!(
+            out,
+            "http_active_connections {}",
+            self.active_connections.load(Ordering::Relaxed)
+        );
+
+        out.push_str("# HELP thread_pool_queue_depth 等待工作线程处理的连接数\n");
+        out.push_str("# TYPE thread_pool_queue_depth gauge\n");
+        let _ = writeln!(
+            out,
+            "thread_pool_queue_depth {}",
+            self.queue_depth.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+// 标签值中的反斜杠、双引号和换行需要转义
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub struct ConnectionGuard<'a> {
+    metrics: &'a Metrics,
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics
+            .active_connections
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// ========== 中间件 ==========
+
+// 记录每个请求的方法、路由模式、状态码和耗时
+pub struct RecordMetrics {
+    metrics: Arc<Metrics>,
+}
+
+impl RecordMetrics {
+    pub fn new(metrics: Arc<Metrics>) -> RecordMetrics {
+        RecordMetrics { metrics }
+    }
+}
+
+impl Middleware for RecordMetrics {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let start = Instant::now();
+        let response = next.run(request);
+
+        // 使用路由模式（/books/:isbn）而不是实际路径（/books/978-...）作为标签
+        let route = request.route.as_deref().unwrap_or(UNMATCHED_ROUTE);
+        let method = match &request.method {
+            Method::Other(_) => OTHER_METHOD,
+            method => method.as_str(),
+        };
+        self.metrics.record(
+            method,
+            route,
+            response.status.0,
+            start.elapsed().as_secs_f64(),
+        );
+        response
+    }
+}
+
+// ========== 路由 ==========
+
+// 注册监控路由
+//   GET /metrics   Prometheus 指标
+//   GET /healthz   存活检查：进程能响应就返回 200
+//   GET /readyz    就绪检查：关闭过程中返回 503
+pub fn register(router: &mut Router, metrics: Arc<Metrics>) {
+    let m = Arc::clone(&metrics);
+    router.get("/metrics", move |_| {
+        Response::text(StatusCode::OK, &m.render())
+            .with_header("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+    });
+
+    router.get("/healthz", |_| Response::text(StatusCode::OK, "ok"));
+
+    router.get("/readyz", move |_| {
+        if metrics.is_draining() {
+            Response::text(StatusCode::SERVICE_UNAVAILABLE, "draining")
+        } else {
+            Response::text(StatusCode::OK, "ready")
+        }
+    });
+}
@@ -0,0 +1,443 @@
+// projects/web/middleware.rs - 中间件管道
+// 每个中间件都可以在调用下一层之前检查/修改请求，在之后检查/修改响应，
+// 也可以直接返回响应而不调用下一层（例如 CORS 预检请求）。
+//
+//   请求 → RequestId → AccessLog → RecordMetrics → Timing → Compression
+//        → Cors → RateLimit → CatchPanic → VirtualHosts → Sessions → Router
+//   响应按相反的顺序依次经过每一层返回
+
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use super::http::{Body, Method, Request, Response, StatusCode};
+use super::router::Router;
+
+pub trait Middleware: Send + Sync {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response;
+}
+
+// 剩余的中间件链；调用 run 会把请求交给下一层
+pub struct Next<'a> {
+    middleware: &'a [Box<dyn Middleware>],
+    router: &'a Router,
+}
+
+impl Next<'_> {
+    pub fn run(self, request: &mut Request) -> Response {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle(
+                request,
+                Next {
+                    middleware: rest,
+                    router: self.router,
+                },
+            ),
+            None => self.router.handle(request),
+        }
+    }
+}
+
+// 中间件链 + 最终的路由表；先添加的中间件位于最外层
+pub struct Pipeline {
+    middleware: Vec<Box<dyn Middleware>>,
+    router: Router,
+}
+
+impl Pipeline {
+    pub fn new(router: Router) -> Pipeline {
+        Pipeline {
+            middleware: Vec::new(),
+            router,
+        }
+    }
+
+    pub fn wrap<M: Middleware + 'static>(mut self, middleware: M) -> Pipeline {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    pub fn handle(&self, request: &mut Request) -> Response {
+        Next {
+            middleware: &self.middleware,
+            router: &self.router,
+        }
+        .run(request)
+    }
+}
+
+// ========== 请求 ID ==========
+
+// 为每个请求分配 ID，写入 X-Request-Id 响应头，方便把日志和客户端报错对应起来
+// 客户端（或前置代理）已经带了合法的 X-Request-Id 时沿用它
+pub struct RequestId;
+
+impl RequestId {
+    fn is_valid(id: &str) -> bool {
+        !id.is_empty()
+            && id.len() <= 64
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+}
+
+impl Middleware for RequestId {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let id = match request.header("X-Request-Id") {
+            Some(id) if RequestId::is_valid(id) => id.to_string(),
+            _ => format!("{:016x}", rand::random::<u64>()),
+        };
+        // 写回请求头，内层的中间件和处理函数都能读到
+        request
+            .headers
+            .insert("x-request-id".to_string(), id.clone());
+
+        let mut response = next.run(request);
+        response.set_header("X-Request-Id", &id);
+        response
+    }
+}
+
+// ========== 访问日志 ==========
+
+// Common Log Format:
+//   127.0.0.1 - - [10/Oct/2025:13:55:36 +0000] "GET /books HTTP/1.1" 200 228
+pub struct AccessLog;
+
+impl Middleware for AccessLog {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let host = request
+            .peer_addr
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let request_line = match &request.query {
+            Some(query) => format!(
+                "{} {}?{} {}",
+                request.method, request.path, query, request.version
+            ),
+            None => format!("{} {} {}", request.method, request.path, request.version),
+        };
+
+        let response = next.run(request);
+
+        // 没有响应体，或者流式响应在写出之前不知道长度，按 CLF 约定都记为 "-"
+        let bytes = match &response.body {
+            Body::Bytes(body) if !body.is_empty() => body.len().to_string(),
+            _ => "-".to_string(),
+        };
+        log_info!(
+            "{} - - [{}] \"{}\" {} {}",
+            host,
+            clf_timestamp(SystemTime::now()),
+            request_line,
+            response.status.0,
+            bytes
+        );
+        response
+    }
+}
+
+// 把时间格式化为 CLF 使用的 UTC 时间：10/Oct/2025:13:55:36 +0000
+fn clf_timestamp(time: SystemTime) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+
+    format!(
+        "{:02}/{}/{}:{:02}:{:02}:{:02} +0000",
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+// 把 1970-01-01 起的天数换算成 (年, 月, 日)
+// 算法来自 Howard Hinnant 的 "chrono-Compatible Low-Level Date Algorithms"
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// ========== 请求耗时 ==========
+
+// 在 X-Response-Time 头中报告处理耗时
+// 流式响应的耗时只包含生成响应对象的时间，不包含向客户端写出数据的时间
+pub struct Timing;
+
+impl Middleware for Timing {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let start = Instant::now();
+        let mut response = next.run(request);
+        let elapsed = start.elapsed();
+        response.set_header(
+            "X-Response-Time",
+            &format!("{:.3}ms", elapsed.as_secs_f64() * 1000.0),
+        );
+        response
+    }
+}
+
+// ========== CORS ==========
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    // 允许的来源；包含 "*" 时允许任意来源
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<String>,
+    // 是否允许携带 Cookie 等凭据；此时不能返回 "*"，必须回显具体来源
+    pub allow_credentials: bool,
+    // 预检结果的缓存时间（秒）
+    pub max_age: u32,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: vec![Method::Get, Method::Post, Method::Put, Method::Delete],
+            allowed_headers: vec!["Content-Type".to_string()],
+            allow_credentials: false,
+            max_age: 600,
+        }
+    }
+}
+
+pub struct Cors {
+    config: CorsConfig,
+}
+
+impl Cors {
+    pub fn new(config: CorsConfig) -> Cors {
+        Cors { config }
+    }
+
+    // 返回应写入 Access-Control-Allow-Origin 的值，来源不被允许时返回 None
+    fn allow_origin(&self, origin: &str) -> Option<String> {
+        let any = self.config.allowed_origins.iter().any(|o| o == "*");
+        if any && !self.config.allow_credentials {
+            Some("*".to_string())
+        } else if any || self.config.allowed_origins.iter().any(|o| o == origin) {
+            Some(origin.to_string())
+        } else {
+            None
+        }
+    }
+
+    fn join_methods(&self) -> String {
+        self.config
+            .allowed_methods
+            .iter()
+            .map(|m| m.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Middleware for Cors {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let Some(origin) = request.header("Origin").map(str::to_string) else {
+            // 不是跨域请求
+            return next.run(request);
+        };
+        let allowed = self.allow_origin(&origin);
+
+        // 预检请求：OPTIONS + Access-Control-Request-Method，直接应答，不交给路由
+        if request.method == Method::Options
+            && request.header("Access-Control-Request-Method").is_some()
+        {
+            let mut response = Response::new(StatusCode::NO_CONTENT);
+            response.add_vary("Origin");
+            if let Some(allowed) = allowed {
+                response.set_header("Access-Control-Allow-Origin", &allowed);
+                response.set_header("Access-Control-Allow-Methods", &self.join_methods());
+                response.set_header(
+                    "Access-Control-Allow-Headers",
+                    &self.config.allowed_headers.join(", "),
+                );
+                response.set_header("Access-Control-Max-Age", &self.config.max_age.to_string());
+                if self.config.allow_credentials {
+                    response.set_header("Access-Control-Allow-Credentials", "true");
+                }
+            }
+            return response;
+        }
+
+        let mut response = next.run(request);
+        response.add_vary("Origin");
+        if let Some(allowed) = allowed {
+            response.set_header("Access-Control-Allow-Origin", &allowed);
+            if self.config.allow_credentials {
+                response.set_header("Access-Control-Allow-Credentials", "true");
+            }
+        }
+        response
+    }
+}
+
+// ========== panic 恢复 ==========
+
+// 捕获处理函数中的 panic，返回 500，而不是让工作线程崩溃
+pub struct CatchPanic;
+
+impl Middleware for CatchPanic {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let method = request.method.clone();
+        let path = request.path.clone();
+
+        // 处理函数 panic 后请求可能处于不一致状态，但我们之后不再使用它，因此可以断言 UnwindSafe
+        match panic::catch_unwind(AssertUnwindSafe(|| next.run(request))) {
+            Ok(response) => response,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "未知错误".to_string());
+                log_error!("❌ 处理 {} {} 时发生 panic: {}", method, path, message);
+                Response::text(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "500 Internal Server Error",
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Request {
+        Request::read_from(&mut raw.as_bytes()).unwrap().unwrap()
+    }
+
+    fn router() -> Router {
+        let mut router = Router::new();
+        router
+            .get("/ok", |req| {
+                let id = req.header("X-Request-Id").unwrap_or("-");
+                Response::text(StatusCode::OK, id)
+            })
+            .get("/panic", |_| panic!("处理函数出错"));
+        router
+    }
+
+    // 在请求和响应上各记录一次经过的顺序
+    struct Trace(&'static str);
+
+    impl Middleware for Trace {
+        fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+            let before = request.header("X-Trace").unwrap_or("").to_string();
+            request
+                .headers
+                .insert("x-trace".to_string(), format!("{}{}", before, self.0));
+            let mut response = next.run(request);
+            let after = response.header("X-Trace").unwrap_or("").to_string();
+            response.set_header("X-Trace", &format!("{}{}", after, self.0));
+            response
+        }
+    }
+
+    #[test]
+    fn first_wrapped_is_outermost() {
+        let mut router = Router::new();
+        router.get("/", |req| {
+            let trace = req.header("X-Trace").unwrap_or("").to_string();
+            Response::new(StatusCode::OK).with_header("X-Trace", &format!("{}|", trace))
+        });
+        let pipeline = Pipeline::new(router).wrap(Trace("a")).wrap(Trace("b"));
+        let response = pipeline.handle(&mut request("GET / HTTP/1.1\r\n\r\n"));
+        assert_eq!(response.header("X-Trace"), Some("ab|ba"));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let pipeline = Pipeline::new(router()).wrap(CatchPanic);
+        let response = pipeline.handle(&mut request("GET /panic HTTP/1.1\r\n\r\n"));
+        assert_eq!(response.status, StatusCode::INTERNAL_SERVER_ERROR);
+        let response = pipeline.handle(&mut request("GET /ok HTTP/1.1\r\n\r\n"));
+        assert_eq!(response.status, StatusCode::OK);
+    }
+
+    #[test]
+    fn request_id_is_reused_only_when_valid() {
+        let pipeline = Pipeline::new(router()).wrap(RequestId);
+        let response = pipeline.handle(&mut request(
+            "GET /ok HTTP/1.1\r\nX-Request-Id: abc-123\r\n\r\n",
+        ));
+        assert_eq!(response.header("X-Request-Id"), Some("abc-123"));
+
+        let response = pipeline.handle(&mut request(
+            "GET /ok HTTP/1.1\r\nX-Request-Id: <script>\r\n\r\n",
+        ));
+        let id = response.header("X-Request-Id").unwrap();
+        assert_eq!(id.len(), 16);
+        // 处理函数看到的是同一个 ID
+        let Body::Bytes(body) = &response.body else {
+            panic!("应当是定长响应体");
+        };
+        assert_eq!(body, id.as_bytes());
+    }
+
+    #[test]
+    fn answers_cors_preflight() {
+        let pipeline = Pipeline::new(router()).wrap(Cors::new(CorsConfig::default()));
+        let response = pipeline.handle(&mut request(
+            "OPTIONS /ok HTTP/1.1\r\nOrigin: http://a.test\r\n\
+             Access-Control-Request-Method: POST\r\n\r\n",
+        ));
+        assert_eq!(response.status, StatusCode::NO_CONTENT);
+        assert_eq!(response.header("Access-Control-Allow-Origin"), Some("*"));
+        assert_eq!(
+            response.header("Access-Control-Allow-Methods"),
+            Some("GET, POST, PUT, DELETE")
+        );
+
+        let response = pipeline.handle(&mut request("GET /ok HTTP/1.1\r\n\r\n"));
+        assert_eq!(response.header("Access-Control-Allow-Origin"), None);
+    }
+
+    #[test]
+    fn cors_with_credentials_echoes_allowed_origin() {
+        let config = CorsConfig {
+            allowed_origins: vec!["http://a.test".to_string()],
+            allow_credentials: true,
+            ..CorsConfig::default()
+        };
+        let pipeline = Pipeline::new(router()).wrap(Cors::new(config));
+
+        let response = pipeline.handle(&mut request(
+            "GET /ok HTTP/1.1\r\nOrigin: http://a.test\r\n\r\n",
+        ));
+        assert_eq!(
+            response.header("Access-Control-Allow-Origin"),
+            Some("http://a.test")
+        );
+        assert_eq!(
+            response.header("Access-Control-Allow-Credentials"),
+            Some("true")
+        );
+        assert_eq!(response.header("Vary"), Some("Origin"));
+
+        let response = pipeline.handle(&mut request(
+            "GET /ok HTTP/1.1\r\nOrigin: http://evil.test\r\n\r\n",
+        ));
+        assert_eq!(response.header("Access-Control-Allow-Origin"), None);
+    }
+}
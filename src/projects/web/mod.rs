@@ -0,0 +1,362 @@
+// projects/web/mod.rs - Web API 项目
+// 基于标准库实现的多线程 HTTP 服务器，通过 JSON REST API 管理图书馆
+
+#[macro_use]
+mod logging;
+
+mod books;
+// HTTP 客户端是通用的，这里只有反向代理用到其中一部分
+#[allow(dead_code)]
+mod client;
+mod compression;
+mod config;
+mod cookie;
+#[cfg(test)]
+mod e2e_tests;
+mod form;
+mod gradebook;
+mod http;
+mod metrics;
+mod middleware;
+mod proxy;
+mod rate_limit;
+mod router;
+mod server;
+mod session;
+mod shutdown;
+mod sse;
+mod static_files;
+mod template;
+mod thread_pool;
+mod upload;
+mod vhost;
+mod websocket;
+
+use std::env;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::sync::Arc;
+
+use books::{Book, Library, SharedLibrary};
+use compression::{Compression, CompressionConfig};
+use config::{Config, ParsedArgs};
+use http::{Response, StatusCode};
+use metrics::{Metrics, RecordMetrics};
+use middleware::{AccessLog, CatchPanic, Cors, CorsConfig, Pipeline, RequestId, Timing};
+use proxy::{ProxyConfig, ReverseProxy};
+use rate_limit::{RateLimit, RateLimitConfig};
+use router::Router;
+use server::{KeepAlive, Server};
+use session::{MemoryStore, SessionConfig, Sessions};
+use shutdown::Signal;
+use template::Templates;
+use thread_pool::ThreadPool;
+use vhost::VirtualHosts;
+
+fn main() {
+    println!("🚀 启动简单的 Web 服务器");
+    println!("========================");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let config = match Config::load(&args) {
+        Ok(ParsedArgs::Run(config)) => *config,
+        Ok(ParsedArgs::Help) => {
+            config::show_help();
+            return;
+        }
+        Err(e) => {
+            eprintln!("❌ 参数解析错误: {}", e);
+            config::show_help();
+            process::exit(1);
+        }
+    };
+    logging::set_level(config.log_level);
+
+    let listener = match shutdown::inherited_listener() {
+        // 由旧进程交接启动：直接使用继承的监听套接字，不需要 bind
+        Some(Ok(listener)) => {
+            log_info!("🔄 已接管旧进程的监听套接字");
+            listener
+        }
+        Some(Err(e)) => {
+            eprintln!("❌ 继承的监听套接字无效: {}", e);
+            process::exit(1);
+        }
+        None => bind(&config),
+    };
+    // 端口为 0 时由系统分配，打印实际地址
+    match listener.local_addr() {
+        Ok(addr) => println!("服务器运行在 http://{}", addr),
+        Err(e) => log_warn!("⚠️ 无法获取监听地址: {}", e),
+    }
+    log_info!(
+        "工作线程: {}，日志级别: {}",
+        config.workers,
+        config.log_level
+    );
+
+    let pool = ThreadPool::new(config.workers);
+    let metrics = Arc::new(Metrics::new(pool.queue_depth()));
+    let server = Arc::new(Server {
+        pipeline: build_pipeline(&config, seed_library(), Arc::clone(&metrics)),
+        keep_alive: KeepAlive::default(),
+        metrics,
+    });
+
+    let shutdown = match listener.local_addr().and_then(shutdown::watch) {
+        Ok(shutdown) => shutdown,
+        Err(e) => {
+            eprintln!("❌ 无法启动信号处理: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let dispatch = |stream: TcpStream| {
+        let server = Arc::clone(&server);
+        pool.execute(move || server.handle_connection(stream));
+    };
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => dispatch(stream),
+            Err(e) => log_error!("❌ 接受连接失败: {}", e),
+        }
+
+        match shutdown.take() {
+            Some(Signal::Terminate) => {
+                // 先进入排空状态再继续接收一小段时间：/readyz 返回 503，负载均衡器据此摘掉这个实例
+                server.metrics.start_draining();
+                shutdown::accept_during_grace(&listener, dispatch);
+                break;
+            }
+            Some(Signal::Restart) => match shutdown::hand_off(&listener) {
+                Ok(pid) => {
+                    log_info!("🔄 新进程 {} 已接管监听套接字，旧进程开始关闭", pid);
+                    server.metrics.start_draining();
+                    break;
+                }
+                Err(e) => log_error!("❌ 重启失败，继续运行: {}", e),
+            },
+            None => {}
+        }
+    }
+
+    // 不再接收新连接：关闭监听套接字（交接后新进程仍持有它），等待进行中的请求完成
+    drop(listener);
+    if !shutdown::drain(&server.metrics, config.shutdown_timeout) {
+        process::exit(1);
+    }
+    // 所有连接都已结束，线程池在 drop 时让工作线程退出
+    drop(pool);
+    log_info!("👋 服务器已关闭");
+}
+
+// 端口被占用等绑定失败的情况给出明确提示，而不是 panic
+fn bind(config: &Config) -> TcpListener {
+    match TcpListener::bind((config.host.as_str(), config.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("❌ 无法监听 {}:{}: {}", config.host, config.port, e);
+            if e.kind() == io::ErrorKind::AddrInUse {
+                eprintln!("💡 端口已被占用，可以用 --port 换一个端口，或用 --port 0 随机分配");
+            }
+            process::exit(1);
+        }
+    }
+}
+
+// 与 07_structs.rs 中的示例数据一致
+fn seed_library() -> SharedLibrary {
+    let mut library = Library::new(String::from("市图书馆"));
+    library.add_book(Book {
+        title: String::from("Rust程序设计语言"),
+        author: String::from("Steve Klabnik"),
+        isbn: String::from("978-7-121-31166-1"),
+    });
+    library.add_book(Book {
+        title: String::from("深入理解计算机系统"),
+        author: String::from("Randal E. Bryant"),
+        isbn: String::from("978-7-111-54493-7"),
+    });
+    SharedLibrary::new(library)
+}
+
+fn build_pipeline(config: &Config, library: SharedLibrary, metrics: Arc<Metrics>) -> Pipeline {
+    let mut router = Router::new();
+    router.get("/", |_| {
+        Response::text(StatusCode::OK, "Hello from Rust! 试试 GET /books")
+    });
+    // 演示 CatchPanic：处理函数里的 unwrap() 失败只会得到 500，服务器继续运行
+    // 只在开发模式下注册，生产环境不应该有能让人随意触发 panic 的路由
+    if config.dev {
+        router.get("/debug/panic", |req| {
+            let token = req.header("X-Debug-Token").unwrap();
+            Response::text(StatusCode::OK, token)
+        });
+    }
+    if !config.templates.is_dir() {
+        log_warn!(
+            "⚠️ 模板目录 {} 不存在，HTML 页面将无法显示",
+            config.templates.display()
+        );
+    }
+    let templates = Arc::new(Templates::new(config.templates.clone(), config.dev));
+
+    books::register(&mut router, library, Arc::clone(&templates));
+    websocket::register(&mut router, config.workers, Arc::clone(&templates));
+    sse::register(
+        &mut router,
+        config.workers,
+        Arc::clone(&metrics),
+        Arc::clone(&templates),
+    );
+    upload::register(
+        &mut router,
+        config.upload_dir.clone(),
+        Arc::clone(&templates),
+    );
+    gradebook::register(&mut router, config.gradebook.clone(), templates);
+    metrics::register(&mut router, Arc::clone(&metrics));
+    if let Some(root) = &config.static_root {
+        static_files::register(&mut router, root.clone());
+    }
+
+    Pipeline::new(router)
+        .wrap(RequestId)
+        .wrap(AccessLog)
+        .wrap(RecordMetrics::new(metrics))
+        .wrap(Timing)
+        .wrap(Compression::new(CompressionConfig::default()))
+        .wrap(Cors::new(CorsConfig::default()))
+        .wrap(RateLimit::new(RateLimitConfig {
+            requests_per_second: config.rate_limit_rps,
+            burst: config.rate_limit_burst,
+            ..RateLimitConfig::default()
+        }))
+        .wrap(CatchPanic)
+        // 虚拟主机放在会话外层：转发给上游的请求不读取本地会话，响应也不会带上本地的 Set-Cookie
+        .wrap(virtual_hosts(config))
+        .wrap(sessions(config))
+}
+
+// 按 Host 头转发到上游服务器；其他请求由本服务器的路由表处理
+fn virtual_hosts(config: &Config) -> VirtualHosts {
+    let proxy_config = ProxyConfig {
+        timeout: config.proxy_timeout,
+        ..ProxyConfig::default()
+    };
+    let mut vhosts = VirtualHosts::new();
+    for vhost in &config.vhosts {
+        log_info!(
+            "🔀 虚拟主机 {} → {}",
+            vhost.host,
+            vhost.upstreams.join(", ")
+        );
+        vhosts.add(
+            &vhost.host,
+            ReverseProxy::new(&vhost.upstreams, proxy_config),
+        );
+    }
+    vhosts
+}
+
+// 会话中间件；没有配置密钥时随机生成一个，重启后旧的会话 Cookie 都会失效
+fn sessions(config: &Config) -> Sessions {
+    let secret = match &config.session_secret {
+        Some(secret) => secret.as_bytes().to_vec(),
+        None => {
+            log_warn!("⚠️ 没有设置会话密钥 (--session-secret)，使用随机密钥");
+            rand::random::<[u8; 32]>().to_vec()
+        }
+    };
+    let session_config = SessionConfig {
+        secure: config.secure_cookies,
+        same_site: config.same_site,
+        ..SessionConfig::default()
+    };
+    let store = MemoryStore::new(session_config.idle_timeout);
+    Sessions::new(session_config, Box::new(store), &secret)
+}
+
+/*
+🎯 项目说明：
+
+这个 Web 服务器项目只使用标准库，综合了多个 Rust 概念：
+
+1. **模块化设计**：
+   - config.rs：命令行参数与环境变量配置
+   - logging.rs：全局日志级别与日志宏
+   - http.rs：请求解析与响应构建（含 chunked 编码）
+   - server.rs：持久连接 (keep-alive) 处理
+   - shutdown.rs：收到 SIGINT/SIGTERM 时优雅关闭，SIGUSR2 时把监听套接字交给新进程
+   - middleware.rs：中间件管道（访问日志、耗时、CORS、请求 ID、panic 恢复）
+   - rate_limit.rs：按客户端 IP 的令牌桶限流
+   - compression.rs：按 Accept-Encoding 协商 gzip/deflate 压缩
+   - metrics.rs：Prometheus 指标与 /healthz、/readyz 健康检查
+   - static_files.rs：静态文件服务（防止路径穿越）
+   - router.rs：路由表与路径参数
+   - thread_pool.rs：固定大小的线程池
+   - books.rs：图书馆 REST API
+   - websocket.rs：WebSocket 握手、帧解析，以及回显和聊天室示例
+   - form.rs：urlencoded 与 multipart/form-data 表单解析
+   - upload.rs：练习提交（文件上传、文件名清理、大小限制）
+   - template.rs：HTML 模板引擎（变量转义、if/for、include、继承、热重载）
+   - gradebook.rs：成绩册页面，读取练习程序保存的学习进度
+   - sse.rs：Server-Sent Events 事件流，推送 08_enums.rs 中的事件枚举
+   - cookie.rs：Cookie 解析与 Set-Cookie 生成
+   - session.rs：签名会话 ID、SessionStore trait 与空闲过期的内存存储
+   - vhost.rs：按 Host 头区分的虚拟主机
+   - proxy.rs：反向代理（轮询负载均衡、被动健康检查、X-Forwarded-For）
+   - client.rs：HTTP/1.1 客户端，反向代理用它访问上游服务器
+
+2. **并发与共享状态**：
+   - 线程池通过 mpsc 通道分发任务
+   - Arc<RwLock<Library>> 在所有连接间共享数据
+   - 聊天室的广播线程通过 mpsc 通道接收所有连接的消息
+   - SSE 事件源保留最近的事件，断线重连时按 Last-Event-ID 补发
+   - 会话通过 Mutex 提供内部可变性，处理函数只需要 &Request 就能写入
+
+3. **错误处理**：
+   - HttpError 枚举描述解析错误
+   - 校验失败返回结构化的 422 JSON 响应
+
+4. **Trait 对象与闭包**：
+   - 处理函数保存为 Box<dyn Fn(&Request) -> Response + Send + Sync>
+   - 中间件通过 Middleware trait 组合成 Vec<Box<dyn Middleware>>
+   - 会话存储保存为 Box<dyn SessionStore>，可以替换为其他实现
+   - 虚拟主机的站点是 Box<dyn Site>：路由表和反向代理都实现了 Site
+
+💡 试一试：
+  cargo run --bin project_web -- --help
+  cargo run --bin project_web -- --port 0 --static-root docs
+  curl http://127.0.0.1:7878/books
+  浏览器打开 http://127.0.0.1:7878/library          # 模板渲染的书籍列表，可用表单添加书籍
+  curl -F exercise=ex1 -F file=@src/00_hello.rs http://127.0.0.1:7878/upload
+  curl -c jar -b jar -F file=@src/00_hello.rs http://127.0.0.1:7878/upload   # 会话记录本人的提交
+  cargo run --bin project_web -- --gradebook grades   # grades/<学习者>/progress.json，浏览器打开 /gradebook
+  curl --raw http://127.0.0.1:7878/books.csv          # chunked 流式响应
+  curl -X POST http://127.0.0.1:7878/books \
+       -H 'Content-Type: application/json' \
+       -d '{"title":"Rust 实战","author":"Tim McNamara","isbn":"978-7-115-59198-1"}'
+  curl http://127.0.0.1:7878/books/978-7-115-59198-1
+  curl -X PUT http://127.0.0.1:7878/books/978-7-115-59198-1 \
+       -H 'Content-Type: application/json' \
+       -d '{"title":"Rust 实战（第2版）","author":"Tim McNamara"}'
+  curl -X DELETE http://127.0.0.1:7878/books/978-7-115-59198-1
+  curl http://127.0.0.1:7878/metrics                  # Prometheus 指标
+  浏览器打开 http://127.0.0.1:7878/chat             # WebSocket 聊天室
+  浏览器打开 http://127.0.0.1:7878/feed             # SSE 实时事件
+  curl -N http://127.0.0.1:7878/events -H 'Last-Event-ID: 3'
+  cargo run --bin project_web -- --vhost docs.localhost=127.0.0.1:8001,127.0.0.1:8002
+  curl -H 'Host: docs.localhost' http://127.0.0.1:7878/    # 转发到上游服务器
+  kill -USR2 $(pgrep project_web)                      # 无中断重启（例如重新编译之后）
+  curl -i http://127.0.0.1:7878/debug/panic           # 500，服务器不会退出（需要 --dev）
+  curl -i -X OPTIONS http://127.0.0.1:7878/books \
+       -H 'Origin: http://example.com' -H 'Access-Control-Request-Method: POST'
+
+🔗 相关概念：
+- 结构体和方法 (07_structs.rs)
+- 错误处理 (10_errors.rs)
+- 泛型和 Trait (11_generics.rs)
+- 并发编程 (14_async.rs)
+*/
@@ -0,0 +1,262 @@
+// projects/web/proxy.rs - 反向代理
+// 把请求转发给一组上游服务器，再把上游的响应返回给客户端：
+//
+//   浏览器 → project_web (:7878) → 127.0.0.1:8001
+//                                → 127.0.0.1:8002   轮询 (round-robin) 分配
+//
+// - 转发时去掉逐跳 (hop-by-hop) 头部，添加 X-Forwarded-For / -Host / -Proto
+// - 被动健康检查：上游连续失败 max_fails 次后，在 fail_timeout 内不再分配请求，
+//   之后自动恢复；不需要额外的探测请求
+// - 连接被拒绝时请求还没有发出去，可以安全地换下一个上游重试；
+//   超时或响应无效时请求可能已经被处理，不重试（POST 重放可能造成重复提交）
+//
+// 上游的响应会完整读入内存后再返回，所以不能代理 SSE、WebSocket 这类长连接
+
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::client::{Client, ClientConfig, ClientError, ClientResponse};
+use super::http::{HttpError, Request, Response, StatusCode};
+use super::vhost::{Site, request_host};
+
+// 逐跳头部只对一条连接有效，代理不能转发
+const HOP_BY_HOP: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "upgrade",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProxyConfig {
+    // 连接、发送和等待上游响应各自的超时时间
+    pub timeout: Duration,
+    // 连续失败多少次后认为上游不可用
+    pub max_fails: u32,
+    // 不可用的上游多久之后再尝试
+    pub fail_timeout: Duration,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        ProxyConfig {
+            timeout: Duration::from_secs(30),
+            max_fails: 1,
+            fail_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    fails: u32,
+    down_until: Option<Instant>,
+}
+
+struct Upstream {
+    // "主机:端口"
+    addr: String,
+    health: Mutex<Health>,
+}
+
+impl Upstream {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Health> {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        self.lock().down_until.is_none_or(|until| now >= until)
+    }
+
+    fn succeeded(&self) {
+        let mut health = self.lock();
+        if health.down_until.is_some() {
+            log_info!("✅ 上游 {} 已恢复", self.addr);
+        }
+        *health = Health::default();
+    }
+
+    fn failed(&self, config: &ProxyConfig) {
+        let mut health = self.lock();
+        health.fails += 1;
+        if health.fails >= config.max_fails {
+            log_warn!(
+                "⚠️ 上游 {} 连续失败 {} 次，{} 秒内不再使用",
+                self.addr,
+                health.fails,
+                config.fail_timeout.as_secs()
+            );
+            health.down_until = Some(Instant::now() + config.fail_timeout);
+        }
+    }
+}
+
+pub struct ReverseProxy {
+    upstreams: Vec<Upstream>,
+    // 轮询计数器
+    next: AtomicUsize,
+    config: ProxyConfig,
+    client: Client,
+}
+
+impl ReverseProxy {
+    pub fn new(upstreams: &[String], config: ProxyConfig) -> ReverseProxy {
+        assert!(!upstreams.is_empty(), "至少需要一个上游服务器");
+        ReverseProxy {
+            upstreams: upstreams
+                .iter()
+                .map(|addr| Upstream {
+                    addr: addr.clone(),
+                    health: Mutex::new(Health::default()),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            config,
+            // 重定向要原样返回给浏览器，不能由代理跟随
+            client: Client::new(ClientConfig {
+                timeout: config.timeout,
+                max_redirects: 0,
+                ..ClientConfig::default()
+            }),
+        }
+    }
+
+    // 本次请求依次尝试的上游：从轮询位置开始，跳过不可用的
+    fn candidates(&self) -> Vec<&Upstream> {
+        let now = Instant::now();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let n = self.upstreams.len();
+        (0..n)
+            .map(|i| &self.upstreams[(start + i) % n])
+            .filter(|upstream| upstream.is_available(now))
+            .collect()
+    }
+
+    fn forward(
+        &self,
+        request: &Request,
+        body: &[u8],
+        upstream: &Upstream,
+    ) -> Result<ClientResponse, ClientError> {
+        let target = match &request.query {
+            Some(query) => format!("{}?{}", request.path, query),
+            None => request.path.clone(),
+        };
+        let url = format!("http://{}{}", upstream.addr, target);
+        let mut builder = self.client.request(request.method.as_str(), &url);
+
+        // Connection 头中列出的头部也是逐跳的
+        let connection = request
+            .header("Connection")
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        for (name, value) in &request.headers {
+            let skip = is_hop_by_hop(name)
+                || connection.split(',').any(|token| token.trim() == name)
+                // 客户端根据上游地址和请求体自己生成这些头部
+                || matches!(name.as_str(), "host" | "content-length" | "transfer-encoding");
+            if !skip {
+                builder = builder.header(name, value);
+            }
+        }
+
+        // 上游看到的客户端地址是代理，真实地址通过 X-Forwarded-For 传递；
+        // 前面还有代理时追加在已有值的后面
+        if let Some(peer) = request.peer_addr {
+            let forwarded_for = match request.header("X-Forwarded-For") {
+                Some(existing) => format!("{}, {}", existing, peer.ip()),
+                None => peer.ip().to_string(),
+            };
+            builder = builder.header("X-Forwarded-For", &forwarded_for);
+        }
+        if let Some(host) = request.header("Host") {
+            builder = builder.header("X-Forwarded-Host", host);
+        }
+        builder = builder.header("X-Forwarded-Proto", "http");
+
+        if !body.is_empty() {
+            let content_type = request
+                .header("Content-Type")
+                .unwrap_or("application/octet-stream");
+            builder = builder.body(content_type, body);
+        }
+        builder.send()
+    }
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP.iter().any(|h| name.eq_ignore_ascii_case(h))
+}
+
+// 完整的请求体；multipart 请求的请求体在临时文件中
+fn request_body(request: &Request) -> io::Result<Vec<u8>> {
+    let Some(upload) = &request.upload else {
+        return Ok(request.body.clone());
+    };
+    let mut file = upload.file();
+    file.seek(SeekFrom::Start(0))?;
+    let mut body = Vec::new();
+    file.read_to_end(&mut body)?;
+    Ok(body)
+}
+
+// 把上游响应转换为返回给客户端的响应
+fn to_response(upstream: ClientResponse) -> Response {
+    let mut response = Response::new(StatusCode(upstream.status)).with_body(upstream.body);
+    for (name, value) in &upstream.headers {
+        // 响应体已经完整读出（chunked 也已解码），长度由服务器重新计算
+        let skip = is_hop_by_hop(name)
+            || name.eq_ignore_ascii_case("content-length")
+            || name.eq_ignore_ascii_case("transfer-encoding");
+        if !skip {
+            response.append_header(name, value);
+        }
+    }
+    response
+}
+
+// 连接被拒绝：请求还没有发出去，可以换一个上游重试
+fn is_refused(error: &ClientError) -> bool {
+    matches!(error, ClientError::Io(e) if e.kind() == io::ErrorKind::ConnectionRefused)
+}
+
+impl Site for ReverseProxy {
+    fn handle(&self, request: &mut Request) -> Response {
+        let body = match request_body(request) {
+            Ok(body) => body,
+            Err(e) => return HttpError::from(e).to_response(),
+        };
+        let host = request_host(request).unwrap_or_default();
+
+        for upstream in self.candidates() {
+            match self.forward(request, &body, upstream) {
+                Ok(response) => {
+                    upstream.succeeded();
+                    return to_response(response);
+                }
+                Err(e) => {
+                    log_warn!("⚠️ 转发 {} 到上游 {} 失败: {}", host, upstream.addr, e);
+                    upstream.failed(&self.config);
+                    if is_refused(&e) {
+                        continue;
+                    }
+                    return match e {
+                        ClientError::Timeout => {
+                            Response::text(StatusCode::GATEWAY_TIMEOUT, "上游服务器响应超时")
+                        }
+                        _ => Response::text(StatusCode::BAD_GATEWAY, "上游服务器响应无效"),
+                    };
+                }
+            }
+        }
+
+        log_error!("❌ {} 没有可用的上游服务器", host);
+        Response::text(StatusCode::BAD_GATEWAY, "没有可用的上游服务器")
+    }
+}
@@ -0,0 +1,121 @@
+// projects/web/rate_limit.rs - 按客户端 IP 限流
+// 令牌桶算法：每个 IP 一个桶，桶里最多 burst 个令牌，每秒补充 requests_per_second 个；
+// 每个请求消耗一个令牌，桶空时返回 429 Too Many Requests
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use super::http::{Request, Response, StatusCode};
+use super::middleware::{Middleware, Next};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    // 长期平均速率
+    pub requests_per_second: f64,
+    // 允许的突发请求数（桶容量）
+    pub burst: u32,
+    // 清理空闲桶的间隔
+    pub cleanup_interval: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            requests_per_second: 10.0,
+            burst: 20,
+            cleanup_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+struct Buckets {
+    by_ip: HashMap<IpAddr, Bucket>,
+    last_cleanup: Instant,
+}
+
+pub struct RateLimit {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+// 一次检查的结果：放行，或者需要等待多久才会有新令牌
+enum Decision {
+    Allow,
+    Reject { retry_after: Duration },
+}
+
+impl RateLimit {
+    pub fn new(config: RateLimitConfig) -> RateLimit {
+        assert!(config.requests_per_second > 0.0, "限流速率必须大于 0");
+        assert!(config.burst > 0, "突发容量必须大于 0");
+
+        RateLimit {
+            config,
+            buckets: Mutex::new(Buckets {
+                by_ip: HashMap::new(),
+                last_cleanup: Instant::now(),
+            }),
+        }
+    }
+
+    fn check(&self, ip: IpAddr, now: Instant) -> Decision {
+        let rate = self.config.requests_per_second;
+        let capacity = f64::from(self.config.burst);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // 定期清理：空闲到已经补满的桶和新建的桶没有区别，删掉不会改变限流结果，
+        // 这样内存占用只和最近活跃的客户端数量有关
+        if now.duration_since(buckets.last_cleanup) >= self.config.cleanup_interval {
+            let refill_time = Duration::from_secs_f64(capacity / rate);
+            buckets
+                .by_ip
+                .retain(|_, bucket| now.duration_since(bucket.last_refill) < refill_time);
+            buckets.last_cleanup = now;
+        }
+
+        let bucket = buckets.by_ip.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Decision::Allow
+        } else {
+            Decision::Reject {
+                retry_after: Duration::from_secs_f64((1.0 - bucket.tokens) / rate),
+            }
+        }
+    }
+}
+
+impl Middleware for RateLimit {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let Some(addr) = request.peer_addr else {
+            return next.run(request);
+        };
+
+        match self.check(addr.ip(), Instant::now()) {
+            Decision::Allow => next.run(request),
+            Decision::Reject { retry_after } => {
+                // Retry-After 只能是整数秒，向上取整，至少 1 秒
+                let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+                Response::text(StatusCode::TOO_MANY_REQUESTS, "429 Too Many Requests")
+                    .with_header("Retry-After", &secs.to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// projects/web/router.rs - 路由表
+// 按 "方法 + 路径模式" 把请求分发给处理函数，支持 /books/:isbn 形式的路径参数，
+// 以及 /static/*path 形式的通配参数（匹配剩余的全部路径，只能放在最后）
+
+use super::http::{Method, Request, Response, StatusCode};
+
+// 处理函数：线程池中的多个线程会同时调用，因此要求 Send + Sync
+pub type Handler = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+
+enum Segment {
+    Literal(String),
+    Param(String),
+    Wildcard(String),
+}
+
+struct Route {
+    method: Method,
+    pattern: String,
+    segments: Vec<Segment>,
+    handler: Handler,
+}
+
+impl Route {
+    // 路径匹配成功时返回提取出的参数
+    fn match_path(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let parts: Vec<&str> = split_path(path).collect();
+        let has_wildcard = matches!(self.segments.last(), Some(Segment::Wildcard(_)));
+        if parts.len() != self.segments.len() && !has_wildcard {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(lit) if parts.get(i) == Some(&lit.as_str()) => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) => params.push((name.clone(), parts.get(i)?.to_string())),
+                Segment::Wildcard(name) => {
+                    // 通配参数至少要匹配一段
+                    if i >= parts.len() {
+                        return None;
+                    }
+                    params.push((name.clone(), parts[i..].join("/")));
+                }
+            }
+        }
+        Some(params)
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router { routes: Vec::new() }
+    }
+
+    pub fn route<F>(&mut self, method: Method, pattern: &str, handler: F) -> &mut Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        let segments = split_path(pattern)
+            .map(|s| {
+                if let Some(name) = s.strip_prefix(':') {
+                    Segment::Param(name.to_string())
+                } else if let Some(name) = s.strip_prefix('*') {
+                    Segment::Wildcard(name.to_string())
+                } else {
+                    Segment::Literal(s.to_string())
+                }
+            })
+            .collect();
+
+        self.routes.push(Route {
+            method,
+            pattern: pattern.to_string(),
+            segments,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    pub fn get<F>(&mut self, pattern: &str, handler: F) -> &mut Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Get, pattern, handler)
+    }
+
+    pub fn post<F>(&mut self, pattern: &str, handler: F) -> &mut Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Post, pattern, handler)
+    }
+
+    pub fn put<F>(&mut self, pattern: &str, handler: F) -> &mut Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Put, pattern, handler)
+    }
+
+    pub fn delete<F>(&mut self, pattern: &str, handler: F) -> &mut Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Delete, pattern, handler)
+    }
+
+    // 分发请求：
+    // - 路径和方法都匹配：调用处理函数
+    // - 路径匹配但方法不匹配：405，并在 Allow 头中列出支持的方法
+    // - 路径不匹配：404
+    pub fn handle(&self, request: &mut Request) -> Response {
+        let mut allowed = Vec::new();
+
+        for route in &self.routes {
+            let Some(params) = route.match_path(&request.path) else {
+                continue;
+            };
+            if route.method != request.method {
+                allowed.push(route.method.as_str().to_string());
+                continue;
+            }
+
+            request.params = params.into_iter().collect();
+            request.route = Some(route.pattern.clone());
+            return (route.handler)(request);
+        }
+
+        if allowed.is_empty() {
+            Response::text(StatusCode::NOT_FOUND, "404 Not Found")
+        } else {
+            Response::text(StatusCode::METHOD_NOT_ALLOWED, "405 Method Not Allowed")
+                .with_header("Allow", &allowed.join(", "))
+        }
+    }
+}
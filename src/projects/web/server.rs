@@ -0,0 +1,133 @@
+// projects/web/server.rs - 连接处理
+// 支持 HTTP/1.1 持久连接 (keep-alive)：一个 TCP 连接上可以依次处理多个请求
+
+use std::io::{self, BufReader};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use super::http::{Body, HttpError, Method, Request, Upgraded};
+use super::metrics::Metrics;
+use super::middleware::Pipeline;
+
+// 写出响应的超时时间
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+// 持久连接的限制
+#[derive(Debug, Clone, Copy)]
+pub struct KeepAlive {
+    // 两个请求之间允许的最长空闲时间，超时后关闭连接
+    pub idle_timeout: Duration,
+    // 单个连接最多处理的请求数，达到后关闭连接，避免某个客户端长期占用工作线程
+    pub max_requests: usize,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        KeepAlive {
+            idle_timeout: Duration::from_secs(5),
+            max_requests: 100,
+        }
+    }
+}
+
+// 所有工作线程共享的服务器状态
+pub struct Server {
+    pub pipeline: Pipeline,
+    pub keep_alive: KeepAlive,
+    pub metrics: Arc<Metrics>,
+}
+
+impl Server {
+    pub fn handle_connection(&self, stream: TcpStream) {
+        let _guard = self.metrics.track_connection();
+        let peer = stream
+            .peer_addr()
+            .map_or_else(|_| "-".to_string(), |addr| addr.to_string());
+        log_debug!("🔌 新连接: {}", peer);
+
+        if let Err(e) = serve(&stream, self) {
+            log_error!("❌ 连接处理失败: {}", e);
+        }
+        log_debug!("🔌 连接关闭: {}", peer);
+    }
+}
+
+fn serve(stream: &TcpStream, server: &Server) -> io::Result<()> {
+    let keep_alive = server.keep_alive;
+    stream.set_read_timeout(Some(keep_alive.idle_timeout))?;
+    // 客户端一直不读取响应时，写操作会在发送缓冲区满后阻塞，同样会占住工作线程
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let peer_addr = stream.peer_addr().ok();
+    // 同一个 BufReader 要在多个请求间复用，否则客户端流水线发送的数据会丢失
+    let mut reader = BufReader::new(stream);
+    let mut served = 0;
+
+    loop {
+        let mut request = match Request::read_from(&mut reader) {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()), // 客户端关闭了连接
+            Err(HttpError::Io(e)) if is_timeout(&e) => return Ok(()), // 空闲超时
+            Err(HttpError::Io(e)) => return Err(e),
+            Err(e) => {
+                // 请求格式错误时无法确定下一个请求从哪里开始，只能关闭连接
+                let mut response = e.to_response();
+                response.set_header("Connection", "close");
+                return response.write_to(&mut &*stream, false, false);
+            }
+        };
+        served += 1;
+        request.peer_addr = peer_addr;
+
+        let mut response = server.pipeline.handle(&mut request);
+
+        // 协议升级：写出 101 响应后把连接交给新线程，当前工作线程回到线程池
+        // 长连接协议（如 WebSocket）如果一直占着工作线程，少量客户端就能占满线程池
+        if let Some(on_upgrade) = response.take_upgrade() {
+            response.write_to(&mut &*stream, false, false)?;
+            let upgraded = Upgraded::new(reader.buffer().to_vec(), stream.try_clone()?);
+            thread::spawn(move || on_upgrade(upgraded));
+            return Ok(());
+        }
+
+        // HTTP/1.0 客户端不理解 chunked 编码，流式响应只能靠关闭连接来标记结束
+        let chunked = request.version != "HTTP/1.0";
+        let streaming_without_length = matches!(response.body, Body::Stream(_)) && !chunked;
+        // 服务器正在关闭时，处理完当前请求就关闭连接，客户端会在新连接上重试
+        let keep_open = request.wants_keep_alive()
+            && served < keep_alive.max_requests
+            && !streaming_without_length
+            && !server.metrics.is_draining();
+
+        if keep_open {
+            response.set_header("Connection", "keep-alive");
+            response.set_header(
+                "Keep-Alive",
+                &format!(
+                    "timeout={}, max={}",
+                    keep_alive.idle_timeout.as_secs(),
+                    keep_alive.max_requests - served
+                ),
+            );
+        } else {
+            response.set_header("Connection", "close");
+        }
+
+        let head = request.method == Method::Head;
+        response.write_to(&mut &*stream, chunked, head)?;
+
+        if !keep_open {
+            return Ok(());
+        }
+    }
+}
+
+// 读超时在不同平台上表现为 WouldBlock 或 TimedOut
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
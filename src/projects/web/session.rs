@@ -0,0 +1,447 @@
+// projects/web/session.rs - 基于 Cookie 的会话
+// 会话数据保存在服务器端，浏览器只保存一个会话 ID：
+//
+//   Set-Cookie: sid=<会话 ID>.<签名>; Path=/; Max-Age=1800; HttpOnly; SameSite=Lax
+//
+// 签名是用服务器密钥对 ID 计算的 HMAC-SHA1，没有密钥就无法伪造出有效的 Cookie；
+// 签名不对的 Cookie 直接当作没有会话，不会去存储中查找
+//
+// 会话在一段时间没有访问后过期；处理函数没有写入任何数据时不会创建会话，也不会发送 Cookie
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+
+use super::cookie::{self, Cookie, SameSite};
+use super::http::{Request, Response};
+use super::middleware::{Middleware, Next};
+
+pub type SessionData = HashMap<String, Value>;
+
+// ========== 存储 ==========
+
+// 会话存储：内存、文件、数据库都可以实现这个 trait
+pub trait SessionStore: Send + Sync {
+    // 读取会话；不存在或已过期时返回 None
+    fn load(&self, id: &str) -> Option<SessionData>;
+    fn save(&self, id: &str, data: SessionData);
+    fn remove(&self, id: &str);
+}
+
+struct Entry {
+    data: SessionData,
+    last_access: Instant,
+}
+
+struct Entries {
+    by_id: HashMap<String, Entry>,
+    last_cleanup: Instant,
+}
+
+// 内存存储：服务器重启后会话全部丢失
+pub struct MemoryStore {
+    idle_timeout: Duration,
+    cleanup_interval: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl MemoryStore {
+    pub fn new(idle_timeout: Duration) -> MemoryStore {
+        MemoryStore {
+            idle_timeout,
+            cleanup_interval: Duration::from_secs(60),
+            entries: Mutex::new(Entries {
+                by_id: HashMap::new(),
+                last_cleanup: Instant::now(),
+            }),
+        }
+    }
+
+    // 取得锁，顺便定期删除过期的会话（与 rate_limit.rs 清理空闲桶的方式相同）
+    fn lock(&self, now: Instant) -> MutexGuard<'_, Entries> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if now.duration_since(entries.last_cleanup) >= self.cleanup_interval {
+            let idle_timeout = self.idle_timeout;
+            entries
+                .by_id
+                .retain(|_, entry| now.duration_since(entry.last_access) < idle_timeout);
+            entries.last_cleanup = now;
+        }
+        entries
+    }
+}
+
+impl SessionStore for MemoryStore {
+    fn load(&self, id: &str) -> Option<SessionData> {
+        let now = Instant::now();
+        let mut entries = self.lock(now);
+        let entry = entries.by_id.get_mut(id)?;
+        if now.duration_since(entry.last_access) >= self.idle_timeout {
+            entries.by_id.remove(id);
+            return None;
+        }
+        // 每次访问都重新计算空闲时间
+        entry.last_access = now;
+        Some(entry.data.clone())
+    }
+
+    fn save(&self, id: &str, data: SessionData) {
+        let now = Instant::now();
+        self.lock(now).by_id.insert(
+            id.to_string(),
+            Entry {
+                data,
+                last_access: now,
+            },
+        );
+    }
+
+    fn remove(&self, id: &str) {
+        self.lock(Instant::now()).by_id.remove(id);
+    }
+}
+
+// ========== 签名 ==========
+
+// HMAC（RFC 2104）：H((K ^ opad) || H((K ^ ipad) || message))
+// 直接用 H(密钥 || 消息) 会受到长度扩展攻击，所以要用 HMAC
+fn hmac_sha1(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+
+    // 比分组长的密钥先哈希，不足的部分补 0
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let digest = Sha1::digest(key);
+        block[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha1::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha1::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
+
+// 比较所用的时间与第一个不同字节的位置无关，攻击者无法通过测量响应时间逐字节猜出签名
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+struct Signer {
+    key: Vec<u8>,
+}
+
+impl Signer {
+    fn sign(&self, id: &str) -> String {
+        format!(
+            "{}.{}",
+            id,
+            BASE64.encode(hmac_sha1(&self.key, id.as_bytes()))
+        )
+    }
+
+    // 签名正确时返回其中的会话 ID
+    fn verify<'a>(&self, value: &'a str) -> Option<&'a str> {
+        let (id, signature) = value.split_once('.')?;
+        let signature = BASE64.decode(signature).ok()?;
+        constant_time_eq(&signature, &hmac_sha1(&self.key, id.as_bytes())).then_some(id)
+    }
+}
+
+// 128 位随机数，十六进制表示
+fn new_id() -> String {
+    rand::random::<[u8; 16]>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// ========== 会话 ==========
+
+#[derive(Debug)]
+struct SessionState {
+    // 新会话在第一次写入后才分配 ID
+    id: Option<String>,
+    data: SessionData,
+    changed: bool,
+    destroyed: bool,
+}
+
+// 处理函数通过 request.session 访问当前会话；
+// 处理函数只拿到 &Request，所以用 Mutex 提供内部可变性
+#[derive(Debug)]
+pub struct Session {
+    state: Mutex<SessionState>,
+}
+
+impl Session {
+    fn new(id: Option<String>, data: SessionData) -> Session {
+        Session {
+            state: Mutex::new(SessionState {
+                id,
+                data,
+                changed: false,
+                destroyed: false,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SessionState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let state = self.lock();
+        serde_json::from_value(state.data.get(key)?.clone()).ok()
+    }
+
+    pub fn insert<T: Serialize>(&self, key: &str, value: &T) {
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                let mut state = self.lock();
+                state.data.insert(key.to_string(), value);
+                state.changed = true;
+            }
+            Err(e) => log_error!("❌ 会话数据序列化失败: {}", e),
+        }
+    }
+
+    // 删除整个会话，浏览器中的 Cookie 也会被删除
+    pub fn destroy(&self) {
+        let mut state = self.lock();
+        state.data.clear();
+        state.destroyed = true;
+    }
+}
+
+// ========== 中间件 ==========
+
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    pub cookie_name: String,
+    // 多久没有访问后过期
+    pub idle_timeout: Duration,
+    // 只在 HTTPS 下发送 Cookie；这个服务器只支持 HTTP，所以默认关闭
+    pub secure: bool,
+    pub same_site: SameSite,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        SessionConfig {
+            cookie_name: "sid".to_string(),
+            idle_timeout: Duration::from_secs(30 * 60),
+            secure: false,
+            same_site: SameSite::Lax,
+        }
+    }
+}
+
+pub struct Sessions {
+    config: SessionConfig,
+    store: Box<dyn SessionStore>,
+    signer: Signer,
+}
+
+impl Sessions {
+    pub fn new(config: SessionConfig, store: Box<dyn SessionStore>, secret: &[u8]) -> Sessions {
+        assert!(!secret.is_empty(), "会话密钥不能为空");
+        Sessions {
+            config,
+            store,
+            signer: Signer {
+                key: secret.to_vec(),
+            },
+        }
+    }
+
+    // 从 Cookie 中取出签名正确、并且在存储中仍然有效的会话
+    fn load(&self, request: &Request) -> Option<(String, SessionData)> {
+        let value = cookie::get(request, &self.config.cookie_name)?;
+        let Some(id) = self.signer.verify(&value) else {
+            log_debug!("🍪 会话 Cookie 签名无效，忽略");
+            return None;
+        };
+        let data = self.store.load(id)?;
+        Some((id.to_string(), data))
+    }
+
+    fn cookie(&self, value: &str) -> Cookie {
+        let cookie = Cookie::new(&self.config.cookie_name, value)
+            .with_path("/")
+            .with_max_age(self.config.idle_timeout)
+            .http_only()
+            .with_same_site(self.config.same_site);
+        if self.config.secure {
+            cookie.secure()
+        } else {
+            cookie
+        }
+    }
+}
+
+impl Middleware for Sessions {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let had_cookie = cookie::get(request, &self.config.cookie_name).is_some();
+        let (id, data) = match self.load(request) {
+            Some((id, data)) => (Some(id), data),
+            None => (None, SessionData::new()),
+        };
+        request.session = Some(Session::new(id, data));
+
+        let mut response = next.run(request);
+
+        let Some(session) = request.session.take() else {
+            return response;
+        };
+        let state = session
+            .state
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if state.destroyed {
+            if let Some(id) = &state.id {
+                self.store.remove(id);
+            }
+            if had_cookie {
+                cookie::set(
+                    &mut response,
+                    &Cookie::removal(&self.config.cookie_name, "/"),
+                );
+            }
+            return response;
+        }
+        // 没有会话、处理函数也没有写入数据：不创建空会话
+        if state.id.is_none() && !state.changed {
+            return response;
+        }
+
+        let id = state.id.unwrap_or_else(new_id);
+        if state.changed {
+            self.store.save(&id, state.data);
+        }
+        // 每次响应都刷新 Max-Age，让浏览器中的过期时间与服务器端一致
+        cookie::set(&mut response, &self.cookie(&self.signer.sign(&id)));
+        // 响应内容与 Cookie 相关，缓存时要区分
+        response.add_vary("Cookie");
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::http::{Body, StatusCode};
+    use super::super::middleware::Pipeline;
+    use super::super::router::Router;
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn hmac_sha1_known_answers() {
+        // RFC 2202 中的测试用例 1、2、6（第 6 个的密钥比分组长）
+        assert_eq!(
+            hex(&hmac_sha1(&[0x0b; 20], b"Hi There")),
+            "b617318655057264e28bc0b6fb378c8ef146be00"
+        );
+        assert_eq!(
+            hex(&hmac_sha1(b"Jefe", b"what do ya want for nothing?")),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            hex(&hmac_sha1(
+                &[0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "aa4ae5e15272d00e95705637ce8a3b55ed402112"
+        );
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_slices() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+        assert!(!constant_time_eq(b"xbc", b"abc"));
+    }
+
+    #[test]
+    fn rejects_tampered_signatures() {
+        let signer = Signer {
+            key: b"0123456789abcdef".to_vec(),
+        };
+        let signed = signer.sign("abc");
+        assert_eq!(signer.verify(&signed), Some("abc"));
+        assert_eq!(signer.verify(&signed.replacen("abc", "abd", 1)), None);
+        assert_eq!(signer.verify("abc"), None);
+        assert_eq!(signer.verify("abc.not-base64!"), None);
+
+        let other = Signer {
+            key: b"fedcba9876543210".to_vec(),
+        };
+        assert_eq!(other.verify(&signed), None);
+    }
+
+    #[test]
+    fn middleware_round_trip() {
+        let mut router = Router::new();
+        router
+            .get("/count", |req| {
+                let session = req.session.as_ref().unwrap();
+                let count = session.get::<u32>("count").unwrap_or(0) + 1;
+                session.insert("count", &count);
+                Response::text(StatusCode::OK, &count.to_string())
+            })
+            .get("/peek", |_| Response::text(StatusCode::OK, "ok"));
+        let pipeline = Pipeline::new(router).wrap(Sessions::new(
+            SessionConfig::default(),
+            Box::new(MemoryStore::new(Duration::from_secs(60))),
+            b"0123456789abcdef",
+        ));
+        let get = |path: &str, cookie: Option<&str>| {
+            let mut raw = format!("GET {} HTTP/1.1\r\n", path);
+            if let Some(cookie) = cookie {
+                raw.push_str(&format!("Cookie: {}\r\n", cookie));
+            }
+            raw.push_str("\r\n");
+            let mut request = Request::read_from(&mut raw.as_bytes()).unwrap().unwrap();
+            pipeline.handle(&mut request)
+        };
+
+        // 没有写入数据时不创建会话
+        assert_eq!(get("/peek", None).header("Set-Cookie"), None);
+
+        let first = get("/count", None);
+        let set_cookie = first.header("Set-Cookie").unwrap();
+        assert!(set_cookie.contains("HttpOnly"), "{}", set_cookie);
+        let cookie = set_cookie.split(';').next().unwrap().to_string();
+
+        let second = get("/count", Some(&cookie));
+        let Body::Bytes(body) = &second.body else {
+            panic!("应当是定长响应体");
+        };
+        assert_eq!(body, b"2");
+
+        // 签名被篡改的 Cookie 当作没有会话
+        let forged = cookie.replacen("sid=", "sid=0", 1);
+        let third = get("/count", Some(&forged));
+        let Body::Bytes(body) = &third.body else {
+            panic!("应当是定长响应体");
+        };
+        assert_eq!(body, b"1");
+    }
+}
@@ -0,0 +1,267 @@
+// projects/web/shutdown.rs - 优雅关闭与无中断重启
+//
+//   Ctrl+C / SIGTERM   /readyz 改为返回 503，再接收 DRAIN_GRACE 时间的连接后停止接收，
+//                      等进行中的请求处理完（最多 --shutdown-timeout 秒）后退出
+//   再次 Ctrl+C        不再等待，立即退出
+//   SIGUSR2            无中断重启：启动一个新进程并把监听套接字交给它，旧进程随后优雅关闭
+//
+// 无中断重启的过程：
+//   1. 旧进程启动新进程（同一个可执行文件、同样的参数），监听套接字作为继承的文件描述符传过去，
+//      环境变量 WEB_LISTEN_FD 告诉新进程是哪个描述符
+//   2. 新进程直接使用这个套接字，不需要重新 bind，所以端口一直处于监听状态
+//   3. 旧进程停止 accept；此后到达的连接留在内核的等待队列中，由新进程接收，不会被拒绝
+//   4. 旧进程处理完已经接收的连接后退出
+// 重新编译后发送 SIGUSR2 就能在不中断服务的情况下换上新版本：
+//   kill -USR2 $(pgrep project_web)
+//
+// 标准库没有处理信号的 API，这里借用项目已经依赖的 tokio，在一个后台线程中等待信号
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::process;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::metrics::Metrics;
+
+// 新进程从这个环境变量得知继承的监听套接字是哪个文件描述符
+const LISTEN_FD_VAR: &str = "WEB_LISTEN_FD";
+// 新进程启动后至少存活这么久，才认为交接成功
+const HANDOFF_CHECK: Duration = Duration::from_secs(1);
+// 唤醒监听循环的重试间隔
+const WAKE_INTERVAL: Duration = Duration::from_millis(200);
+// 收到关闭信号后继续接收连接的时间，让负载均衡器有机会通过 /readyz 发现实例正在关闭
+const DRAIN_GRACE: Duration = Duration::from_secs(2);
+// 宽限期内没有新连接时的轮询间隔
+const GRACE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+pub enum Signal {
+    Terminate,
+    Restart,
+}
+
+// 信号处理线程与监听循环之间共享的状态：收到但还没有被监听循环处理的信号
+pub struct Shutdown {
+    pending: Mutex<Option<Signal>>,
+}
+
+impl Shutdown {
+    fn lock(&self) -> MutexGuard<'_, Option<Signal>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // 监听循环每接收一个连接后调用一次
+    pub fn take(&self) -> Option<Signal> {
+        self.lock().take()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.lock().is_some()
+    }
+}
+
+// 启动信号处理线程；addr 是监听地址，用于唤醒阻塞在 accept 中的监听循环
+pub fn watch(addr: SocketAddr) -> io::Result<Arc<Shutdown>> {
+    let shutdown = Arc::new(Shutdown {
+        pending: Mutex::new(None),
+    });
+
+    let state = Arc::clone(&shutdown);
+    thread::Builder::new()
+        .name("signals".to_string())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    log_error!("❌ 无法启动信号处理: {}", e);
+                    return;
+                }
+            };
+            runtime.block_on(async {
+                let mut signals = match Signals::new() {
+                    Ok(signals) => signals,
+                    Err(e) => {
+                        log_error!("❌ 无法监听信号: {}", e);
+                        return;
+                    }
+                };
+                let mut terminating = false;
+                loop {
+                    let signal = signals.recv().await;
+                    match signal {
+                        Signal::Terminate if terminating => {
+                            log_warn!("⚠️ 再次收到关闭信号，立即退出");
+                            process::exit(130);
+                        }
+                        Signal::Terminate => {
+                            terminating = true;
+                            log_info!("🛑 收到关闭信号，停止接收新连接");
+                        }
+                        Signal::Restart => log_info!("🔄 收到重启信号，准备交接监听套接字"),
+                    }
+                    *state.lock() = Some(signal);
+                    wake(Arc::clone(&state), addr);
+                }
+            });
+        })?;
+
+    Ok(shutdown)
+}
+
+#[cfg(unix)]
+struct Signals {
+    interrupt: tokio::signal::unix::Signal,
+    terminate: tokio::signal::unix::Signal,
+    restart: tokio::signal::unix::Signal,
+}
+
+#[cfg(unix)]
+impl Signals {
+    // 在整个运行期间只注册一次，两次 recv 之间到达的信号也不会丢失
+    fn new() -> io::Result<Signals> {
+        use tokio::signal::unix::{SignalKind, signal};
+        Ok(Signals {
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+            restart: signal(SignalKind::user_defined2())?,
+        })
+    }
+
+    async fn recv(&mut self) -> Signal {
+        tokio::select! {
+            _ = self.interrupt.recv() => Signal::Terminate,
+            _ = self.terminate.recv() => Signal::Terminate,
+            _ = self.restart.recv() => Signal::Restart,
+        }
+    }
+}
+
+// 其他平台只支持 Ctrl+C
+#[cfg(not(unix))]
+struct Signals;
+
+#[cfg(not(unix))]
+impl Signals {
+    fn new() -> io::Result<Signals> {
+        Ok(Signals)
+    }
+
+    async fn recv(&mut self) -> Signal {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => Signal::Terminate,
+            Err(_) => std::future::pending().await,
+        }
+    }
+}
+
+// 监听循环阻塞在 accept 中，连接一下自己把它唤醒；这个连接不发送任何数据，
+// 工作线程读到 EOF 后直接关闭它。交接之后新进程也在同一个套接字上 accept，
+// 唤醒连接可能被新进程接收，所以一直重试到监听循环取走信号为止
+fn wake(state: Arc<Shutdown>, addr: SocketAddr) {
+    // 监听 0.0.0.0 或 [::] 时连接本机回环地址
+    let ip = match addr {
+        SocketAddr::V4(a) if a.ip().is_unspecified() => Ipv4Addr::LOCALHOST.into(),
+        SocketAddr::V6(a) if a.ip().is_unspecified() => Ipv6Addr::LOCALHOST.into(),
+        _ => addr.ip(),
+    };
+    let addr = SocketAddr::new(ip, addr.port());
+    thread::spawn(move || {
+        while state.is_pending() {
+            if let Err(e) = TcpStream::connect_timeout(&addr, WAKE_INTERVAL) {
+                log_warn!("⚠️ 无法唤醒监听循环: {}", e);
+            }
+            thread::sleep(WAKE_INTERVAL);
+        }
+    });
+}
+
+// 关闭前的宽限期：继续接收连接并交给 dispatch 处理，DRAIN_GRACE 之后返回。
+// 监听套接字改为非阻塞，这样即使没有新连接也能按时结束
+pub fn accept_during_grace(listener: &TcpListener, dispatch: impl Fn(TcpStream)) {
+    if let Err(e) = listener.set_nonblocking(true) {
+        log_warn!("⚠️ 无法进入关闭宽限期: {}", e);
+        return;
+    }
+    let deadline = Instant::now() + DRAIN_GRACE;
+    while Instant::now() < deadline {
+        match listener.accept() {
+            // 有些平台上接收到的连接会继承非阻塞模式，工作线程需要阻塞读写
+            Ok((stream, _)) => match stream.set_nonblocking(false) {
+                Ok(()) => dispatch(stream),
+                Err(e) => log_error!("❌ 接受连接失败: {}", e),
+            },
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(GRACE_POLL_INTERVAL),
+            Err(e) => log_error!("❌ 接受连接失败: {}", e),
+        }
+    }
+}
+
+// 启动新进程并把监听套接字交给它，返回新进程的 PID
+#[cfg(unix)]
+pub fn hand_off(listener: &TcpListener) -> io::Result<u32> {
+    use std::env;
+    use std::os::fd::OwnedFd;
+    use std::process::{Command, Stdio};
+
+    // 作为新进程的标准输入 (fd 0) 传过去；Command 只能这样传递任意文件描述符，
+    // 服务器本来也不读标准输入
+    let socket = OwnedFd::from(listener.try_clone()?);
+    let mut child = Command::new(env::current_exe()?)
+        .args(env::args_os().skip(1))
+        .env(LISTEN_FD_VAR, "0")
+        .stdin(Stdio::from(socket))
+        .spawn()?;
+
+    // 新进程如果启动就失败（例如参数错误），旧进程继续服务
+    let deadline = Instant::now() + HANDOFF_CHECK;
+    while Instant::now() < deadline {
+        if let Some(status) = child.try_wait()? {
+            return Err(io::Error::other(format!("新进程已退出: {}", status)));
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    Ok(child.id())
+}
+
+#[cfg(not(unix))]
+pub fn hand_off(_listener: &TcpListener) -> io::Result<u32> {
+    Err(io::Error::other("当前平台不支持套接字交接"))
+}
+
+// 从父进程继承的监听套接字；不是由交接启动时返回 None
+#[cfg(unix)]
+pub fn inherited_listener() -> Option<io::Result<TcpListener>> {
+    use std::env;
+    use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+
+    let fd: RawFd = env::var(LISTEN_FD_VAR).ok()?.parse().ok()?;
+    // SAFETY: WEB_LISTEN_FD 由 hand_off 设置，指向一个只在这里取得所有权的监听套接字
+    let listener = TcpListener::from(unsafe { OwnedFd::from_raw_fd(fd) });
+    // 不是套接字时这里会出错
+    Some(listener.local_addr().map(|_| listener))
+}
+
+#[cfg(not(unix))]
+pub fn inherited_listener() -> Option<io::Result<TcpListener>> {
+    None
+}
+
+// 等待进行中的连接处理完；超过 timeout 返回 false
+pub fn drain(metrics: &Metrics, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let in_flight = metrics.in_flight();
+        if in_flight == 0 {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            log_warn!("⚠️ 等待超时，仍有 {} 个连接未完成", in_flight);
+            return false;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
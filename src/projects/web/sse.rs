@@ -0,0 +1,411 @@
+// projects/web/sse.rs - Server-Sent Events
+// 服务器通过一个不结束的 text/event-stream 响应持续推送事件，浏览器用 EventSource 接收：
+//
+//   retry: 3000              断线后浏览器等待多久重连（毫秒）
+//
+//   id: 42                   事件编号，重连时浏览器通过 Last-Event-ID 头发回来
+//   event: user_action       事件类型，对应 addEventListener 的名字
+//   data: {"x":1}            数据，可以有多行 data
+//                            空行表示一个事件结束
+//   : heartbeat              冒号开头的是注释，用作心跳，防止连接被代理当作空闲而关闭
+//
+// 注意：每个 SSE 连接会一直占用线程池中的一个工作线程，所以要限制同时订阅的数量
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::http::{Request, Response, StatusCode};
+use super::metrics::Metrics;
+use super::router::Router;
+use super::template::Templates;
+
+// 建议浏览器断线后的重连间隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(3);
+// 没有事件时发送心跳注释的间隔
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+// 检查服务器是否正在关闭的间隔
+const DRAIN_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+// 示例事件源产生事件的间隔
+const EVENT_INTERVAL: Duration = Duration::from_secs(2);
+// 保留最近多少条事件，用于断线重连后补发
+const HISTORY_SIZE: usize = 100;
+
+// ========== 协议 ==========
+
+#[derive(Debug, Clone)]
+pub struct SseEvent {
+    pub id: Option<u64>,
+    pub event: Option<String>,
+    pub data: String,
+}
+
+impl SseEvent {
+    pub fn new(data: &str) -> SseEvent {
+        SseEvent {
+            id: None,
+            event: None,
+            data: data.to_string(),
+        }
+    }
+
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_event(mut self, event: &str) -> Self {
+        self.event = Some(event.to_string());
+        self
+    }
+
+    fn encode(&self) -> String {
+        let mut out = String::new();
+        if let Some(id) = self.id {
+            out.push_str(&format!("id: {}\n", id));
+        }
+        if let Some(event) = &self.event {
+            out.push_str(&format!("event: {}\n", event));
+        }
+        // 数据中的每一行都要单独加 "data: " 前缀
+        for line in self.data.split('\n') {
+            out.push_str(&format!("data: {}\n", line.trim_end_matches('\r')));
+        }
+        out.push('\n');
+        out
+    }
+}
+
+// 写事件流；每次写完立即 flush，保证事件马上送到浏览器（包括经过压缩的情况）
+pub struct SseWriter<'a> {
+    writer: &'a mut dyn Write,
+}
+
+impl SseWriter<'_> {
+    pub fn send(&mut self, event: &SseEvent) -> io::Result<()> {
+        self.write(&event.encode())
+    }
+
+    // 注释行，浏览器会忽略；用作心跳
+    pub fn comment(&mut self, text: &str) -> io::Result<()> {
+        self.write(&format!(": {}\n\n", text))
+    }
+
+    pub fn retry(&mut self, interval: Duration) -> io::Result<()> {
+        self.write(&format!("retry: {}\n\n", interval.as_millis()))
+    }
+
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+// SSE 响应：f 在响应头发出之后执行，返回时事件流结束
+pub fn stream<F>(f: F) -> Response
+where
+    F: FnOnce(&mut SseWriter<'_>) -> io::Result<()> + Send + 'static,
+{
+    Response::stream(
+        StatusCode::OK,
+        "text/event-stream; charset=utf-8",
+        move |writer| f(&mut SseWriter { writer }),
+    )
+    .with_header("Cache-Control", "no-cache")
+    // 告诉 nginx 之类的反向代理不要缓冲事件
+    .with_header("X-Accel-Buffering", "no")
+}
+
+// 浏览器重连时带上的最后一个事件编号；
+// 也接受查询参数 ?lastEventId=，方便用 curl 测试
+pub fn last_event_id(request: &Request) -> Option<u64> {
+    request
+        .header("Last-Event-ID")
+        .map(str::to_string)
+        .or_else(|| request.query_param("lastEventId"))
+        .and_then(|id| id.trim().parse().ok())
+}
+
+// ========== 示例：事件枚举 ==========
+// 与 08_enums.rs 中的事件系统枚举一致，加上 Serialize 以便以 JSON 发送
+
+#[derive(Debug, Serialize)]
+enum Event {
+    UserAction(UserAction),
+    SystemEvent(SystemEvent),
+}
+
+#[derive(Debug, Serialize)]
+enum UserAction {
+    Click { x: i32, y: i32 },
+    KeyPress(char),
+}
+
+#[derive(Debug, Serialize)]
+enum SystemEvent {
+    Shutdown,
+    NetworkError(String),
+}
+
+impl Event {
+    // 随机生成一个事件，模拟实时数据
+    fn random() -> Event {
+        match rand::random_range(0..10) {
+            0..=4 => Event::UserAction(UserAction::Click {
+                x: rand::random_range(0..800),
+                y: rand::random_range(0..600),
+            }),
+            5..=7 => {
+                let key = rand::random_range(b'a'..=b'z') as char;
+                Event::UserAction(UserAction::KeyPress(key))
+            }
+            8 => Event::SystemEvent(SystemEvent::NetworkError(String::from("连接超时"))),
+            _ => Event::SystemEvent(SystemEvent::Shutdown),
+        }
+    }
+
+    // SSE 的事件类型：浏览器可以只监听其中一类
+    fn kind(&self) -> &'static str {
+        match self {
+            Event::UserAction(_) => "user_action",
+            Event::SystemEvent(_) => "system_event",
+        }
+    }
+}
+
+// ========== 示例：实时事件源 ==========
+
+struct FeedState {
+    next_id: u64,
+    history: VecDeque<SseEvent>,
+    subscribers: Vec<mpsc::Sender<SseEvent>>,
+}
+
+pub struct EventFeed {
+    state: Mutex<FeedState>,
+    active: AtomicUsize,
+    max_subscribers: usize,
+}
+
+impl EventFeed {
+    // 创建事件源，并启动一个后台线程定时产生事件
+    pub fn new(max_subscribers: usize) -> Arc<EventFeed> {
+        let feed = Arc::new(EventFeed {
+            state: Mutex::new(FeedState {
+                next_id: 1,
+                history: VecDeque::new(),
+                subscribers: Vec::new(),
+            }),
+            active: AtomicUsize::new(0),
+            max_subscribers,
+        });
+
+        let producer = Arc::clone(&feed);
+        thread::spawn(move || {
+            loop {
+                thread::sleep(EVENT_INTERVAL);
+                producer.publish(&Event::random());
+            }
+        });
+        feed
+    }
+
+    fn publish(&self, event: &Event) {
+        let data = match serde_json::to_string(event) {
+            Ok(data) => data,
+            Err(e) => {
+                log_error!("❌ 事件序列化失败: {}", e);
+                return;
+            }
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let event = SseEvent::new(&data).with_id(id).with_event(event.kind());
+        state.history.push_back(event.clone());
+        if state.history.len() > HISTORY_SIZE {
+            state.history.pop_front();
+        }
+        // 发送失败说明订阅者已经断开，直接移除
+        state
+            .subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+
+    // 订阅事件；已达到上限时返回 None
+    // 补发历史事件和加入订阅列表在同一把锁内完成，中间不会漏掉事件
+    fn subscribe(self: &Arc<Self>, last_event_id: Option<u64>) -> Option<Subscription> {
+        self.active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < self.max_subscribers).then_some(n + 1)
+            })
+            .ok()?;
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let missed = match last_event_id {
+            Some(last) => state
+                .history
+                .iter()
+                .filter(|event| event.id > Some(last))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        let (sender, receiver) = mpsc::channel();
+        state.subscribers.push(sender);
+
+        Some(Subscription {
+            feed: Arc::clone(self),
+            missed,
+            receiver,
+        })
+    }
+}
+
+// 订阅在 drop 时释放名额
+struct Subscription {
+    feed: Arc<EventFeed>,
+    missed: Vec<SseEvent>,
+    receiver: mpsc::Receiver<SseEvent>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.feed.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn feed_stream(feed: &Arc<EventFeed>, metrics: &Arc<Metrics>, request: &Request) -> Response {
+    let Some(subscription) = feed.subscribe(last_event_id(request)) else {
+        return Response::text(StatusCode::SERVICE_UNAVAILABLE, "订阅者过多，请稍后重试")
+            .with_header("Retry-After", &RETRY_INTERVAL.as_secs().to_string());
+    };
+
+    let metrics = Arc::clone(metrics);
+    stream(move |sse| {
+        sse.retry(RETRY_INTERVAL)?;
+        for event in &subscription.missed {
+            sse.send(event)?;
+        }
+        // 写入失败（浏览器已关闭）时返回错误，事件流随之结束；
+        // 服务器开始关闭时主动结束事件流，否则心跳会让连接一直存活，排空等到超时。
+        // 浏览器过 RETRY_INTERVAL 后重连，重启时连到的是新进程
+        let mut last_write = Instant::now();
+        loop {
+            if metrics.is_draining() {
+                return Ok(());
+            }
+            match subscription.receiver.recv_timeout(DRAIN_CHECK_INTERVAL) {
+                Ok(event) => sse.send(&event)?,
+                Err(mpsc::RecvTimeoutError::Timeout)
+                    if last_write.elapsed() >= HEARTBEAT_INTERVAL =>
+                {
+                    sse.comment("heartbeat")?
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+            last_write = Instant::now();
+        }
+    })
+}
+
+// 注册 SSE 示例路由
+//   GET /events   事件流（支持 Last-Event-ID 断线续传）
+//   GET /feed     实时事件网页
+// 同时订阅的数量不超过工作线程的一半，给普通请求留出线程
+pub fn register(
+    router: &mut Router,
+    workers: usize,
+    metrics: Arc<Metrics>,
+    templates: Arc<Templates>,
+) {
+    let feed = EventFeed::new((workers / 2).max(1));
+    router.get("/events", move |req| feed_stream(&feed, &metrics, req));
+
+    router.get("/feed", move |_| {
+        templates.page(StatusCode::OK, "feed.html", &())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_event_fields() {
+        assert_eq!(SseEvent::new("hello").encode(), "data: hello\n\n");
+        assert_eq!(
+            SseEvent::new("{}").with_id(7).with_event("user").encode(),
+            "id: 7\nevent: user\ndata: {}\n\n"
+        );
+    }
+
+    #[test]
+    fn splits_multiline_data() {
+        // 空行会提前结束事件，每一行都必须带 "data: " 前缀
+        assert_eq!(
+            SseEvent::new("a\r\n\nb").encode(),
+            "data: a\ndata: \ndata: b\n\n"
+        );
+    }
+
+    #[test]
+    fn writes_comments_and_retry() {
+        let mut out = Vec::new();
+        let mut writer = SseWriter { writer: &mut out };
+        writer.retry(Duration::from_secs(3)).unwrap();
+        writer.comment("ping").unwrap();
+        writer.send(&SseEvent::new("x")).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "retry: 3000\n\n: ping\n\ndata: x\n\n"
+        );
+    }
+
+    #[test]
+    fn reads_last_event_id() {
+        let request = |raw: &str| Request::read_from(&mut raw.as_bytes()).unwrap().unwrap();
+        assert_eq!(
+            last_event_id(&request(
+                "GET /events HTTP/1.1\r\nLast-Event-ID: 42\r\n\r\n"
+            )),
+            Some(42)
+        );
+        assert_eq!(
+            last_event_id(&request("GET /events?lastEventId=5 HTTP/1.1\r\n\r\n")),
+            Some(5)
+        );
+        assert_eq!(
+            last_event_id(&request("GET /events HTTP/1.1\r\nLast-Event-ID: x\r\n\r\n")),
+            None
+        );
+    }
+
+    #[test]
+    fn replays_missed_events_and_limits_subscribers() {
+        let feed = EventFeed::new(1);
+        let event = Event::UserAction(UserAction::KeyPress('a'));
+        feed.publish(&event);
+        feed.publish(&event);
+
+        let subscription = feed.subscribe(Some(1)).unwrap();
+        let missed: Vec<_> = subscription.missed.iter().map(|e| e.id).collect();
+        assert_eq!(missed, [Some(2)]);
+        assert!(feed.subscribe(None).is_none());
+
+        // 新事件直接推送给订阅者
+        feed.publish(&event);
+        assert_eq!(subscription.receiver.recv().unwrap().id, Some(3));
+
+        drop(subscription);
+        assert!(feed.subscribe(None).is_some());
+    }
+}
@@ -0,0 +1,84 @@
+// projects/web/static_files.rs - 静态文件
+// 在 /static/ 下提供静态目录中的文件，拒绝任何试图跳出该目录的路径
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::http::{Request, Response, StatusCode, url_decode};
+use super::router::Router;
+
+// 根据扩展名猜测 Content-Type
+fn content_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "application/javascript",
+        "json" => "application/json",
+        "txt" | "md" | "rs" | "toml" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+// 把请求路径映射到静态目录中的文件
+// 只接受普通的路径片段：".."、绝对路径、Windows 盘符都会被拒绝
+fn resolve(root: &Path, request_path: &str) -> Option<PathBuf> {
+    let decoded = url_decode(request_path);
+    let relative = Path::new(&decoded);
+    if decoded.contains('\0')
+        || !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    // 符号链接也可能指向目录外部，比较规范化之后的路径
+    let root = root.canonicalize().ok()?;
+    let mut path = root.join(relative).canonicalize().ok()?;
+    if !path.starts_with(&root) {
+        return None;
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+fn serve_file(root: &Path, request: &Request) -> Response {
+    let not_found = || Response::text(StatusCode::NOT_FOUND, "404 Not Found");
+
+    let Some(path) = resolve(root, request.param("path").unwrap_or_default()) else {
+        return not_found();
+    };
+    match fs::read(&path) {
+        Ok(body) => Response::new(StatusCode::OK)
+            .with_header("Content-Type", content_type(&path))
+            .with_body(body),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => not_found(),
+        Err(e) => {
+            log_error!("❌ 读取静态文件 {} 失败: {}", path.display(), e);
+            Response::text(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "500 Internal Server Error",
+            )
+        }
+    }
+}
+
+// 注册 GET /static/*path
+pub fn register(router: &mut Router, root: PathBuf) {
+    router.get("/static/*path", move |req| serve_file(&root, req));
+}
@@ -0,0 +1,753 @@
+// projects/web/template.rs - HTML 模板引擎
+// 语法：
+//   {{ book.title }}                    输出变量，自动进行 HTML 转义
+//   {{ snippet | raw }}                 输出变量，不转义
+//   {{ book.author | urlencode }}       百分号编码，用于 URL 中的查询参数和路径片段
+//   {% if books %}...{% else %}...{% endif %}            条件，支持 if not
+//   {% for book in books %}...{% else %}...{% endfor %}  循环，else 在数组为空时输出
+//                                       循环内可用 loop.index、loop.first、loop.last
+//   {% include "_nav.html" %}           引入另一个模板
+//   {% extends "base.html" %}           继承父模板，用 {% block 名称 %}...{% endblock %} 覆盖其中的块
+//   {# 注释 #}
+//
+// 渲染数据可以是任何实现了 Serialize 的值，先转换成 serde_json::Value 再查找变量
+// 模板从目录中加载并缓存；开发模式下每次渲染前检查文件修改时间，修改后自动重新加载
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::http::{Response, StatusCode};
+
+// include / extends 的最大嵌套层数，防止模板互相引用导致无限递归
+const MAX_DEPTH: usize = 16;
+
+// ========== 错误类型 ==========
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound(String),
+    Io(io::Error),
+    Syntax { template: String, message: String },
+    Render(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::NotFound(name) => write!(f, "模板不存在: {}", name),
+            TemplateError::Io(e) => write!(f, "读取模板失败: {}", e),
+            TemplateError::Syntax { template, message } => {
+                write!(f, "模板 {} 语法错误: {}", template, message)
+            }
+            TemplateError::Render(message) => write!(f, "渲染失败: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+// ========== 解析 ==========
+
+enum Token<'a> {
+    Text(&'a str),
+    Var(&'a str),
+    Tag(&'a str),
+}
+
+// 把模板源码切分成文本、{{ 变量 }} 和 {% 标签 %}，注释直接丢弃
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = ["{{", "{%", "{#"]
+        .iter()
+        .filter_map(|open| rest.find(open))
+        .min()
+    {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let open = &rest[start..start + 2];
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let inner_start = start + 2;
+        let len = rest[inner_start..]
+            .find(close)
+            .ok_or_else(|| format!("{} 没有对应的 {}", open, close))?;
+        let inner = rest[inner_start..inner_start + len].trim();
+        match open {
+            "{{" => tokens.push(Token::Var(inner)),
+            "{%" => tokens.push(Token::Tag(inner)),
+            _ => {}
+        }
+        rest = &rest[inner_start + len + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    Ok(tokens)
+}
+
+// 变量输出前的处理方式
+#[derive(Clone, Copy)]
+enum Filter {
+    Escape,
+    Raw,
+    UrlEncode,
+}
+
+enum Node {
+    Text(String),
+    Var {
+        path: Vec<String>,
+        filter: Filter,
+    },
+    If {
+        negate: bool,
+        path: Vec<String>,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        var: String,
+        path: Vec<String>,
+        body: Vec<Node>,
+        empty: Vec<Node>,
+    },
+    Include(String),
+    // 块的内容放在 Arc 中，子模板覆盖父模板的块时不需要复制节点
+    Block {
+        name: String,
+        body: Arc<Vec<Node>>,
+    },
+}
+
+struct Template {
+    extends: Option<String>,
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    fn parse(source: &str) -> Result<Template, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?.into_iter(),
+            extends: None,
+        };
+        let (nodes, _) = parser.parse_nodes(&[])?;
+        Ok(Template {
+            extends: parser.extends,
+            nodes,
+        })
+    }
+}
+
+struct Parser<'a> {
+    tokens: std::vec::IntoIter<Token<'a>>,
+    extends: Option<String>,
+}
+
+impl Parser<'_> {
+    // 解析到 ends 中的任意一个结束标签为止，返回节点和遇到的结束标签
+    fn parse_nodes(&mut self, ends: &[&str]) -> Result<(Vec<Node>, String), String> {
+        let mut nodes = Vec::new();
+
+        while let Some(token) = self.tokens.next() {
+            match token {
+                Token::Text(text) => nodes.push(Node::Text(text.to_string())),
+                Token::Var(expr) => {
+                    let (path, filter) = match expr.split_once('|') {
+                        Some((path, filter)) => (path, Some(filter.trim())),
+                        None => (expr, None),
+                    };
+                    let filter = match filter {
+                        None => Filter::Escape,
+                        Some("raw") => Filter::Raw,
+                        Some("urlencode") => Filter::UrlEncode,
+                        Some(other) => return Err(format!("未知的过滤器: {}", other)),
+                    };
+                    nodes.push(Node::Var {
+                        path: parse_path(path)?,
+                        filter,
+                    });
+                }
+                Token::Tag(tag) => {
+                    let (keyword, args) = tag.split_once(' ').unwrap_or((tag, ""));
+                    let args = args.trim();
+                    if ends.contains(&keyword) {
+                        return Ok((nodes, keyword.to_string()));
+                    }
+                    nodes.push(self.parse_tag(keyword, args)?);
+                }
+            }
+        }
+
+        match ends.last() {
+            Some(end) => Err(format!("缺少 {{% {} %}}", end)),
+            None => Ok((nodes, String::new())),
+        }
+    }
+
+    fn parse_tag(&mut self, keyword: &str, args: &str) -> Result<Node, String> {
+        match keyword {
+            "if" => {
+                let (negate, path) = match args.strip_prefix("not ") {
+                    Some(path) => (true, path),
+                    None => (false, args),
+                };
+                let (then, end) = self.parse_nodes(&["else", "endif"])?;
+                let otherwise = if end == "else" {
+                    self.parse_nodes(&["endif"])?.0
+                } else {
+                    Vec::new()
+                };
+                Ok(Node::If {
+                    negate,
+                    path: parse_path(path)?,
+                    then,
+                    otherwise,
+                })
+            }
+            "for" => {
+                let (var, path) = args
+                    .split_once(" in ")
+                    .ok_or_else(|| format!("for 的格式应为 \"变量 in 列表\": {}", args))?;
+                let (body, end) = self.parse_nodes(&["else", "endfor"])?;
+                let empty = if end == "else" {
+                    self.parse_nodes(&["endfor"])?.0
+                } else {
+                    Vec::new()
+                };
+                Ok(Node::For {
+                    var: parse_name(var.trim())?,
+                    path: parse_path(path)?,
+                    body,
+                    empty,
+                })
+            }
+            "include" => Ok(Node::Include(parse_string(args)?)),
+            "extends" => {
+                if self.extends.is_some() {
+                    return Err("一个模板只能 extends 一次".to_string());
+                }
+                self.extends = Some(parse_string(args)?);
+                Ok(Node::Text(String::new()))
+            }
+            "block" => {
+                let name = parse_name(args)?;
+                let (body, _) = self.parse_nodes(&["endblock"])?;
+                Ok(Node::Block {
+                    name,
+                    body: Arc::new(body),
+                })
+            }
+            other => Err(format!("未知的标签: {}", other)),
+        }
+    }
+}
+
+fn parse_name(name: &str) -> Result<String, String> {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        Ok(name.to_string())
+    } else {
+        Err(format!("无效的名称: {:?}", name))
+    }
+}
+
+// "book.title" → ["book", "title"]
+fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    path.trim().split('.').map(parse_name).collect()
+}
+
+// 带引号的字符串："base.html" 或 'base.html'
+fn parse_string(s: &str) -> Result<String, String> {
+    let s = s.trim();
+    let unquoted = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')));
+    unquoted
+        .map(str::to_string)
+        .ok_or_else(|| format!("需要带引号的模板名: {}", s))
+}
+
+// ========== 渲染 ==========
+
+// 变量作用域：for 循环会压入新的一层，查找时由内向外
+struct Scope<'a> {
+    root: &'a Value,
+    frames: Vec<(String, Value)>,
+}
+
+impl Scope<'_> {
+    fn lookup(&self, path: &[String]) -> Option<&Value> {
+        let (first, rest) = path.split_first()?;
+        let mut value = self
+            .frames
+            .iter()
+            .rev()
+            .find(|(name, _)| name == first)
+            .map(|(_, value)| value)
+            .or_else(|| self.root.get(first))?;
+
+        for key in rest {
+            value = match value {
+                Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+                _ => value.get(key)?,
+            };
+        }
+        Some(value)
+    }
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64() != Some(0.0),
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// 除 RFC 3986 的非保留字符外全部按 UTF-8 字节编码成 %XX，空格编码为 %20 而不是 +
+fn url_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+// 子模板定义的块，按名称查找
+type Blocks = HashMap<String, Arc<Vec<Node>>>;
+
+// 登记模板中的块；子模板先登记，所以同名时子模板的块优先
+fn collect_blocks(nodes: &[Node], blocks: &mut Blocks) {
+    for node in nodes {
+        if let Node::Block { name, body } = node {
+            blocks
+                .entry(name.clone())
+                .or_insert_with(|| Arc::clone(body));
+            collect_blocks(body, blocks);
+        }
+    }
+}
+
+// ========== 模板目录 ==========
+
+struct Cached {
+    modified: Option<SystemTime>,
+    template: Arc<Template>,
+}
+
+pub struct Templates {
+    dir: PathBuf,
+    hot_reload: bool,
+    cache: RwLock<HashMap<String, Cached>>,
+}
+
+impl Templates {
+    // hot_reload 为 true 时（开发模式），模板文件修改后会自动重新加载
+    pub fn new(dir: PathBuf, hot_reload: bool) -> Templates {
+        Templates {
+            dir,
+            hot_reload,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn load(&self, name: &str) -> Result<Arc<Template>, TemplateError> {
+        // 模板名只能是目录中的相对路径
+        let relative = Path::new(name);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(TemplateError::NotFound(name.to_string()));
+        }
+        let path = self.dir.join(relative);
+
+        let modified = if self.hot_reload {
+            fs::metadata(&path).and_then(|m| m.modified()).ok()
+        } else {
+            None
+        };
+        {
+            let cache = self.cache.read().unwrap_or_else(PoisonError::into_inner);
+            let fresh = cache.get(name).filter(|cached| {
+                !self.hot_reload || (modified.is_some() && cached.modified == modified)
+            });
+            if let Some(cached) = fresh {
+                return Ok(Arc::clone(&cached.template));
+            }
+        }
+
+        let source = fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => TemplateError::NotFound(name.to_string()),
+            _ => TemplateError::Io(e),
+        })?;
+        let template =
+            Arc::new(
+                Template::parse(&source).map_err(|message| TemplateError::Syntax {
+                    template: name.to_string(),
+                    message,
+                })?,
+            );
+
+        if self.hot_reload {
+            log_debug!("🔄 已加载模板 {}", name);
+        }
+        self.cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                name.to_string(),
+                Cached {
+                    modified,
+                    template: Arc::clone(&template),
+                },
+            );
+        Ok(template)
+    }
+
+    pub fn render<T: Serialize>(&self, name: &str, context: &T) -> Result<String, TemplateError> {
+        let root =
+            serde_json::to_value(context).map_err(|e| TemplateError::Render(e.to_string()))?;
+        let mut scope = Scope {
+            root: &root,
+            frames: Vec::new(),
+        };
+        let mut out = String::new();
+        self.render_template(name, &mut scope, &mut Blocks::new(), &mut out, 0)?;
+        Ok(out)
+    }
+
+    // 渲染成 HTML 响应；模板出错时记录日志并返回 500
+    pub fn page<T: Serialize>(&self, status: StatusCode, name: &str, context: &T) -> Response {
+        match self.render(name, context) {
+            Ok(html) => Response::html(status, &html),
+            Err(e) => {
+                log_error!("❌ {}", e);
+                Response::text(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "500 Internal Server Error",
+                )
+            }
+        }
+    }
+
+    fn render_template(
+        &self,
+        name: &str,
+        scope: &mut Scope<'_>,
+        blocks: &mut Blocks,
+        out: &mut String,
+        depth: usize,
+    ) -> Result<(), TemplateError> {
+        if depth > MAX_DEPTH {
+            return Err(TemplateError::Render(format!(
+                "模板嵌套超过 {} 层: {}",
+                MAX_DEPTH, name
+            )));
+        }
+
+        let template = self.load(name)?;
+        collect_blocks(&template.nodes, blocks);
+        match &template.extends {
+            // 继承时只渲染父模板，子模板中块以外的内容被忽略
+            Some(parent) => self.render_template(parent, scope, blocks, out, depth + 1),
+            None => self.render_nodes(&template.nodes, scope, blocks, out, depth),
+        }
+    }
+
+    fn render_nodes(
+        &self,
+        nodes: &[Node],
+        scope: &mut Scope<'_>,
+        blocks: &mut Blocks,
+        out: &mut String,
+        depth: usize,
+    ) -> Result<(), TemplateError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Var { path, filter } => {
+                    let text = match scope.lookup(path) {
+                        None | Some(Value::Null) => String::new(),
+                        Some(Value::String(s)) => s.clone(),
+                        Some(other) => other.to_string(),
+                    };
+                    match filter {
+                        Filter::Escape => out.push_str(&escape_html(&text)),
+                        Filter::Raw => out.push_str(&text),
+                        // 编码结果只含字母、数字和 %，在 HTML 属性中也不需要再转义
+                        Filter::UrlEncode => out.push_str(&url_encode(&text)),
+                    }
+                }
+                Node::If {
+                    negate,
+                    path,
+                    then,
+                    otherwise,
+                } => {
+                    let branch = if is_truthy(scope.lookup(path)) != *negate {
+                        then
+                    } else {
+                        otherwise
+                    };
+                    self.render_nodes(branch, scope, blocks, out, depth)?;
+                }
+                Node::For {
+                    var,
+                    path,
+                    body,
+                    empty,
+                } => {
+                    let items = match scope.lookup(path) {
+                        None | Some(Value::Null) => Vec::new(),
+                        Some(Value::Array(items)) => items.clone(),
+                        Some(_) => {
+                            return Err(TemplateError::Render(format!(
+                                "{} 不是数组",
+                                path.join(".")
+                            )));
+                        }
+                    };
+                    if items.is_empty() {
+                        self.render_nodes(empty, scope, blocks, out, depth)?;
+                    }
+                    let count = items.len();
+                    for (i, item) in items.into_iter().enumerate() {
+                        let info = serde_json::json!({
+                            "index": i + 1,
+                            "first": i == 0,
+                            "last": i + 1 == count,
+                        });
+                        scope.frames.push(("loop".to_string(), info));
+                        scope.frames.push((var.clone(), item));
+                        let result = self.render_nodes(body, scope, blocks, out, depth);
+                        scope.frames.truncate(scope.frames.len() - 2);
+                        result?;
+                    }
+                }
+                // 被引入的模板有自己的块，不受当前模板继承关系的影响
+                Node::Include(name) => {
+                    self.render_template(name, scope, &mut Blocks::new(), out, depth + 1)?
+                }
+                Node::Block { name, body } => {
+                    let body = blocks
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| Arc::clone(body));
+                    self.render_nodes(&body, scope, blocks, out, depth)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+
+    // 每个测试在自己的临时目录中放模板文件，结束时删除
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(files: &[(&str, &str)]) -> TestDir {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let dir = env::temp_dir().join(format!(
+                "rust-web-templates-{}-{}",
+                process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            let dir = TestDir(dir);
+            for (name, source) in files {
+                dir.write(name, source);
+            }
+            dir
+        }
+
+        fn write(&self, name: &str, source: &str) {
+            fs::write(self.0.join(name), source).unwrap();
+        }
+
+        fn templates(&self, hot_reload: bool) -> Templates {
+            Templates::new(self.0.clone(), hot_reload)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn render(source: &str, context: Value) -> String {
+        let dir = TestDir::new(&[("page.html", source)]);
+        dir.templates(false).render("page.html", &context).unwrap()
+    }
+
+    #[test]
+    fn escapes_variables_unless_raw() {
+        let context = json!({"name": "<b>\"Tom\" & 'Jerry'</b>"});
+        assert_eq!(
+            render("{{ name }}", context.clone()),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+        assert_eq!(
+            render("{{ name | raw }}", context),
+            "<b>\"Tom\" & 'Jerry'</b>"
+        );
+    }
+
+    #[test]
+    fn url_encodes_and_looks_up_nested_values() {
+        let context = json!({"book": {"author": "Steve Klabnik/中"}, "count": 3});
+        assert_eq!(
+            render("{{ book.author | urlencode }}", context.clone()),
+            "Steve%20Klabnik%2F%E4%B8%AD"
+        );
+        assert_eq!(render("{{ count }}{{ missing }}", context), "3");
+    }
+
+    #[test]
+    fn if_and_for_blocks() {
+        let source = "{% if not items %}空{% endif %}\
+                      {% for item in items %}{{ loop.index }}.{{ item }}{% if not loop.last %},{% endif %}\
+                      {% else %}无{% endfor %}";
+        assert_eq!(render(source, json!({"items": ["a", "b"]})), "1.a,2.b");
+        assert_eq!(render(source, json!({"items": []})), "空无");
+        assert_eq!(
+            render(
+                "{% if flag %}是{% else %}否{% endif %}",
+                json!({"flag": false})
+            ),
+            "否"
+        );
+    }
+
+    #[test]
+    fn includes_see_loop_variables() {
+        let dir = TestDir::new(&[
+            (
+                "list.html",
+                "{% for book in books %}{% include \"_row.html\" %}{% endfor %}",
+            ),
+            ("_row.html", "[{{ book.title }}]"),
+        ]);
+        let html = dir
+            .templates(false)
+            .render(
+                "list.html",
+                &json!({"books": [{"title": "A"}, {"title": "B"}]}),
+            )
+            .unwrap();
+        assert_eq!(html, "[A][B]");
+    }
+
+    #[test]
+    fn child_blocks_override_parent() {
+        let dir = TestDir::new(&[
+            (
+                "base.html",
+                "<title>{% block title %}默认{% endblock %}</title>{% block body %}{% endblock %}",
+            ),
+            (
+                "child.html",
+                "{% extends \"base.html\" %}忽略{% block body %}<p>{{ text }}</p>{% endblock %}",
+            ),
+        ]);
+        let html = dir
+            .templates(false)
+            .render("child.html", &json!({"text": "<hi>"}))
+            .unwrap();
+        assert_eq!(html, "<title>默认</title><p>&lt;hi&gt;</p>");
+    }
+
+    #[test]
+    fn reports_errors() {
+        let dir = TestDir::new(&[
+            ("broken.html", "{% if x %}没有结束"),
+            ("loop.html", "{% include \"loop.html\" %}"),
+        ]);
+        let templates = dir.templates(false);
+        assert!(matches!(
+            templates.render("missing.html", &json!({})),
+            Err(TemplateError::NotFound(_))
+        ));
+        assert!(matches!(
+            templates.render("../etc/passwd", &json!({})),
+            Err(TemplateError::NotFound(_))
+        ));
+        assert!(matches!(
+            templates.render("broken.html", &json!({})),
+            Err(TemplateError::Syntax { .. })
+        ));
+        assert!(matches!(
+            templates.render("loop.html", &json!({})),
+            Err(TemplateError::Render(_))
+        ));
+    }
+
+    #[test]
+    fn caches_unless_hot_reload() {
+        let dir = TestDir::new(&[("page.html", "v1")]);
+        let cached = dir.templates(false);
+        let hot = dir.templates(true);
+        assert_eq!(cached.render("page.html", &json!({})).unwrap(), "v1");
+        assert_eq!(hot.render("page.html", &json!({})).unwrap(), "v1");
+
+        dir.write("page.html", "v2");
+        // 修改时间的精度可能只有一秒，确保它确实变了
+        let later = SystemTime::now() + std::time::Duration::from_secs(2);
+        fs::File::options()
+            .write(true)
+            .open(dir.0.join("page.html"))
+            .and_then(|file| file.set_modified(later))
+            .unwrap();
+
+        assert_eq!(cached.render("page.html", &json!({})).unwrap(), "v1");
+        assert_eq!(hot.render("page.html", &json!({})).unwrap(), "v2");
+    }
+}
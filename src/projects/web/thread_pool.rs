@@ -0,0 +1,106 @@
+// projects/web/thread_pool.rs - 固定大小的线程池
+// 每个连接交给空闲的工作线程处理，避免为每个连接都创建新线程
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: Option<mpsc::Sender<Job>>,
+    // 已提交但还没有被工作线程取走的任务数
+    queued: Arc<AtomicUsize>,
+}
+
+impl ThreadPool {
+    // 创建线程池，size 为工作线程数量，必须大于 0
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0, "线程池大小必须大于 0");
+
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let queued = Arc::new(AtomicUsize::new(0));
+
+        let workers = (0..size)
+            .map(|id| Worker::new(id, Arc::clone(&receiver), Arc::clone(&queued)))
+            .collect();
+
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+            queued,
+        }
+    }
+
+    // 队列深度计数器，用于监控指标
+    pub fn queue_depth(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.queued)
+    }
+
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            self.queued.fetch_add(1, Ordering::Relaxed);
+            // 只有所有工作线程都已退出时发送才会失败，此时任务无法执行，直接丢弃
+            if sender.send(Box::new(f)).is_err() {
+                self.queued.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    // 关闭通道让工作线程退出循环，然后等待它们结束
+    fn drop(&mut self) {
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+struct Worker {
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(
+        id: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+        queued: Arc<AtomicUsize>,
+    ) -> Worker {
+        let thread = thread::Builder::new()
+            .name(format!("worker-{}", id))
+            .spawn(move || {
+                loop {
+                    // 取到任务后立刻释放锁，让其他线程也能取任务
+                    let message = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => break,
+                    };
+
+                    match message {
+                        // 任务 panic 时只丢弃这个任务，工作线程继续服务后续连接
+                        Ok(job) => {
+                            queued.fetch_sub(1, Ordering::Relaxed);
+                            let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                        }
+                        Err(_) => break, // 通道已关闭
+                    }
+                }
+            })
+            .expect("无法创建工作线程");
+
+        Worker {
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+// projects/web/upload.rs - 练习提交（文件上传示例）
+//   GET  /upload   上传页面，并列出本人提交过的文件
+//   POST /upload   multipart 表单：exercise 字段（可选）+ file 文件
+//   POST /upload/forget   清除本人的提交记录（删除会话）
+//
+// 每个学习者自己提交过哪些文件保存在会话中（见 session.rs），页面上只列出这些文件；
+// 上传目录中其他学习者的文件名不会显示
+//
+// 上传的文件名来自浏览器，完全不可信：只保留最后一段文件名，只允许安全的字符和扩展名
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::form;
+use super::http::{HttpError, Request, Response, StatusCode};
+use super::router::Router;
+use super::template::Templates;
+
+// 单个文件的大小上限（整个请求的上限见 http.rs）
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+// 普通字段的大小上限
+const MAX_FIELD_BYTES: u64 = 1024;
+// 文件名最大长度
+const MAX_FILENAME_LEN: usize = 100;
+// 允许提交的文件类型：练习答案是源代码或文本
+const ALLOWED_EXTENSIONS: [&str; 4] = ["rs", "txt", "md", "toml"];
+
+// 只保留字母、数字、'-'、'_'、'.'，其余字符（包括路径分隔符）替换为 '_'
+fn clean(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// 清理文件名：
+// - 去掉路径部分（浏览器可能发送 "C:\Users\me\main.rs" 或 "../../main.rs"）
+// - 不能以 '.' 开头（隐藏文件、".."），扩展名必须在允许列表中
+fn sanitize_filename(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or("");
+    let cleaned = clean(name);
+
+    if cleaned.is_empty() || cleaned.starts_with('.') || cleaned.len() > MAX_FILENAME_LEN {
+        return None;
+    }
+    let extension = Path::new(&cleaned)
+        .extension()?
+        .to_str()?
+        .to_ascii_lowercase();
+    ALLOWED_EXTENSIONS
+        .contains(&extension.as_str())
+        .then_some(cleaned)
+}
+
+// 与 project_cli 的 create_file 做同样的检查：写入前检查文件是否已存在，写入后验证内容
+// 命令行工具会警告后覆盖；网页无法让用户确认，所以已存在时直接拒绝
+// create_new 在同一个系统调用中完成检查和创建，不会有竞争
+fn save_part(
+    multipart: &mut form::Multipart<'_>,
+    path: &Path,
+) -> Result<u64, (StatusCode, String)> {
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            return Err((
+                StatusCode::CONFLICT,
+                "同名文件已存在，请换一个文件名".to_string(),
+            ));
+        }
+        Err(e) => return Err(server_error(&e.to_string())),
+    };
+
+    let written = match multipart.read_to(&mut file, MAX_FILE_BYTES) {
+        Ok(written) => written,
+        Err(e) => {
+            // 写了一半的文件没有用，删除
+            drop(file);
+            let _ = fs::remove_file(path);
+            return Err(match e {
+                HttpError::PayloadTooLarge => (
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    format!("文件不能超过 {} KB", MAX_FILE_BYTES / 1024),
+                ),
+                other => (StatusCode::BAD_REQUEST, other.to_string()),
+            });
+        }
+    };
+
+    // 验证写入的大小
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() == written => Ok(written),
+        Ok(_) => Err(server_error("文件写入不完整")),
+        Err(e) => Err(server_error(&e.to_string())),
+    }
+}
+
+fn server_error(message: &str) -> (StatusCode, String) {
+    log_error!("❌ 保存上传文件失败: {}", message);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "保存文件失败".to_string(),
+    )
+}
+
+// 处理上传表单，成功时返回保存的文件名和大小
+fn receive(dir: &Path, request: &Request) -> Result<(String, u64), (StatusCode, String)> {
+    let mut multipart = form::multipart(request)
+        .map_err(|response| (response.status, "请使用文件上传表单提交".to_string()))?;
+    let bad_request = |e: HttpError| (StatusCode::BAD_REQUEST, e.to_string());
+
+    let mut exercise = String::new();
+    while let Some(part) = multipart.next_part().map_err(bad_request)? {
+        match (part.name.as_str(), part.filename) {
+            ("exercise", None) => {
+                exercise = multipart.read_text(MAX_FIELD_BYTES).map_err(bad_request)?;
+            }
+            ("file", Some(filename)) => {
+                let filename = sanitize_filename(&filename).ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!(
+                            "无效的文件名，只接受 .{} 文件",
+                            ALLOWED_EXTENSIONS.join("、.")
+                        ),
+                    )
+                })?;
+                // 练习编号作为文件名前缀，方便区分；它同样来自用户，也要清理
+                let prefix = clean(exercise.trim());
+                let prefix = prefix.trim_start_matches('.');
+                let filename = if prefix.is_empty() || prefix.len() > MAX_FILENAME_LEN {
+                    filename
+                } else {
+                    format!("{}_{}", prefix, filename)
+                };
+
+                fs::create_dir_all(dir).map_err(|e| server_error(&e.to_string()))?;
+                let size = save_part(&mut multipart, &dir.join(&filename))?;
+                return Ok((filename, size));
+            }
+            // 其他字段直接跳过
+            _ => {}
+        }
+    }
+    Err((StatusCode::BAD_REQUEST, "没有选择文件".to_string()))
+}
+
+// 会话中记录的本人提交
+const SUBMISSIONS_KEY: &str = "submissions";
+
+fn my_submissions(request: &Request) -> Vec<String> {
+    request
+        .session
+        .as_ref()
+        .and_then(|session| session.get(SUBMISSIONS_KEY))
+        .unwrap_or_default()
+}
+
+fn render(
+    templates: &Templates,
+    request: &Request,
+    status: StatusCode,
+    message: Option<String>,
+    error: Option<String>,
+) -> Response {
+    templates.page(
+        status,
+        "upload.html",
+        &json!({
+            "message": message,
+            "error": error,
+            "mine": my_submissions(request),
+            "max_kb": MAX_FILE_BYTES / 1024,
+            "extensions": ALLOWED_EXTENSIONS.map(|ext| format!(".{}", ext)).join(","),
+        }),
+    )
+}
+
+fn submit(dir: &Path, templates: &Templates, request: &Request) -> Response {
+    match receive(dir, request) {
+        Ok((filename, size)) => {
+            log_info!("📥 收到练习提交: {} ({} 字节)", filename, size);
+            let message = format!("已保存 {}（{} 字节）", filename, size);
+            if let Some(session) = &request.session {
+                let mut mine = my_submissions(request);
+                mine.push(filename);
+                session.insert(SUBMISSIONS_KEY, &mine);
+            }
+            render(templates, request, StatusCode::CREATED, Some(message), None)
+        }
+        Err((status, error)) => render(templates, request, status, None, Some(error)),
+    }
+}
+
+// 注册上传路由，文件保存在 dir 目录中
+pub fn register(router: &mut Router, dir: PathBuf, templates: Arc<Templates>) {
+    let tpl = Arc::clone(&templates);
+    router.get("/upload", move |req| {
+        render(&tpl, req, StatusCode::OK, None, None)
+    });
+
+    router.post("/upload", move |req| submit(&dir, &templates, req));
+
+    // 只删除提交记录，已保存的文件保留
+    router.post("/upload/forget", |req| {
+        if let Some(session) = &req.session {
+            session.destroy();
+        }
+        Response::new(StatusCode::SEE_OTHER).with_header("Location", "/upload")
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn sanitizes_filenames() {
+        assert_eq!(sanitize_filename("main.rs").as_deref(), Some("main.rs"));
+        assert_eq!(
+            sanitize_filename("../../main.rs").as_deref(),
+            Some("main.rs")
+        );
+        assert_eq!(
+            sanitize_filename("C:\\Users\\me\\My Notes.MD").as_deref(),
+            Some("My_Notes.MD")
+        );
+        assert_eq!(sanitize_filename("答案.rs").as_deref(), Some("__.rs"));
+        assert_eq!(sanitize_filename(".."), None);
+        assert_eq!(sanitize_filename(".hidden.rs"), None);
+        assert_eq!(sanitize_filename("dir/"), None);
+        assert_eq!(sanitize_filename("run.sh"), None);
+        assert_eq!(sanitize_filename("Makefile"), None);
+        let long = format!("{}.rs", "a".repeat(MAX_FILENAME_LEN));
+        assert_eq!(sanitize_filename(&long), None);
+    }
+
+    fn upload(exercise: &str, filename: &str, content: &[u8]) -> Request {
+        let mut body = format!(
+            "--b\r\nContent-Disposition: form-data; name=\"exercise\"\r\n\r\n{}\r\n\
+             --b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\r\n",
+            exercise, filename
+        )
+        .into_bytes();
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n--b--\r\n");
+
+        let mut raw = format!(
+            "POST /upload HTTP/1.1\r\nContent-Type: multipart/form-data; boundary=b\r\n\
+             Content-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(&body);
+        Request::read_from(&mut &raw[..]).unwrap().unwrap()
+    }
+
+    #[test]
+    fn saves_with_cleaned_exercise_prefix() {
+        let dir = env::temp_dir().join(format!("rust-web-upload-test-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let (name, size) =
+            receive(&dir, &upload("../own ership", "a/b.rs", b"fn main() {}")).unwrap();
+        assert_eq!((name.as_str(), size), ("_own_ership_b.rs", 12));
+        assert_eq!(fs::read(dir.join(&name)).unwrap(), b"fn main() {}");
+
+        // 同名文件不会被覆盖
+        let (status, _) = receive(&dir, &upload("../own ership", "b.rs", b"other")).unwrap_err();
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(fs::read(dir.join(&name)).unwrap(), b"fn main() {}");
+
+        // 超过大小上限的文件不会留下写了一半的内容
+        let big = vec![b'x'; MAX_FILE_BYTES as usize + 1];
+        let (status, _) = receive(&dir, &upload("", "big.txt", &big)).unwrap_err();
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!dir.join("big.txt").exists());
+
+        let (status, _) = receive(&dir, &upload("", "evil.exe", b"MZ")).unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+// projects/web/vhost.rs - 虚拟主机
+// 同一个地址和端口上按 Host 请求头区分不同的站点：
+//
+//   Host: docs.localhost      → 反向代理到 127.0.0.1:8001
+//   Host: *.demo.localhost    → 任意子域名，例如 a.demo.localhost
+//   其他 Host                 → 默认站点（本服务器自己的路由表）
+//
+// 浏览器会把 *.localhost 解析到本机，不需要修改 hosts 文件；用 curl 时可以手动指定：
+//   curl -H 'Host: docs.localhost' http://127.0.0.1:7878/
+
+use super::http::{Request, Response};
+use super::middleware::{Middleware, Next};
+use super::router::Router;
+
+// 一个站点：自己的路由表，或者转发到其他服务器的反向代理
+pub trait Site: Send + Sync {
+    fn handle(&self, request: &mut Request) -> Response;
+}
+
+impl Site for Router {
+    fn handle(&self, request: &mut Request) -> Response {
+        Router::handle(self, request)
+    }
+}
+
+enum HostPattern {
+    Exact(String),
+    // "*.example.com" 匹配 "a.example.com"、"a.b.example.com"，不匹配 "example.com"
+    Subdomain(String),
+}
+
+impl HostPattern {
+    fn parse(pattern: &str) -> HostPattern {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => HostPattern::Subdomain(format!(".{}", domain)),
+            None => HostPattern::Exact(pattern),
+        }
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            HostPattern::Exact(name) => host == name,
+            HostPattern::Subdomain(suffix) => host.len() > suffix.len() && host.ends_with(suffix),
+        }
+    }
+}
+
+// 从 Host 头中取出主机名：转为小写，去掉端口和末尾的 '.'
+//   "Docs.Localhost:7878" → "docs.localhost"
+//   "[::1]:7878"          → "[::1]"
+pub fn request_host(request: &Request) -> Option<String> {
+    let host = request.header("Host")?.trim();
+    let name = match host.rfind(':') {
+        Some(i) if !host[i..].contains(']') => &host[..i],
+        _ => host,
+    };
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    (!name.is_empty()).then_some(name)
+}
+
+// 先添加的站点优先匹配；没有匹配的请求交给内层（默认站点）
+pub struct VirtualHosts {
+    sites: Vec<(HostPattern, Box<dyn Site>)>,
+}
+
+impl VirtualHosts {
+    pub fn new() -> VirtualHosts {
+        VirtualHosts { sites: Vec::new() }
+    }
+
+    pub fn add<S: Site + 'static>(&mut self, host: &str, site: S) -> &mut VirtualHosts {
+        self.sites.push((HostPattern::parse(host), Box::new(site)));
+        self
+    }
+
+    fn find(&self, host: &str) -> Option<&dyn Site> {
+        self.sites
+            .iter()
+            .find(|(pattern, _)| pattern.matches(host))
+            .map(|(_, site)| site.as_ref())
+    }
+}
+
+impl Middleware for VirtualHosts {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        match request_host(request).and_then(|host| self.find(&host)) {
+            Some(site) => site.handle(request),
+            None => next.run(request),
+        }
+    }
+}
@@ -0,0 +1,721 @@
+// projects/web/websocket.rs - WebSocket (RFC 6455)
+// 握手：客户端发送带 Sec-WebSocket-Key 的 GET 请求，服务器返回 101 和 Sec-WebSocket-Accept
+// 之后双方通过帧 (frame) 通信：
+//
+//    0                   1                   2                   3
+//   +-+-+-+-+-------+-+-------------+-------------------------------+
+//   |F|R|R|R| opcode|M| Payload len |    Extended payload length    |
+//   |I|S|S|S|  (4)  |A|     (7)     |           (16/64)             |
+//   |N|V|V|V|       |S|             |                               |
+//   +-+-+-+-+-------+-+-------------+-------------------------------+
+//   |                  Masking-key (客户端发出的帧必须有)            |
+//   +---------------------------------------------------------------+
+//   |                          Payload Data                         |
+//   +---------------------------------------------------------------+
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, mpsc};
+use std::thread;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha1::{Digest, Sha1};
+
+use super::http::{Request, Response, StatusCode, Upgraded};
+use super::router::Router;
+use super::template::Templates;
+
+// 计算 Sec-WebSocket-Accept 时拼接在 key 后面的固定 GUID
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+// 单条消息（包括所有分片）的最大长度
+const MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+// 每个连接占用一个线程（聊天室中还有一个写线程），同时连接数按工作线程数量的倍数限制
+const CONNECTIONS_PER_WORKER: usize = 8;
+// 对方一直不读数据时，写操作最多等待这么久，之后当作连接已断开
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+// 聊天室中每个成员最多积压的消息数；写不过来的成员会被断开，不会拖慢其他成员
+const OUTBOX_SIZE: usize = 64;
+
+// ========== 消息与错误 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    // 关闭码和原因，对方没有给出关闭码时为 None
+    Close(Option<(u16, String)>),
+}
+
+// 关闭码
+const CLOSE_NORMAL: u16 = 1000;
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+const CLOSE_INVALID_DATA: u16 = 1007;
+const CLOSE_TOO_BIG: u16 = 1009;
+
+#[derive(Debug)]
+pub enum WsError {
+    Io(io::Error),
+    // 对方违反协议，附带应发送的关闭码
+    Protocol(u16, &'static str),
+    // 连接已经关闭
+    Closed,
+}
+
+impl fmt::Display for WsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WsError::Io(e) => write!(f, "IO 错误: {}", e),
+            WsError::Protocol(code, msg) => write!(f, "协议错误 ({}): {}", code, msg),
+            WsError::Closed => write!(f, "连接已关闭"),
+        }
+    }
+}
+
+impl std::error::Error for WsError {}
+
+impl From<io::Error> for WsError {
+    fn from(e: io::Error) -> Self {
+        WsError::Io(e)
+    }
+}
+
+// ========== 帧 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl Opcode {
+    fn from_u8(b: u8) -> Option<Opcode> {
+        match b {
+            0x0 => Some(Opcode::Continuation),
+            0x1 => Some(Opcode::Text),
+            0x2 => Some(Opcode::Binary),
+            0x8 => Some(Opcode::Close),
+            0x9 => Some(Opcode::Ping),
+            0xA => Some(Opcode::Pong),
+            _ => None,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Opcode::Continuation => 0x0,
+            Opcode::Text => 0x1,
+            Opcode::Binary => 0x2,
+            Opcode::Close => 0x8,
+            Opcode::Ping => 0x9,
+            Opcode::Pong => 0xA,
+        }
+    }
+
+    fn is_control(self) -> bool {
+        matches!(self, Opcode::Close | Opcode::Ping | Opcode::Pong)
+    }
+}
+
+struct Frame {
+    fin: bool,
+    opcode: Opcode,
+    payload: Vec<u8>,
+}
+
+// 读取一个客户端帧并去掉掩码
+fn read_frame<R: Read>(reader: &mut R) -> Result<Frame, WsError> {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head)?;
+
+    let fin = head[0] & 0x80 != 0;
+    if head[0] & 0x70 != 0 {
+        return Err(WsError::Protocol(
+            CLOSE_PROTOCOL_ERROR,
+            "未协商扩展却设置了 RSV 位",
+        ));
+    }
+    let opcode = Opcode::from_u8(head[0] & 0x0F)
+        .ok_or(WsError::Protocol(CLOSE_PROTOCOL_ERROR, "未知的 opcode"))?;
+
+    let masked = head[1] & 0x80 != 0;
+    if !masked {
+        return Err(WsError::Protocol(
+            CLOSE_PROTOCOL_ERROR,
+            "客户端帧必须带掩码",
+        ));
+    }
+
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut buf = [0; 2];
+            reader.read_exact(&mut buf)?;
+            u64::from(u16::from_be_bytes(buf))
+        }
+        127 => {
+            let mut buf = [0; 8];
+            reader.read_exact(&mut buf)?;
+            u64::from_be_bytes(buf)
+        }
+        n => u64::from(n),
+    };
+
+    if opcode.is_control() && (len > 125 || !fin) {
+        return Err(WsError::Protocol(
+            CLOSE_PROTOCOL_ERROR,
+            "控制帧不能分片且不能超过 125 字节",
+        ));
+    }
+    if len > MAX_MESSAGE_BYTES as u64 {
+        return Err(WsError::Protocol(CLOSE_TOO_BIG, "消息过大"));
+    }
+
+    let mut mask = [0; 4];
+    reader.read_exact(&mut mask)?;
+
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok(Frame {
+        fin,
+        opcode,
+        payload,
+    })
+}
+
+// 写出一个服务器帧：服务器发出的帧不带掩码
+fn write_frame<W: Write>(writer: &mut W, opcode: Opcode, payload: &[u8]) -> io::Result<()> {
+    let mut head = Vec::with_capacity(10);
+    head.push(0x80 | opcode.as_u8());
+    match payload.len() {
+        n if n < 126 => head.push(n as u8),
+        n if n <= u16::MAX as usize => {
+            head.push(126);
+            head.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        n => {
+            head.push(127);
+            head.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    }
+    writer.write_all(&head)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+// ========== 连接 ==========
+
+// 发送端可以克隆并交给其他线程，用于广播等场景
+#[derive(Clone)]
+pub struct WsSender {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+impl WsSender {
+    pub fn send(&self, message: &Message) -> io::Result<()> {
+        let (opcode, payload) = match message {
+            Message::Text(text) => (Opcode::Text, text.as_bytes().to_vec()),
+            Message::Binary(data) => (Opcode::Binary, data.clone()),
+            Message::Ping(data) => (Opcode::Ping, data.clone()),
+            Message::Pong(data) => (Opcode::Pong, data.clone()),
+            Message::Close(None) => (Opcode::Close, Vec::new()),
+            Message::Close(Some((code, reason))) => {
+                let mut payload = code.to_be_bytes().to_vec();
+                payload.extend_from_slice(reason.as_bytes());
+                (Opcode::Close, payload)
+            }
+        };
+
+        // 整帧在锁内写出，保证多个线程同时发送时帧不会交错
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        write_frame(&mut *stream, opcode, &payload)
+    }
+
+    // 直接关闭底层连接，不发送关闭帧；读线程会随之收到错误并结束
+    pub fn shutdown(&self) {
+        let stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+pub struct WebSocket {
+    reader: BufReader<Upgraded>,
+    sender: WsSender,
+    // 正在接收的分片消息：(首帧 opcode, 已收到的数据)
+    partial: Option<(Opcode, Vec<u8>)>,
+    closed: bool,
+}
+
+impl WebSocket {
+    fn new(upgraded: Upgraded) -> io::Result<WebSocket> {
+        // WebSocket 是长连接，取消 HTTP 阶段设置的空闲超时；
+        // 写操作仍然要有超时，否则一个不读数据的客户端会让发送方一直阻塞
+        upgraded.stream().set_read_timeout(None)?;
+        upgraded.stream().set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let stream = upgraded.stream().try_clone()?;
+        Ok(WebSocket {
+            reader: BufReader::new(upgraded),
+            sender: WsSender {
+                stream: Arc::new(Mutex::new(stream)),
+            },
+            partial: None,
+            closed: false,
+        })
+    }
+
+    pub fn sender(&self) -> WsSender {
+        self.sender.clone()
+    }
+
+    pub fn send(&self, message: &Message) -> io::Result<()> {
+        self.sender.send(message)
+    }
+
+    // 接收下一条完整消息
+    // - 分片消息会被拼接后整体返回
+    // - 收到 Ping 时自动回复 Pong
+    // - 收到 Close 时自动回复 Close，之后再调用返回 WsError::Closed
+    // - 协议错误时先向对方发送对应的关闭码
+    pub fn recv(&mut self) -> Result<Message, WsError> {
+        if self.closed {
+            return Err(WsError::Closed);
+        }
+        match self.read_message() {
+            Err(WsError::Protocol(code, reason)) => {
+                self.closed = true;
+                let _ = self.send(&Message::Close(Some((code, reason.to_string()))));
+                Err(WsError::Protocol(code, reason))
+            }
+            other => other,
+        }
+    }
+
+    fn read_message(&mut self) -> Result<Message, WsError> {
+        loop {
+            let frame = read_frame(&mut self.reader)?;
+
+            match frame.opcode {
+                // 控制帧可以夹在分片消息中间
+                Opcode::Ping => {
+                    self.send(&Message::Pong(frame.payload.clone()))?;
+                    return Ok(Message::Ping(frame.payload));
+                }
+                Opcode::Pong => return Ok(Message::Pong(frame.payload)),
+                Opcode::Close => {
+                    self.closed = true;
+                    let close = parse_close_payload(&frame.payload)?;
+                    let reply = close
+                        .as_ref()
+                        .map(|(code, _)| (*code, String::new()))
+                        .unwrap_or((CLOSE_NORMAL, String::new()));
+                    let _ = self.send(&Message::Close(Some(reply)));
+                    return Ok(Message::Close(close));
+                }
+                Opcode::Text | Opcode::Binary => {
+                    if self.partial.is_some() {
+                        return Err(WsError::Protocol(
+                            CLOSE_PROTOCOL_ERROR,
+                            "上一条分片消息尚未结束",
+                        ));
+                    }
+                    if frame.fin {
+                        return to_message(frame.opcode, frame.payload);
+                    }
+                    self.partial = Some((frame.opcode, frame.payload));
+                }
+                Opcode::Continuation => {
+                    let Some((opcode, mut data)) = self.partial.take() else {
+                        return Err(WsError::Protocol(
+                            CLOSE_PROTOCOL_ERROR,
+                            "没有需要继续的分片消息",
+                        ));
+                    };
+                    if data.len() + frame.payload.len() > MAX_MESSAGE_BYTES {
+                        return Err(WsError::Protocol(CLOSE_TOO_BIG, "消息过大"));
+                    }
+                    data.extend_from_slice(&frame.payload);
+                    if frame.fin {
+                        return to_message(opcode, data);
+                    }
+                    self.partial = Some((opcode, data));
+                }
+            }
+        }
+    }
+}
+
+fn to_message(opcode: Opcode, data: Vec<u8>) -> Result<Message, WsError> {
+    match opcode {
+        Opcode::Text => String::from_utf8(data)
+            .map(Message::Text)
+            .map_err(|_| WsError::Protocol(CLOSE_INVALID_DATA, "文本消息不是合法的 UTF-8")),
+        _ => Ok(Message::Binary(data)),
+    }
+}
+
+fn parse_close_payload(payload: &[u8]) -> Result<Option<(u16, String)>, WsError> {
+    match payload {
+        [] => Ok(None),
+        [_] => Err(WsError::Protocol(CLOSE_PROTOCOL_ERROR, "关闭帧长度错误")),
+        [hi, lo, reason @ ..] => {
+            let reason = String::from_utf8(reason.to_vec())
+                .map_err(|_| WsError::Protocol(CLOSE_INVALID_DATA, "关闭原因不是合法的 UTF-8"))?;
+            Ok(Some((u16::from_be_bytes([*hi, *lo]), reason)))
+        }
+    }
+}
+
+// ========== 握手 ==========
+
+// Sec-WebSocket-Accept = base64(sha1(key + GUID))
+fn accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    BASE64.encode(hasher.finalize())
+}
+
+fn header_has_token(request: &Request, name: &str, token: &str) -> bool {
+    request.header(name).is_some_and(|value| {
+        value
+            .split(',')
+            .any(|t| t.trim().eq_ignore_ascii_case(token))
+    })
+}
+
+// 校验握手请求；成功时返回 101 响应，连接建立后调用 on_connect
+pub fn upgrade<F>(request: &Request, on_connect: F) -> Response
+where
+    F: FnOnce(WebSocket) + Send + 'static,
+{
+    if !header_has_token(request, "Upgrade", "websocket")
+        || !header_has_token(request, "Connection", "upgrade")
+    {
+        return Response::text(StatusCode::UPGRADE_REQUIRED, "需要 WebSocket 握手")
+            .with_header("Upgrade", "websocket");
+    }
+    if request.header("Sec-WebSocket-Version") != Some("13") {
+        return Response::text(StatusCode::UPGRADE_REQUIRED, "只支持 WebSocket 版本 13")
+            .with_header("Sec-WebSocket-Version", "13");
+    }
+    // key 必须是 16 字节随机数的 base64 编码
+    let key = match request.header("Sec-WebSocket-Key") {
+        Some(key) if BASE64.decode(key).is_ok_and(|k| k.len() == 16) => key,
+        _ => return Response::text(StatusCode::BAD_REQUEST, "无效的 Sec-WebSocket-Key"),
+    };
+
+    Response::upgrade(move |upgraded| match WebSocket::new(upgraded) {
+        Ok(ws) => on_connect(ws),
+        Err(e) => log_error!("❌ WebSocket 初始化失败: {}", e),
+    })
+    .with_header("Upgrade", "websocket")
+    .with_header("Connection", "Upgrade")
+    .with_header("Sec-WebSocket-Accept", &accept_key(key))
+}
+
+// ========== 连接数限制 ==========
+
+// 升级后的连接各占一个线程，不受线程池限制；超过上限时不再接受新的握手
+pub struct ConnectionLimit {
+    active: AtomicUsize,
+    max: usize,
+}
+
+// 连接结束（被 drop）时释放名额
+pub struct ConnectionSlot(Arc<ConnectionLimit>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ConnectionLimit {
+    pub fn new(max: usize) -> Arc<ConnectionLimit> {
+        Arc::new(ConnectionLimit {
+            active: AtomicUsize::new(0),
+            max,
+        })
+    }
+
+    fn acquire(self: &Arc<Self>) -> Option<ConnectionSlot> {
+        self.active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < self.max).then_some(n + 1)
+            })
+            .ok()?;
+        Some(ConnectionSlot(Arc::clone(self)))
+    }
+
+    // 有名额时握手，名额随连接一起释放；没有名额时返回 503
+    pub fn upgrade<F>(self: &Arc<Self>, request: &Request, on_connect: F) -> Response
+    where
+        F: FnOnce(WebSocket) + Send + 'static,
+    {
+        let Some(slot) = self.acquire() else {
+            return Response::text(StatusCode::SERVICE_UNAVAILABLE, "连接数过多，请稍后重试")
+                .with_header("Retry-After", "5");
+        };
+        // 握手失败时 101 响应不会发出，闭包随响应一起被 drop，名额同样会释放
+        upgrade(request, move |ws| {
+            let _slot = slot;
+            on_connect(ws);
+        })
+    }
+}
+
+// ========== 示例：回显 ==========
+
+fn echo(mut ws: WebSocket) {
+    loop {
+        let reply = match ws.recv() {
+            Ok(Message::Text(text)) => Message::Text(text),
+            Ok(Message::Binary(data)) => Message::Binary(data),
+            Ok(Message::Ping(_) | Message::Pong(_)) => continue,
+            Ok(Message::Close(_)) | Err(_) => return,
+        };
+        if ws.send(&reply).is_err() {
+            return;
+        }
+    }
+}
+
+// ========== 示例：广播聊天室 ==========
+// 与 14_async.rs 中的通道示例相同的思路：多个生产者（每个连接的读线程）
+// 通过 mpsc 通道把事件发给唯一的消费者（广播线程），由它转发给所有成员。
+// 广播线程不直接写网络：每个成员有一个有界的发件箱和自己的写线程，
+// 慢的客户端只会让自己的发件箱积压，积压满时被移出聊天室并断开连接
+
+enum ChatEvent {
+    Join(u64, mpsc::SyncSender<Message>),
+    Leave(u64),
+    Say(u64, String),
+}
+
+pub struct ChatRoom {
+    events: mpsc::Sender<ChatEvent>,
+    next_id: AtomicU64,
+}
+
+impl ChatRoom {
+    pub fn new() -> ChatRoom {
+        let (events, receiver) = mpsc::channel();
+        thread::spawn(move || run_broadcaster(receiver));
+        ChatRoom {
+            events,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn join(&self, mut ws: WebSocket) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (outbox, messages) = mpsc::sync_channel(OUTBOX_SIZE);
+        let sender = ws.sender();
+        thread::spawn(move || write_outbox(&sender, messages));
+        if self.events.send(ChatEvent::Join(id, outbox)).is_err() {
+            return;
+        }
+
+        loop {
+            match ws.recv() {
+                Ok(Message::Text(text)) => {
+                    if self.events.send(ChatEvent::Say(id, text)).is_err() {
+                        break;
+                    }
+                }
+                Ok(Message::Binary(_) | Message::Ping(_) | Message::Pong(_)) => {}
+                Ok(Message::Close(_)) | Err(_) => break,
+            }
+        }
+
+        let _ = self.events.send(ChatEvent::Leave(id));
+    }
+}
+
+// 成员的写线程：依次发出发件箱中的消息
+// 成员被移出聊天室（发件箱被关闭）或写入失败时关闭连接，读线程随之结束
+fn write_outbox(sender: &WsSender, messages: mpsc::Receiver<Message>) {
+    for message in messages {
+        if sender.send(&message).is_err() {
+            break;
+        }
+    }
+    sender.shutdown();
+}
+
+fn run_broadcaster(receiver: mpsc::Receiver<ChatEvent>) {
+    let mut members: HashMap<u64, mpsc::SyncSender<Message>> = HashMap::new();
+
+    for event in receiver {
+        let text = match event {
+            ChatEvent::Join(id, sender) => {
+                members.insert(id, sender);
+                format!("📢 用户 {} 加入了聊天室（当前 {} 人）", id, members.len())
+            }
+            ChatEvent::Leave(id) => {
+                members.remove(&id);
+                format!("📢 用户 {} 离开了聊天室（当前 {} 人）", id, members.len())
+            }
+            ChatEvent::Say(id, text) => format!("用户 {}: {}", id, text),
+        };
+
+        // 发件箱已满（客户端太慢）或已关闭（连接已断开）时移除成员；
+        // 移除后发件箱被关闭，写线程发完积压的消息就断开连接
+        let message = Message::Text(text);
+        members.retain(|_, outbox| outbox.try_send(message.clone()).is_ok());
+    }
+}
+
+// 注册 WebSocket 示例路由
+//   GET /ws/echo   回显收到的每条消息
+//   GET /ws/chat   广播聊天室
+//   GET /chat      聊天室网页
+// 两个 WebSocket 路由共用一个连接数上限
+pub fn register(router: &mut Router, workers: usize, templates: Arc<Templates>) {
+    let limit = ConnectionLimit::new(workers * CONNECTIONS_PER_WORKER);
+
+    let echo_limit = Arc::clone(&limit);
+    router.get("/ws/echo", move |req| echo_limit.upgrade(req, echo));
+
+    let room = Arc::new(ChatRoom::new());
+    router.get("/ws/chat", move |req| {
+        let room = Arc::clone(&room);
+        limit.upgrade(req, move |ws| room.join(ws))
+    });
+
+    router.get("/chat", move |_| {
+        templates.page(StatusCode::OK, "chat.html", &())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6455 5.7 中的示例：带掩码的单帧文本消息 "Hello"
+    const MASKED_HELLO: [u8; 11] = [
+        0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+    ];
+
+    fn protocol_error(bytes: &[u8]) -> u16 {
+        match read_frame(&mut &bytes[..]) {
+            Err(WsError::Protocol(code, _)) => code,
+            Err(e) => panic!("应当是协议错误: {}", e),
+            Ok(_) => panic!("应当是协议错误"),
+        }
+    }
+
+    #[test]
+    fn unmasks_client_frame() {
+        let frame = read_frame(&mut &MASKED_HELLO[..]).unwrap();
+        assert!(frame.fin);
+        assert_eq!(frame.opcode, Opcode::Text);
+        assert_eq!(frame.payload, b"Hello");
+    }
+
+    #[test]
+    fn reads_extended_length() {
+        let payload = vec![b'a'; 300];
+        let mut bytes = vec![0x82, 0x80 | 126];
+        bytes.extend_from_slice(&300u16.to_be_bytes());
+        // 全零掩码不改变数据
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&payload);
+        let frame = read_frame(&mut &bytes[..]).unwrap();
+        assert_eq!(frame.opcode, Opcode::Binary);
+        assert_eq!(frame.payload, payload);
+    }
+
+    #[test]
+    fn rejects_invalid_frames() {
+        // 没有掩码
+        assert_eq!(protocol_error(&[0x81, 0x05]), CLOSE_PROTOCOL_ERROR);
+        // 设置了 RSV1
+        assert_eq!(protocol_error(&[0xC1, 0x80]), CLOSE_PROTOCOL_ERROR);
+        // 未知 opcode
+        assert_eq!(protocol_error(&[0x83, 0x80]), CLOSE_PROTOCOL_ERROR);
+        // 分片的 ping
+        assert_eq!(protocol_error(&[0x09, 0x80]), CLOSE_PROTOCOL_ERROR);
+        // 超过 125 字节的控制帧
+        assert_eq!(
+            protocol_error(&[0x89, 0x80 | 126, 0, 126]),
+            CLOSE_PROTOCOL_ERROR
+        );
+        // 超过消息大小上限
+        let mut too_big = vec![0x82, 0x80 | 127];
+        too_big.extend_from_slice(&(MAX_MESSAGE_BYTES as u64 + 1).to_be_bytes());
+        assert_eq!(protocol_error(&too_big), CLOSE_TOO_BIG);
+    }
+
+    #[test]
+    fn writes_unmasked_server_frames() {
+        let mut out = Vec::new();
+        write_frame(&mut out, Opcode::Text, b"Hello").unwrap();
+        assert_eq!(out, [0x81, 0x05, b'H', b'e', b'l', b'l', b'o']);
+
+        let mut out = Vec::new();
+        write_frame(&mut out, Opcode::Binary, &[0; 256]).unwrap();
+        assert_eq!(out[..4], [0x82, 126, 0x01, 0x00]);
+        assert_eq!(out.len(), 4 + 256);
+    }
+
+    #[test]
+    fn parses_close_payload() {
+        assert_eq!(parse_close_payload(&[]).unwrap(), None);
+        assert_eq!(
+            parse_close_payload(&[0x03, 0xE8, b'b', b'y', b'e']).unwrap(),
+            Some((CLOSE_NORMAL, "bye".to_string()))
+        );
+        assert!(parse_close_payload(&[0x03]).is_err());
+    }
+
+    #[test]
+    fn computes_accept_key() {
+        // RFC 6455 1.3 中的示例
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn handshake_validates_request() {
+        let request = |headers: &str| {
+            let raw = format!("GET /ws/echo HTTP/1.1\r\n{}\r\n", headers);
+            Request::read_from(&mut raw.as_bytes()).unwrap().unwrap()
+        };
+
+        let ok = request(
+            "Upgrade: websocket\r\nConnection: keep-alive, Upgrade\r\n\
+             Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n",
+        );
+        let response = upgrade(&ok, |_| {});
+        assert_eq!(response.status, StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(
+            response.header("Sec-WebSocket-Accept"),
+            Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+        );
+
+        let plain = request("");
+        assert_eq!(upgrade(&plain, |_| {}).status, StatusCode::UPGRADE_REQUIRED);
+
+        let short_key = request(
+            "Upgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: c2hvcnQ=\r\n",
+        );
+        assert_eq!(upgrade(&short_key, |_| {}).status, StatusCode::BAD_REQUEST);
+    }
+}